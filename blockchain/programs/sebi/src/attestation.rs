@@ -0,0 +1,144 @@
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::ed25519_program;
+use anchor_lang::solana_program::sysvar::instructions::{load_current_index_checked, load_instruction_at_checked};
+use crate::state::Market;
+use crate::errors::MarketError;
+
+/// Message the market's price_updater signs: borsh of the fields below, 56
+/// bytes little endian. `market` pins it to one market; it is accepted while
+/// now <= expiry, and may be reused until then.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, Debug)]
+pub struct PriceAttestation {
+    pub market: Pubkey,
+    pub price: u128,
+    pub expiry: i64,
+}
+
+impl PriceAttestation {
+    // market pubkey = 32, price u128 = 16, expiry i64 = 8
+    pub const LEN: usize = 32 + 16 + 8;
+}
+
+// Ed25519 program data: num_signatures u8, padding u8, then per signature
+// seven u16 fields: signature offset/ix index, public key offset/ix index,
+// message offset/size/ix index
+const OFFSETS_START: usize = 2;
+const OFFSETS_LEN: usize = 14;
+// ix index meaning "this instruction"
+const CURRENT_IX: u16 = u16::MAX;
+
+fn read_u16(data: &[u8], at: usize) -> Result<u16> {
+    data.get(at..at + 2)
+        .map(|b| u16::from_le_bytes([b[0], b[1]]))
+        .ok_or(error!(MarketError::InvalidAttestation))
+}
+
+/// Signer and message of an Ed25519 instruction holding exactly one
+/// signature whose key and message are inline. The Ed25519 program has
+/// already verified the signature by the time this instruction runs.
+pub fn parse_ed25519(data: &[u8]) -> Result<(Pubkey, &[u8])> {
+    if data.len() < OFFSETS_START + OFFSETS_LEN || data[0] != 1 {
+        return err!(MarketError::InvalidAttestation);
+    }
+    let field = |i: usize| read_u16(data, OFFSETS_START + 2 * i);
+    let (sig_ix, key_offset, key_ix) = (field(1)?, field(2)? as usize, field(3)?);
+    let (msg_offset, msg_size, msg_ix) = (field(4)? as usize, field(5)? as usize, field(6)?);
+    if sig_ix != CURRENT_IX || key_ix != CURRENT_IX || msg_ix != CURRENT_IX {
+        return err!(MarketError::InvalidAttestation);
+    }
+
+    let key = data.get(key_offset..key_offset + 32).ok_or(MarketError::InvalidAttestation)?;
+    let message = data.get(msg_offset..msg_offset + msg_size).ok_or(MarketError::InvalidAttestation)?;
+    let key = Pubkey::try_from(key).map_err(|_| MarketError::InvalidAttestation)?;
+    Ok((key, message))
+}
+
+/// Price from a PriceAttestation carried by the Ed25519 instruction right
+/// before the current one, checked against the market's signer, expiry and
+/// the same price rules update_price enforces.
+pub fn verified_price(instructions: &AccountInfo, market: &Account<Market>, now: i64) -> Result<u128> {
+    // markets without an updater accept no attestations at all
+    if market.price_updater == Pubkey::default() {
+        return err!(MarketError::InvalidAttestation);
+    }
+    let current = load_current_index_checked(instructions)?;
+    let previous = current.checked_sub(1).ok_or(MarketError::InvalidAttestation)?;
+    let ix = load_instruction_at_checked(previous as usize, instructions)?;
+    if ix.program_id != ed25519_program::ID {
+        return err!(MarketError::InvalidAttestation);
+    }
+
+    let (signer, message) = parse_ed25519(&ix.data)?;
+    if signer != market.price_updater || message.len() != PriceAttestation::LEN {
+        return err!(MarketError::InvalidAttestation);
+    }
+    let attestation = PriceAttestation::try_from_slice(message).map_err(|_| MarketError::InvalidAttestation)?;
+    if attestation.market != market.key() {
+        return err!(MarketError::InvalidAttestation);
+    }
+    if now > attestation.expiry {
+        return err!(MarketError::AttestationExpired);
+    }
+
+    if market.auction_enabled {
+        return err!(MarketError::AuctionInProgress);
+    }
+    if market.price_frozen {
+        return err!(MarketError::PriceFrozen);
+    }
+    if !market.is_on_tick(attestation.price) {
+        return err!(MarketError::InvalidTick);
+    }
+    Ok(attestation.price)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // layout produced by web3.js Ed25519Program.createInstructionWithPublicKey
+    fn ed25519_data(key: &Pubkey, message: &[u8], indices: u16) -> Vec<u8> {
+        let key_offset = (OFFSETS_START + OFFSETS_LEN) as u16;
+        let sig_offset = key_offset + 32;
+        let msg_offset = sig_offset + 64;
+        let mut data = vec![1u8, 0];
+        for v in [sig_offset, indices, key_offset, indices, msg_offset, message.len() as u16, indices] {
+            data.extend_from_slice(&v.to_le_bytes());
+        }
+        data.extend_from_slice(key.as_ref());
+        data.extend_from_slice(&[0u8; 64]);
+        data.extend_from_slice(message);
+        data
+    }
+
+    fn is_invalid(err: Error) -> bool {
+        matches!(err, Error::AnchorError(e) if e.error_name == "InvalidAttestation")
+    }
+
+    #[test]
+    fn parses_inline_signature() {
+        let key = Pubkey::new_unique();
+        let attestation = PriceAttestation { market: Pubkey::new_unique(), price: 1_250_000, expiry: 99 };
+        let message = attestation.try_to_vec().unwrap();
+        assert_eq!(message.len(), PriceAttestation::LEN);
+
+        let data = ed25519_data(&key, &message, CURRENT_IX);
+        let (signer, parsed) = parse_ed25519(&data).unwrap();
+        assert_eq!(signer, key);
+        assert_eq!(PriceAttestation::try_from_slice(parsed).unwrap(), attestation);
+    }
+
+    #[test]
+    fn rejects_data_outside_the_instruction() {
+        let key = Pubkey::new_unique();
+        assert!(is_invalid(parse_ed25519(&ed25519_data(&key, b"msg", 0)).unwrap_err()));
+
+        let mut data = ed25519_data(&key, b"msg", CURRENT_IX);
+        data[0] = 2;
+        assert!(is_invalid(parse_ed25519(&data).unwrap_err()));
+
+        let data = ed25519_data(&key, b"msg", CURRENT_IX);
+        assert!(is_invalid(parse_ed25519(&data[..data.len() - 1]).unwrap_err()));
+        assert!(is_invalid(parse_ed25519(&data[..10]).unwrap_err()));
+    }
+}
@@ -0,0 +1,58 @@
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::instruction::{get_stack_height, TRANSACTION_LEVEL_STACK_HEIGHT};
+use anchor_lang::solana_program::sysvar::instructions::{load_current_index_checked, load_instruction_at_checked};
+use crate::errors::MarketError;
+
+/// Program whose top-level instruction led here: Pubkey::default() (the
+/// system program id) when a wallet called this program directly,
+/// otherwise the router or aggregator at the root of the CPI chain. Only
+/// CPI trades read the instructions sysvar, so only they must pass it.
+pub fn via_program(instructions: Option<&AccountInfo>) -> Result<Pubkey> {
+    root_program(get_stack_height(), instructions)
+}
+
+fn root_program(stack_height: usize, instructions: Option<&AccountInfo>) -> Result<Pubkey> {
+    if stack_height <= TRANSACTION_LEVEL_STACK_HEIGHT {
+        return Ok(Pubkey::default());
+    }
+    let instructions = instructions.ok_or(MarketError::InstructionsSysvarRequired)?;
+    let index = load_current_index_checked(instructions)?;
+    Ok(load_instruction_at_checked(index as usize, instructions)?.program_id)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use anchor_lang::solana_program::sysvar::instructions::{construct_instructions_data, BorrowedInstruction, ID};
+
+    // sysvar data for a transaction of top-level instructions to `programs`, executing `current`
+    fn sysvar_data(programs: &[Pubkey], current: u16) -> Vec<u8> {
+        let ixs: Vec<BorrowedInstruction> = programs
+            .iter()
+            .map(|program_id| BorrowedInstruction { program_id, accounts: Vec::new(), data: &[] })
+            .collect();
+        let mut data = construct_instructions_data(&ixs);
+        // the runtime keeps the executing index in the last two bytes
+        let at = data.len() - 2;
+        data[at..].copy_from_slice(&current.to_le_bytes());
+        data
+    }
+
+    #[test]
+    fn direct_calls_need_no_sysvar() {
+        assert_eq!(root_program(TRANSACTION_LEVEL_STACK_HEIGHT, None).unwrap(), Pubkey::default());
+    }
+
+    #[test]
+    fn cpi_calls_report_the_top_level_program() {
+        let (router, other) = (Pubkey::new_unique(), Pubkey::new_unique());
+        let mut data = sysvar_data(&[other, router], 1);
+        let mut lamports = 0;
+        let owner = Pubkey::default();
+        let info = AccountInfo::new(&ID, false, false, &mut lamports, &mut data, &owner, false, 0);
+
+        assert_eq!(root_program(TRANSACTION_LEVEL_STACK_HEIGHT + 1, Some(&info)).unwrap(), router);
+        let err = root_program(TRANSACTION_LEVEL_STACK_HEIGHT + 1, None).unwrap_err();
+        assert_eq!(err, MarketError::InstructionsSysvarRequired.into());
+    }
+}
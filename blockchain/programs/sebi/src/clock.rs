@@ -0,0 +1,22 @@
+use anchor_lang::prelude::*;
+use crate::errors::MarketError;
+
+/// Current unix timestamp. A missing Clock sysvar surfaces as
+/// `ClockUnavailable` instead of an opaque program error.
+pub fn now() -> Result<i64> {
+    Clock::get()
+        .map(|clock| clock.unix_timestamp)
+        .map_err(|_| error!(MarketError::ClockUnavailable))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // Off-chain there is no sysvar syscall, so the stub always fails.
+    #[test]
+    fn missing_clock_is_clock_unavailable() {
+        let err = now().unwrap_err();
+        assert!(matches!(err, Error::AnchorError(e) if e.error_name == "ClockUnavailable"));
+    }
+}
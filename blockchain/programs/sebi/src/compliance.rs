@@ -0,0 +1,95 @@
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::hash::hash;
+use anchor_lang::solana_program::instruction::Instruction;
+use anchor_lang::solana_program::program::{get_return_data, invoke};
+use crate::state::Market;
+use crate::errors::MarketError;
+use crate::events::TradeSide;
+
+/// Arguments of the callback a market's compliance_program must expose. In
+/// Anchor terms it is `check_trade(market, trader, side, amount, price) -> bool`
+/// with accounts [market, trader], both read-only: instruction data is
+/// sha256("global:check_trade")[..8] followed by borsh of this struct. The
+/// trade proceeds only if the callee sets return data to borsh `true`; a
+/// callee that errors aborts the whole transaction.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, PartialEq, Eq, Debug)]
+pub struct ComplianceCheck {
+    pub market: Pubkey,
+    pub trader: Pubkey,
+    pub side: TradeSide,
+    pub amount: u64,
+    pub price: u128,
+}
+
+pub fn instruction_data(check: &ComplianceCheck) -> Result<Vec<u8>> {
+    let mut data = hash(b"global:check_trade").to_bytes()[..8].to_vec();
+    check.serialize(&mut data)?;
+    Ok(data)
+}
+
+/// Approval must come from the compliance program itself, as a single 1 byte
+pub fn is_approval(program: &Pubkey, return_data: Option<(Pubkey, Vec<u8>)>) -> bool {
+    matches!(return_data, Some((from, data)) if from == *program && data == [1])
+}
+
+/// Runs the market's compliance callback for one trade; a no-op for markets
+/// without one. The callback account must be passed and match the market.
+pub fn check_trade<'info>(
+    program: Option<&UncheckedAccount<'info>>,
+    market: &Account<'info, Market>,
+    trader: &AccountInfo<'info>,
+    side: TradeSide,
+    amount: u64,
+    price: u128,
+) -> Result<()> {
+    if market.compliance_program == Pubkey::default() {
+        return Ok(());
+    }
+    let program = program
+        .filter(|p| p.key() == market.compliance_program)
+        .ok_or(MarketError::ComplianceRejected)?;
+
+    let check = ComplianceCheck { market: market.key(), trader: trader.key(), side, amount, price };
+    let ix = Instruction {
+        program_id: program.key(),
+        accounts: vec![AccountMeta::new_readonly(market.key(), false), AccountMeta::new_readonly(trader.key(), false)],
+        data: instruction_data(&check)?,
+    };
+    invoke(&ix, &[market.to_account_info(), trader.clone(), program.to_account_info()])?;
+
+    if !is_approval(program.key, get_return_data()) {
+        return err!(MarketError::ComplianceRejected);
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn instruction_data_is_anchor_sighash_then_borsh() {
+        let check = ComplianceCheck {
+            market: Pubkey::new_unique(),
+            trader: Pubkey::new_unique(),
+            side: TradeSide::Sell,
+            amount: 7,
+            price: 9,
+        };
+        let data = instruction_data(&check).unwrap();
+        assert_eq!(&data[..8], &hash(b"global:check_trade").to_bytes()[..8]);
+        // market, trader pubkeys = 32*2, side u8 = 1, amount u64 = 8, price u128 = 16
+        assert_eq!(data.len(), 8 + 64 + 1 + 8 + 16);
+        assert_eq!(ComplianceCheck::try_from_slice(&data[8..]).unwrap(), check);
+    }
+
+    #[test]
+    fn only_a_true_from_the_callee_approves() {
+        let program = Pubkey::new_unique();
+        assert!(is_approval(&program, Some((program, vec![1]))));
+        assert!(!is_approval(&program, Some((program, vec![0]))));
+        assert!(!is_approval(&program, Some((program, vec![1, 0]))));
+        assert!(!is_approval(&program, Some((Pubkey::new_unique(), vec![1]))));
+        assert!(!is_approval(&program, None));
+    }
+}
@@ -0,0 +1,135 @@
+/// Interface version reported by `program_info`; bumped on incompatible changes
+pub const PROGRAM_VERSION: u32 = 3;
+
+/// 10^38 is the largest power of ten that fits in a u128
+pub const MAX_PRICE_SCALE: u32 = 38;
+
+/// Decimals of Market.display_price
+pub const DISPLAY_PRICE_DECIMALS: u32 = 6;
+
+/// Bond base units one wallet may draw from a market's faucet per UTC day
+#[cfg(feature = "devnet-faucet")]
+pub const FAUCET_BONDS_PER_DAY: u64 = 1_000;
+
+/// Delay before a newly set approved payee can receive withdrawals
+pub const PAYEE_TIMELOCK_SECS: i64 = 2 * 24 * 60 * 60;
+
+/// Basis points in one whole
+pub const BPS_DENOMINATOR: u128 = 10_000;
+
+// Capability bits reported by `program_info`
+pub const FEATURE_REALIZED_PNL: u64 = 1 << 0;
+pub const FEATURE_RESCUE_TOKENS: u64 = 1 << 1;
+pub const FEATURE_PAUSE_GRACE: u64 = 1 << 2;
+pub const FEATURE_MARKET_REGISTRY: u64 = 1 << 3;
+pub const FEATURE_INSURANCE_FUND: u64 = 1 << 4;
+pub const FEATURE_PRICE_TICK: u64 = 1 << 5;
+pub const FEATURE_SELL_TOGGLE: u64 = 1 << 6;
+pub const FEATURE_MARKET_PHASES: u64 = 1 << 7;
+pub const FEATURE_QUOTE_MINT_ALLOWLIST: u64 = 1 << 8;
+pub const FEATURE_LOW_INVENTORY_PAUSE: u64 = 1 << 9;
+pub const FEATURE_PRICE_SCALE: u64 = 1 << 10;
+pub const FEATURE_PRICE_FREEZE: u64 = 1 << 11;
+pub const FEATURE_TVL_VIEW: u64 = 1 << 12;
+pub const FEATURE_MAX_TRADE_AMOUNT: u64 = 1 << 13;
+pub const FEATURE_SOLVENT_WITHDRAW: u64 = 1 << 14;
+pub const FEATURE_DISPLAY_PRICE: u64 = 1 << 15;
+pub const FEATURE_DUTCH_AUCTION: u64 = 1 << 16;
+pub const FEATURE_PRICE_HISTORY: u64 = 1 << 17;
+pub const FEATURE_TWAP: u64 = 1 << 18;
+pub const FEATURE_TERMINATE: u64 = 1 << 19;
+pub const FEATURE_PRICE_ATTESTATION: u64 = 1 << 20;
+pub const FEATURE_VAULT_AUTHORITY_ROTATION: u64 = 1 << 21;
+pub const FEATURE_CONDITIONAL_BUY: u64 = 1 << 22;
+pub const FEATURE_CIRCUIT_BREAKER: u64 = 1 << 23;
+pub const FEATURE_PROTOCOL_STATS: u64 = 1 << 24;
+pub const FEATURE_PRO_RATA_REDEMPTION: u64 = 1 << 25;
+pub const FEATURE_MINT_ISSUANCE: u64 = 1 << 26;
+pub const FEATURE_COMPLIANCE_HOOK: u64 = 1 << 27;
+pub const FEATURE_WITHDRAW_COOLDOWN: u64 = 1 << 28;
+pub const FEATURE_CAN_TRADE_VIEW: u64 = 1 << 29;
+pub const FEATURE_EVENT_VERBOSITY: u64 = 1 << 30;
+pub const FEATURE_PER_LOT_PRICING: u64 = 1 << 31;
+pub const FEATURE_DELEGATED_TRADING: u64 = 1 << 32;
+pub const FEATURE_MAX_SLIPPAGE: u64 = 1 << 33;
+pub const FEATURE_DAILY_OHLC: u64 = 1 << 34;
+pub const FEATURE_BURN_UNSOLD: u64 = 1 << 35;
+pub const FEATURE_MIN_INITIAL_FUNDING: u64 = 1 << 36;
+pub const FEATURE_EMERGENCY_EXIT: u64 = 1 << 37;
+pub const FEATURE_VALIDATE_CONFIG: u64 = 1 << 38;
+pub const FEATURE_TRADE_NOTIFY: u64 = 1 << 39;
+pub const FEATURE_ADMIN_MARKET_LIMIT: u64 = 1 << 40;
+pub const FEATURE_QUOTE_MINT_MIGRATION: u64 = 1 << 41;
+/// Reported only by builds with the devnet-faucet feature
+pub const FEATURE_DEVNET_FAUCET: u64 = 1 << 42;
+pub const FEATURE_MAX_NOTIONAL: u64 = 1 << 43;
+pub const FEATURE_APPROVED_PAYEE: u64 = 1 << 44;
+pub const FEATURE_REQUOTE_AND_TRADE: u64 = 1 << 45;
+pub const FEATURE_PRICE_BATCH: u64 = 1 << 46;
+pub const FEATURE_WITHDRAW_CAP: u64 = 1 << 47;
+pub const FEATURE_RELAYER_AUTHORIZATION: u64 = 1 << 48;
+pub const FEATURE_BUY_CREATES_ATA: u64 = 1 << 49;
+pub const FEATURE_REQUIRED_BACKING: u64 = 1 << 50;
+pub const FEATURE_TRADE_COOLDOWN: u64 = 1 << 51;
+pub const FEATURE_PRICE_UPDATE_GUARD: u64 = 1 << 52;
+pub const FEATURE_WASH_CHECK: u64 = 1 << 53;
+
+pub const SUPPORTED_FEATURES: u64 = FEATURE_REALIZED_PNL
+    | FEATURE_RESCUE_TOKENS
+    | FEATURE_PAUSE_GRACE
+    | FEATURE_MARKET_REGISTRY
+    | FEATURE_INSURANCE_FUND
+    | FEATURE_PRICE_TICK
+    | FEATURE_SELL_TOGGLE
+    | FEATURE_MARKET_PHASES
+    | FEATURE_QUOTE_MINT_ALLOWLIST
+    | FEATURE_LOW_INVENTORY_PAUSE
+    | FEATURE_PRICE_SCALE
+    | FEATURE_PRICE_FREEZE
+    | FEATURE_TVL_VIEW
+    | FEATURE_MAX_TRADE_AMOUNT
+    | FEATURE_SOLVENT_WITHDRAW
+    | FEATURE_DISPLAY_PRICE
+    | FEATURE_DUTCH_AUCTION
+    | FEATURE_PRICE_HISTORY
+    | FEATURE_TWAP
+    | FEATURE_TERMINATE
+    | FEATURE_PRICE_ATTESTATION
+    | FEATURE_VAULT_AUTHORITY_ROTATION
+    | FEATURE_CONDITIONAL_BUY
+    | FEATURE_CIRCUIT_BREAKER
+    | FEATURE_PROTOCOL_STATS
+    | FEATURE_PRO_RATA_REDEMPTION
+    | FEATURE_MINT_ISSUANCE
+    | FEATURE_COMPLIANCE_HOOK
+    | FEATURE_WITHDRAW_COOLDOWN
+    | FEATURE_CAN_TRADE_VIEW
+    | FEATURE_EVENT_VERBOSITY
+    | FEATURE_PER_LOT_PRICING
+    | FEATURE_DELEGATED_TRADING
+    | FEATURE_MAX_SLIPPAGE
+    | FEATURE_DAILY_OHLC
+    | FEATURE_BURN_UNSOLD
+    | FEATURE_MIN_INITIAL_FUNDING
+    | FEATURE_EMERGENCY_EXIT
+    | FEATURE_VALIDATE_CONFIG
+    | FEATURE_TRADE_NOTIFY
+    | FEATURE_ADMIN_MARKET_LIMIT
+    | FEATURE_QUOTE_MINT_MIGRATION
+    | FEATURE_MAX_NOTIONAL
+    | FEATURE_APPROVED_PAYEE
+    | FEATURE_REQUOTE_AND_TRADE
+    | FEATURE_PRICE_BATCH
+    | FEATURE_WITHDRAW_CAP
+    | FEATURE_RELAYER_AUTHORIZATION
+    | FEATURE_BUY_CREATES_ATA
+    | FEATURE_REQUIRED_BACKING
+    | FEATURE_TRADE_COOLDOWN
+    | FEATURE_PRICE_UPDATE_GUARD
+    | FEATURE_WASH_CHECK
+    | DEVNET_FEATURES;
+
+#[cfg(feature = "devnet-faucet")]
+const DEVNET_FEATURES: u64 = FEATURE_DEVNET_FAUCET;
+#[cfg(not(feature = "devnet-faucet"))]
+const DEVNET_FEATURES: u64 = 0;
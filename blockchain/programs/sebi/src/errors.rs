@@ -10,4 +10,136 @@ pub enum MarketError {
     Unauthorized,
     #[msg("Math overflow")]
     MathOverflow,
+    #[msg("Market vaults cannot be rescued")]
+    CannotRescueVault,
+    #[msg("Market PDA bump is missing or does not match its seeds")]
+    InvalidBump,
+    #[msg("Insurance vault already initialized")]
+    InsuranceAlreadyInitialized,
+    #[msg("Price is not a multiple of the market tick")]
+    InvalidTick,
+    #[msg("Selling back to this market is disabled")]
+    SellDisabled,
+    #[msg("Operation not allowed in the current market phase")]
+    InvalidPhase,
+    #[msg("Quote mint is not on the approved list")]
+    UnapprovedQuoteMint,
+    #[msg("Approved quote mint list is full")]
+    QuoteMintListFull,
+    #[msg("Buying is paused until the bond vault is restocked")]
+    LowInventory,
+    #[msg("Price scale exceeds the maximum supported exponent")]
+    InvalidPriceScale,
+    #[msg("Price is permanently frozen")]
+    PriceFrozen,
+    #[msg("Trade amount exceeds the per-transaction maximum")]
+    TradeTooLarge,
+    #[msg("Withdrawal would leave outstanding bonds unbacked")]
+    WouldBeInsolvent,
+    #[msg("Clock sysvar is unavailable")]
+    ClockUnavailable,
+    #[msg("Auction must end after it starts")]
+    InvalidAuction,
+    #[msg("Price is set by a running auction")]
+    AuctionInProgress,
+    #[msg("Market decimals are already set")]
+    DecimalsAlreadySet,
+    #[msg("Batch arguments do not match the supplied account groups")]
+    InvalidBatch,
+    #[msg("Proceeds fall below the requested minimum")]
+    SlippageExceeded,
+    #[msg("Price history has no points yet")]
+    EmptyPriceHistory,
+    #[msg("Market has been permanently terminated")]
+    MarketTerminated,
+    #[msg("Price attestation is missing, malformed or not signed by the price updater")]
+    InvalidAttestation,
+    #[msg("Price attestation has expired")]
+    AttestationExpired,
+    #[msg("Market must be paused for this operation")]
+    MarketNotHalted,
+    #[msg("Invalid vault authority")]
+    InvalidVaultAuthority,
+    #[msg("Current price is above the buyer's target")]
+    PriceAboveTarget,
+    #[msg("Internal invariant violated")]
+    InvariantViolated,
+    #[msg("Bond and quote mints must differ")]
+    SameMint,
+    #[msg("Invalid redemption window")]
+    InvalidRedemptionWindow,
+    #[msg("Redemption window has closed")]
+    RedemptionWindowClosed,
+    #[msg("Redemption window is still open")]
+    RedemptionWindowOpen,
+    #[msg("Redemption claims are still outstanding")]
+    RedemptionPending,
+    #[msg("Redemption claim already paid")]
+    RedemptionAlreadyPaid,
+    #[msg("Market is not the bond mint authority")]
+    NotMintAuthority,
+    #[msg("Trade rejected by the market's compliance program")]
+    ComplianceRejected,
+    #[msg("Withdrawal cooldown has not elapsed")]
+    WithdrawCooldown,
+    #[msg("Lot size must be nonzero")]
+    InvalidLotSize,
+    #[msg("Signer is not the owner's trading delegate")]
+    NotDelegated,
+    #[msg("Market is not set to burn unsold inventory")]
+    BurnDisabled,
+    #[msg("Vault has not reached its minimum initial funding")]
+    MarketNotFunded,
+    #[msg("Emergency exit is not enabled for this market")]
+    EmergencyExitDisabled,
+    #[msg("Notify program was not passed or did not acknowledge the trade")]
+    NotifyFailed,
+    #[msg("Notify program cannot be this program")]
+    InvalidNotifyProgram,
+    #[msg("Admin has reached the program's market limit")]
+    MarketLimitReached,
+    #[msg("USDC vault must be empty to migrate the quote mint")]
+    QuoteVaultNotEmpty,
+    #[msg("New quote mint must keep the old mint's decimals")]
+    QuoteDecimalsMismatch,
+    #[msg("Wallet has reached today's faucet limit")]
+    FaucetLimitReached,
+    #[msg("Trade value exceeds the market's max notional")]
+    NotionalTooLarge,
+    #[msg("Withdrawal destination is not owned by the admin or an approved payee")]
+    UnapprovedPayee,
+    #[msg("Batch exceeds the instruction's maximum size")]
+    BatchTooLarge,
+    #[msg("Withdrawal would leave pending redemption claims underfunded")]
+    RedemptionClaimsOutstanding,
+    #[msg("Withdrawal would exceed the market's cumulative cap")]
+    WithdrawCapReached,
+    #[msg("A cumulative withdrawal cap can only be lowered")]
+    InvalidWithdrawCap,
+    #[msg("Trade exceeds the relayer's remaining allowance")]
+    RelayLimitExceeded,
+    #[msg("Relayer authorization has expired")]
+    RelayExpired,
+    #[msg("CPI trades must pass the instructions sysvar")]
+    InstructionsSysvarRequired,
+    #[msg("Requote trades need a price change limit and update cooldown")]
+    PriceGuardRequired,
+    #[msg("Market already uses the current layout")]
+    AlreadyMigrated,
+    #[msg("Not a market account migrate_market can convert")]
+    InvalidMarketAccount,
+    #[msg("Trade is inside the trader's cooldown")]
+    TradeCooldown,
+    #[msg("Markets with a trade cooldown need the trader's investor position")]
+    PositionRequired,
+    #[msg("Insurance can only be withdrawn once the market can no longer sell")]
+    InsuranceBacksSells,
+    #[msg("The market's insurance vault must be passed")]
+    InsuranceVaultRequired,
+    #[msg("Price change exceeds the market's per-update limit")]
+    PriceChangeTooLarge,
+    #[msg("Price update cooldown has not elapsed")]
+    PriceUpdateCooldown,
+    #[msg("Trader flipped sides too often within the market's wash window")]
+    WashTradingSuspected,
 }
@@ -0,0 +1,321 @@
+use anchor_lang::prelude::*;
+use crate::state::EventVerbosity;
+
+#[event]
+pub struct TradeEvent {
+    pub market: Pubkey,
+    pub trader: Pubkey,
+    pub side: TradeSide,
+    pub amount: u64,
+    /// Price the trade executed at: attested, auction or stored
+    pub price: u128,
+    /// market.price_per_token when the trade ran; differs from price only for attested trades
+    pub reference_price: u128,
+    /// Top-level program of a CPI trade; Pubkey::default() for direct calls
+    pub via_program: Pubkey,
+}
+
+impl TradeEvent {
+    /// Full emits this event, Minimal a TradeSummaryEvent, Off nothing
+    pub fn emit_at(self, verbosity: EventVerbosity) {
+        match verbosity {
+            EventVerbosity::Full => emit!(self),
+            EventVerbosity::Minimal => emit!(TradeSummaryEvent {
+                market: self.market,
+                side: self.side,
+                amount: self.amount,
+                price: self.price,
+            }),
+            EventVerbosity::Off => {}
+        }
+    }
+}
+
+/// TradeEvent without the trader and reference price, for markets logging at Minimal
+#[event]
+pub struct TradeSummaryEvent {
+    pub market: Pubkey,
+    pub side: TradeSide,
+    pub amount: u64,
+    pub price: u128,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, PartialEq, Eq, Debug)]
+pub enum TradeSide {
+    Buy,
+    Sell,
+}
+
+#[event]
+pub struct PauseEvent {
+    pub market: Pubkey,
+    pub paused: bool,
+    /// Unix timestamp at which trading halts; 0 when the market is resumed
+    pub effective_ts: i64,
+}
+
+#[event]
+pub struct InsuranceFundedEvent {
+    pub market: Pubkey,
+    pub funder: Pubkey,
+    pub amount: u64,
+    pub balance: u64,
+}
+
+#[event]
+pub struct InsuranceTappedEvent {
+    pub market: Pubkey,
+    /// Sell shortfall paid from the insurance vault
+    pub amount: u64,
+    pub remaining: u64,
+}
+
+/// Insurance returned by withdraw_insurance after the market stopped selling
+#[event]
+pub struct InsuranceWithdrawnEvent {
+    pub market: Pubkey,
+    pub destination: Pubkey,
+    pub amount: u64,
+    pub remaining: u64,
+}
+
+#[event]
+pub struct LowInventoryEvent {
+    pub market: Pubkey,
+    pub vault_bond: u64,
+    pub threshold: u64,
+    /// true when buying halts, false when a restocked vault lets it resume
+    pub buying_paused: bool,
+}
+
+#[event]
+pub struct MarketTerminatedEvent {
+    pub market: Pubkey,
+    pub admin: Pubkey,
+    pub ts: i64,
+}
+
+#[event]
+pub struct VaultAuthorityRotatedEvent {
+    pub market: Pubkey,
+    pub old_authority: Pubkey,
+    pub new_authority: Pubkey,
+}
+
+#[event]
+pub struct ConfigChangedEvent {
+    pub market: Pubkey,
+    pub field: ConfigField,
+    pub old_value: u64,
+    pub new_value: u64,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, PartialEq, Eq, Debug)]
+pub enum ConfigField {
+    BondDecimals,
+    UsdcDecimals,
+}
+
+#[event]
+pub struct RedemptionPaidEvent {
+    pub market: Pubkey,
+    pub holder: Pubkey,
+    pub bonds: u64,
+    pub owed: u64,
+    /// owed after the pro-rata haircut; equal to owed when the vault covered every claim
+    pub paid: u64,
+}
+
+/// One finished UTC day of a market's trades; see DailyStats
+#[event]
+pub struct OhlcEvent {
+    pub market: Pubkey,
+    /// Unix day (ts / 86_400)
+    pub day: i64,
+    pub open: u128,
+    pub high: u128,
+    pub low: u128,
+    pub close: u128,
+    pub volume: u64,
+    pub trade_count: u64,
+}
+
+/// Unsold inventory retired at maturity by burn_unsold
+#[event]
+pub struct BurnEvent {
+    pub market: Pubkey,
+    pub amount: u64,
+}
+
+#[event]
+pub struct EmergencyExitEvent {
+    pub market: Pubkey,
+    pub holder: Pubkey,
+    pub amount: u64,
+    pub paid: u64,
+    /// Price paid per bond, fixed point like price_per_token; below it when the vault falls short
+    pub rate: u128,
+}
+
+#[event]
+pub struct QuoteMintMigratedEvent {
+    pub market: Pubkey,
+    pub old_mint: Pubkey,
+    pub new_mint: Pubkey,
+    pub old_vault: Pubkey,
+    pub new_vault: Pubkey,
+}
+
+#[event]
+pub struct ApprovedPayeeEvent {
+    pub market: Pubkey,
+    pub payee: Pubkey,
+    pub effective_ts: i64,
+}
+
+/// Decode entry for one event. Logs carry `discriminator ++ borsh(fields)`,
+/// with fields in the listed order. Enums are a single u8 variant index.
+pub struct EventLayout {
+    pub name: &'static str,
+    pub discriminator: &'static [u8],
+    /// (field name, borsh type)
+    pub fields: &'static [(&'static str, &'static str)],
+}
+
+/// Every event the program emits; the discriminator is sha256("event:<name>")[..8]
+pub const EVENTS: &[EventLayout] = &[
+    EventLayout {
+        name: "TradeEvent",
+        discriminator: TradeEvent::DISCRIMINATOR,
+        fields: &[("market", "pubkey"), ("trader", "pubkey"), ("side", "u8"), ("amount", "u64"), ("price", "u128"), ("reference_price", "u128"), ("via_program", "pubkey")],
+    },
+    EventLayout {
+        name: "TradeSummaryEvent",
+        discriminator: TradeSummaryEvent::DISCRIMINATOR,
+        fields: &[("market", "pubkey"), ("side", "u8"), ("amount", "u64"), ("price", "u128")],
+    },
+    EventLayout {
+        name: "PauseEvent",
+        discriminator: PauseEvent::DISCRIMINATOR,
+        fields: &[("market", "pubkey"), ("paused", "bool"), ("effective_ts", "i64")],
+    },
+    EventLayout {
+        name: "InsuranceFundedEvent",
+        discriminator: InsuranceFundedEvent::DISCRIMINATOR,
+        fields: &[("market", "pubkey"), ("funder", "pubkey"), ("amount", "u64"), ("balance", "u64")],
+    },
+    EventLayout {
+        name: "InsuranceTappedEvent",
+        discriminator: InsuranceTappedEvent::DISCRIMINATOR,
+        fields: &[("market", "pubkey"), ("amount", "u64"), ("remaining", "u64")],
+    },
+    EventLayout {
+        name: "InsuranceWithdrawnEvent",
+        discriminator: InsuranceWithdrawnEvent::DISCRIMINATOR,
+        fields: &[("market", "pubkey"), ("destination", "pubkey"), ("amount", "u64"), ("remaining", "u64")],
+    },
+    EventLayout {
+        name: "LowInventoryEvent",
+        discriminator: LowInventoryEvent::DISCRIMINATOR,
+        fields: &[("market", "pubkey"), ("vault_bond", "u64"), ("threshold", "u64"), ("buying_paused", "bool")],
+    },
+    EventLayout {
+        name: "MarketTerminatedEvent",
+        discriminator: MarketTerminatedEvent::DISCRIMINATOR,
+        fields: &[("market", "pubkey"), ("admin", "pubkey"), ("ts", "i64")],
+    },
+    EventLayout {
+        name: "VaultAuthorityRotatedEvent",
+        discriminator: VaultAuthorityRotatedEvent::DISCRIMINATOR,
+        fields: &[("market", "pubkey"), ("old_authority", "pubkey"), ("new_authority", "pubkey")],
+    },
+    EventLayout {
+        name: "ConfigChangedEvent",
+        discriminator: ConfigChangedEvent::DISCRIMINATOR,
+        fields: &[("market", "pubkey"), ("field", "u8"), ("old_value", "u64"), ("new_value", "u64")],
+    },
+    EventLayout {
+        name: "RedemptionPaidEvent",
+        discriminator: RedemptionPaidEvent::DISCRIMINATOR,
+        fields: &[("market", "pubkey"), ("holder", "pubkey"), ("bonds", "u64"), ("owed", "u64"), ("paid", "u64")],
+    },
+    EventLayout {
+        name: "OhlcEvent",
+        discriminator: OhlcEvent::DISCRIMINATOR,
+        fields: &[("market", "pubkey"), ("day", "i64"), ("open", "u128"), ("high", "u128"), ("low", "u128"), ("close", "u128"), ("volume", "u64"), ("trade_count", "u64")],
+    },
+    EventLayout {
+        name: "BurnEvent",
+        discriminator: BurnEvent::DISCRIMINATOR,
+        fields: &[("market", "pubkey"), ("amount", "u64")],
+    },
+    EventLayout {
+        name: "EmergencyExitEvent",
+        discriminator: EmergencyExitEvent::DISCRIMINATOR,
+        fields: &[("market", "pubkey"), ("holder", "pubkey"), ("amount", "u64"), ("paid", "u64"), ("rate", "u128")],
+    },
+    EventLayout {
+        name: "QuoteMintMigratedEvent",
+        discriminator: QuoteMintMigratedEvent::DISCRIMINATOR,
+        fields: &[("market", "pubkey"), ("old_mint", "pubkey"), ("new_mint", "pubkey"), ("old_vault", "pubkey"), ("new_vault", "pubkey")],
+    },
+    EventLayout {
+        name: "ApprovedPayeeEvent",
+        discriminator: ApprovedPayeeEvent::DISCRIMINATOR,
+        fields: &[("market", "pubkey"), ("payee", "pubkey"), ("effective_ts", "i64")],
+    },
+];
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use anchor_lang::solana_program::hash::hash;
+    use anchor_lang::Event;
+
+    fn size_of(ty: &str) -> usize {
+        match ty {
+            "pubkey" => 32,
+            "u128" => 16,
+            "u64" | "i64" => 8,
+            "u8" | "bool" => 1,
+            other => panic!("unknown type {other}"),
+        }
+    }
+
+    #[test]
+    fn discriminators_match_anchor_derivation() {
+        for event in EVENTS {
+            let preimage = format!("event:{}", event.name);
+            assert_eq!(event.discriminator, &hash(preimage.as_bytes()).to_bytes()[..8], "{}", event.name);
+        }
+    }
+
+    #[test]
+    fn layouts_match_serialized_events() {
+        let market = Pubkey::new_unique();
+        let samples: Vec<Vec<u8>> = vec![
+            TradeEvent { market, trader: market, side: TradeSide::Sell, amount: 1, price: 2, reference_price: 3, via_program: market }.data(),
+            TradeSummaryEvent { market, side: TradeSide::Buy, amount: 1, price: 2 }.data(),
+            PauseEvent { market, paused: true, effective_ts: 3 }.data(),
+            InsuranceFundedEvent { market, funder: market, amount: 1, balance: 2 }.data(),
+            InsuranceTappedEvent { market, amount: 1, remaining: 2 }.data(),
+            InsuranceWithdrawnEvent { market, destination: market, amount: 1, remaining: 2 }.data(),
+            LowInventoryEvent { market, vault_bond: 1, threshold: 2, buying_paused: false }.data(),
+            MarketTerminatedEvent { market, admin: market, ts: 4 }.data(),
+            VaultAuthorityRotatedEvent { market, old_authority: market, new_authority: market }.data(),
+            ConfigChangedEvent { market, field: ConfigField::UsdcDecimals, old_value: 0, new_value: 6 }.data(),
+            RedemptionPaidEvent { market, holder: market, bonds: 1, owed: 2, paid: 1 }.data(),
+            OhlcEvent { market, day: 1, open: 2, high: 3, low: 1, close: 2, volume: 4, trade_count: 2 }.data(),
+            BurnEvent { market, amount: 1 }.data(),
+            EmergencyExitEvent { market, holder: market, amount: 1, paid: 2, rate: 3 }.data(),
+            QuoteMintMigratedEvent { market, old_mint: market, new_mint: market, old_vault: market, new_vault: market }.data(),
+            ApprovedPayeeEvent { market, payee: market, effective_ts: 3 }.data(),
+        ];
+        assert_eq!(samples.len(), EVENTS.len());
+        for (event, data) in EVENTS.iter().zip(samples) {
+            let size: usize = event.fields.iter().map(|(_, ty)| size_of(ty)).sum();
+            assert_eq!(&data[..8], event.discriminator, "{}", event.name);
+            assert_eq!(data.len(), 8 + size, "{}", event.name);
+        }
+    }
+}
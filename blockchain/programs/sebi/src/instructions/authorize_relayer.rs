@@ -0,0 +1,38 @@
+use anchor_lang::prelude::*;
+use crate::state::{Market, RelayerAuthorization};
+use crate::errors::MarketError;
+
+#[derive(Accounts)]
+pub struct AuthorizeRelayer<'info> {
+    #[account(constraint = !market.terminated @ MarketError::MarketTerminated)]
+    pub market: Account<'info, Market>,
+
+    #[account(
+        init_if_needed,
+        payer = owner,
+        space = RelayerAuthorization::LEN,
+        seeds = [b"relayer", market.key().as_ref(), owner.key().as_ref()],
+        bump
+    )]
+    pub relayer_authorization: Account<'info, RelayerAuthorization>,
+
+    #[account(mut)]
+    pub owner: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+/// Replaces any existing authorization for this owner and market, resetting
+/// the allowance to `max_notional` quote base units.
+pub fn handler(ctx: Context<AuthorizeRelayer>, relayer: Pubkey, max_notional: u64, expiry: i64) -> Result<()> {
+    let record = &mut ctx.accounts.relayer_authorization;
+    record.market = ctx.accounts.market.key();
+    record.owner = ctx.accounts.owner.key();
+    record.relayer = relayer;
+    record.max_notional = max_notional;
+    record.remaining_notional = max_notional;
+    record.expiry = expiry;
+    record.bump = ctx.bumps.relayer_authorization;
+    msg!("Relayer {} authorized for {} until {}", relayer, max_notional, expiry);
+    Ok(())
+}
@@ -0,0 +1,61 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token::{self, Burn, Mint, Token, TokenAccount};
+use crate::state::{Market, MarketPhase};
+use crate::errors::MarketError;
+use crate::events::BurnEvent;
+
+#[derive(Accounts)]
+pub struct BurnUnsold<'info> {
+    #[account(
+        has_one = bond_mint,
+        seeds = [b"market", market.bond_mint.as_ref()],
+        bump = market.bump
+    )]
+    pub market: Account<'info, Market>,
+
+    #[account(mut)]
+    pub bond_mint: Account<'info, Mint>,
+
+    #[account(mut, constraint = vault_bond.key() == market.vault_bond)]
+    pub vault_bond: Account<'info, TokenAccount>,
+
+    pub token_program: Program<'info, Token>,
+}
+
+/// Permissionless crank: once matured, burns whatever is left in
+/// vault_bond on markets the admin opted in with set_burn_unsold_at_maturity.
+pub fn handler(ctx: Context<BurnUnsold>) -> Result<()> {
+    let market = &ctx.accounts.market;
+    if !market.burn_unsold_at_maturity {
+        return err!(MarketError::BurnDisabled);
+    }
+    if !matches!(market.phase, MarketPhase::Matured | MarketPhase::Closed) {
+        return err!(MarketError::InvalidPhase);
+    }
+    // after rotate_vault_authority the PDA can no longer sign for the vault
+    if market.vault_authority != market.key() {
+        return err!(MarketError::InvalidVaultAuthority);
+    }
+
+    let amount = ctx.accounts.vault_bond.amount;
+    if amount == 0 {
+        return Ok(());
+    }
+    let seeds = market.signer_seeds();
+    let signer = &[&seeds[..]];
+    token::burn(
+        CpiContext::new_with_signer(
+            ctx.accounts.token_program.to_account_info(),
+            Burn {
+                mint: ctx.accounts.bond_mint.to_account_info(),
+                from: ctx.accounts.vault_bond.to_account_info(),
+                authority: ctx.accounts.market.to_account_info(),
+            },
+            signer,
+        ),
+        amount,
+    )?;
+
+    emit!(BurnEvent { market: market.key(), amount });
+    Ok(())
+}
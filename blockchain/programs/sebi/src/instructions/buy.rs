@@ -1,22 +1,42 @@
 use anchor_lang::prelude::*;
-use anchor_spl::token::{self, Token, TokenAccount, Transfer};
-use crate::state::Market;
+use anchor_lang::system_program;
+use anchor_lang::solana_program::sysvar::instructions as sysvar_instructions;
+use anchor_spl::associated_token::{self, AssociatedToken};
+use anchor_spl::token::{self, Mint, Token, TokenAccount, Transfer};
+use crate::state::{trader_for, DailyStats, InvestorPosition, Market, ProtocolStats, RelayerAuthorization, TradingDelegate};
 use crate::errors::MarketError;
+use crate::clock;
+#[cfg(feature = "invariant-checks")]
+use crate::invariants;
+use crate::attestation;
+use crate::compliance;
+use crate::attribution;
+use crate::notify::{self, TradeNotification};
+use crate::events::{LowInventoryEvent, TradeEvent, TradeSide};
 
 #[derive(Accounts)]
 pub struct Buy<'info> {
-    #[account(mut, seeds = [b"market", market.bond_mint.as_ref()], bump = market.bump)]
+    #[account(
+        mut,
+        seeds = [b"market", market.bond_mint.as_ref()],
+        bump = market.bump,
+        constraint = !market.terminated @ MarketError::MarketTerminated
+    )]
     pub market: Account<'info, Market>,
 
     /// Buyer (signer)
     #[account(mut)]
     pub buyer: Signer<'info>,
 
-    #[account(mut, constraint = buyer_usdc.owner == buyer.key())]
+    #[account(mut, constraint = buyer_usdc.owner == trader_for(&trading_delegate, &relayer_authorization, buyer.key()))]
     pub buyer_usdc: Account<'info, TokenAccount>,
 
-    #[account(mut, constraint = buyer_bond.owner == buyer.key())]
-    pub buyer_bond: Account<'info, TokenAccount>,
+    /// CHECK: the trader's bond token account, checked in the handler. May not
+    /// exist yet when bond_mint, associated_token_program and system_program
+    /// are passed: it is then created as the buyer's associated token account,
+    /// with the buyer paying rent.
+    #[account(mut)]
+    pub buyer_bond: UncheckedAccount<'info>,
 
     /// Vault token accounts owned by market PDA
     #[account(mut, constraint = vault_usdc.key() == market.vault_usdc)]
@@ -25,22 +45,143 @@ pub struct Buy<'info> {
     #[account(mut, constraint = vault_bond.key() == market.vault_bond)]
     pub vault_bond: Account<'info, TokenAccount>,
 
+    /// CHECK: instructions sysvar, pinned by address. When present, the
+    /// preceding Ed25519 instruction must carry a PriceAttestation to trade at.
+    #[account(address = sysvar_instructions::ID)]
+    pub instructions: Option<UncheckedAccount<'info>>,
+
+    /// Program-wide totals; optional so trading works before it is created
+    #[account(mut, seeds = [b"protocol_stats"], bump = protocol_stats.bump)]
+    pub protocol_stats: Option<Account<'info, ProtocolStats>>,
+
+    /// Today's candle; optional like protocol_stats
+    #[account(mut, seeds = [b"daily_stats", market.key().as_ref()], bump = daily_stats.bump)]
+    pub daily_stats: Option<Account<'info, DailyStats>>,
+
+    /// CHECK: compared against market.compliance_program; required only
+    /// when the market has one
+    pub compliance_program: Option<UncheckedAccount<'info>>,
+
+    /// Present when `buyer` trades for trading_delegate.owner, whose token
+    /// accounts must have approved `buyer` through SPL `approve`
+    #[account(
+        seeds = [b"delegate", market.key().as_ref(), trading_delegate.owner.as_ref()],
+        bump = trading_delegate.bump,
+        constraint = trading_delegate.delegate == buyer.key() @ MarketError::NotDelegated
+    )]
+    pub trading_delegate: Option<Account<'info, TradingDelegate>>,
+
+    /// Present when `buyer` relays for relayer_authorization.owner; the trade's
+    /// quote amount is debited from its allowance
+    #[account(
+        mut,
+        seeds = [b"relayer", market.key().as_ref(), relayer_authorization.owner.as_ref()],
+        bump = relayer_authorization.bump,
+        constraint = relayer_authorization.relayer == buyer.key() @ MarketError::NotDelegated
+    )]
+    pub relayer_authorization: Option<Account<'info, RelayerAuthorization>>,
+
+    /// CHECK: compared against market.notify_program; called once the trade settles
+    pub notify_program: Option<UncheckedAccount<'info>>,
+
+    #[account(address = market.bond_mint)]
+    pub bond_mint: Option<Account<'info, Mint>>,
+
+    pub associated_token_program: Option<Program<'info, AssociatedToken>>,
+
+    pub system_program: Option<Program<'info, System>>,
+
     pub token_program: Program<'info, Token>,
+
+    /// CHECK: instructions sysvar, pinned by address; read only to attribute
+    /// a CPI trade to its calling program. Direct callers leave it out; CPI
+    /// callers pass it here, or pass `instructions` if also attesting a price.
+    #[account(address = sysvar_instructions::ID)]
+    pub instructions_sysvar: Option<UncheckedAccount<'info>>,
+
+    /// CHECK: the trader's position at ["position", market, trader], checked
+    /// in the handler and stamped with this trade; required while the market
+    /// has a trade cooldown. May not exist yet when system_program is passed:
+    /// it is then created, with the buyer paying rent.
+    #[account(mut)]
+    pub investor_position: Option<UncheckedAccount<'info>>,
+}
+
+impl<'info> Buy<'info> {
+    /// Either copy of the instructions sysvar the caller passed
+    pub fn attribution_sysvar(&self) -> Option<&AccountInfo<'info>> {
+        self.instructions_sysvar.as_ref().or(self.instructions.as_ref()).map(AsRef::as_ref)
+    }
 }
 
 pub fn handler(ctx: Context<Buy>, amount: u64) -> Result<()> {
+    execute(ctx, amount, u128::MAX, u64::MAX)
+}
+
+/// Shared by buy and buy_if_below; fails if the effective price is above
+/// `max_price` or the total cost above `max_cost`
+pub fn execute(ctx: Context<Buy>, amount: u64, max_price: u128, max_cost: u64) -> Result<()> {
+    let now = clock::now()?;
+    ctx.accounts.market.sync_auction_price(now)?;
     let market = &ctx.accounts.market;
-    if market.paused {
+    if market.is_halted(now) {
         return err!(MarketError::MarketPaused);
     }
+    if !market.phase.allows_buy() {
+        return err!(MarketError::InvalidPhase);
+    }
+    if market.exceeds_max_trade(amount) {
+        return err!(MarketError::TradeTooLarge);
+    }
+
+    // buying stays halted after inventory ran low until the vault is restocked
+    let inventory = ctx.accounts.vault_bond.amount;
+    if market.low_inventory_paused && inventory < market.low_inventory_threshold {
+        return err!(MarketError::LowInventory);
+    }
 
-    // price_per_token is u128; compute total_price = amount * price
-    let price_u128 = market.price_per_token;
-    let amount_u128 = amount as u128;
-    let total_price_u128 = price_u128.checked_mul(amount_u128).ok_or(MarketError::MathOverflow)?;
+    // total_price = amount * price_per_token / 10^price_scale, rounded up
+    let price_u128 = match &ctx.accounts.instructions {
+        Some(instructions) => attestation::verified_price(instructions, market, now)?,
+        None => market.price_per_token,
+    };
+    if price_u128 > max_price {
+        return err!(MarketError::PriceAboveTarget);
+    }
+    if market.exceeds_slippage(price_u128) {
+        return err!(MarketError::SlippageExceeded);
+    }
+    let total_price_u64 = market.buy_cost_at(amount, price_u128)?;
+    if market.exceeds_max_notional(total_price_u64) {
+        return err!(MarketError::NotionalTooLarge);
+    }
+    if total_price_u64 > max_cost {
+        return err!(MarketError::SlippageExceeded);
+    }
+
+    if ctx.accounts.trading_delegate.is_some() && ctx.accounts.relayer_authorization.is_some() {
+        return err!(MarketError::NotDelegated);
+    }
+    // the hook screens the signer, so it can't vouch for a delegated owner
+    let acting_for_owner = ctx.accounts.trading_delegate.is_some() || ctx.accounts.relayer_authorization.is_some();
+    if acting_for_owner && market.compliance_program != Pubkey::default() {
+        return err!(MarketError::ComplianceRejected);
+    }
+    if let Some(auth) = &mut ctx.accounts.relayer_authorization {
+        auth.spend(total_price_u64, now)?;
+    }
+    let trader = trader_for(&ctx.accounts.trading_delegate, &ctx.accounts.relayer_authorization, ctx.accounts.buyer.key());
+    record_position_trade(ctx.accounts, trader, now)?;
+    compliance::check_trade(
+        ctx.accounts.compliance_program.as_ref(),
+        market,
+        &ctx.accounts.buyer.to_account_info(),
+        TradeSide::Buy,
+        amount,
+        price_u128,
+    )?;
 
-    // assume USDC decimals are 6: total_price_u128 already scaled appropriately by admin
-    let total_price_u64 = total_price_u128.try_into().map_err(|_| MarketError::MathOverflow)?;
+    prepare_buyer_bond(ctx.accounts, trader)?;
 
     // transfer USDC from buyer -> vault_usdc
     let cpi_accounts_usdc = Transfer {
@@ -54,7 +195,7 @@ pub fn handler(ctx: Context<Buy>, amount: u64) -> Result<()> {
     )?;
 
     // transfer bonds from vault -> buyer, signed by PDA
-    let seeds = &[b"market", market.bond_mint.as_ref(), &[market.bump]];
+    let seeds = market.signer_seeds();
     let signer = &[&seeds[..]];
     let cpi_accounts_bond = Transfer {
         from: ctx.accounts.vault_bond.to_account_info(),
@@ -66,28 +207,196 @@ pub fn handler(ctx: Context<Buy>, amount: u64) -> Result<()> {
         amount,
     )?;
 
-    emit!(TradeEvent {
+    ctx.accounts.market.record_buy(amount, total_price_u64)?;
+    ctx.accounts.market.last_trade_price = price_u128;
+    if let Some(stats) = &mut ctx.accounts.protocol_stats {
+        stats.record_trade(total_price_u64)?;
+    }
+    if let Some(daily) = &mut ctx.accounts.daily_stats {
+        if let Some(candle) = daily.record(price_u128, total_price_u64, now)? {
+            emit!(candle);
+        }
+    }
+
+    let remaining = inventory.checked_sub(amount).ok_or(MarketError::MathOverflow)?;
+    let market = &mut ctx.accounts.market;
+    let low = market.low_inventory_threshold > 0 && remaining < market.low_inventory_threshold;
+    if low != market.low_inventory_paused {
+        market.low_inventory_paused = low;
+        emit!(LowInventoryEvent {
+            market: market.key(),
+            vault_bond: remaining,
+            threshold: market.low_inventory_threshold,
+            buying_paused: low,
+        });
+    }
+
+    TradeEvent {
         market: ctx.accounts.market.key(),
-        trader: ctx.accounts.buyer.key(),
+        trader,
         side: TradeSide::Buy,
         amount,
         price: price_u128,
-    });
+        reference_price: ctx.accounts.market.price_per_token,
+        via_program: attribution::via_program(ctx.accounts.attribution_sysvar())?,
+    }
+    .emit_at(ctx.accounts.market.event_verbosity);
+
+    notify::notify_trade(
+        ctx.accounts.notify_program.as_ref(),
+        &ctx.accounts.market,
+        &TradeNotification {
+            market: ctx.accounts.market.key(),
+            trader,
+            side: TradeSide::Buy,
+            amount,
+            price: price_u128,
+            quote_amount: total_price_u64,
+        },
+    )?;
+
+    #[cfg(feature = "invariant-checks")]
+    {
+        let (usdc_before, bond_before) = (ctx.accounts.vault_usdc.amount, ctx.accounts.vault_bond.amount);
+        ctx.accounts.vault_usdc.reload()?;
+        ctx.accounts.vault_bond.reload()?;
+        invariants::check_balance_delta(usdc_before, ctx.accounts.vault_usdc.amount, total_price_u64 as i128)?;
+        invariants::check_balance_delta(bond_before, ctx.accounts.vault_bond.amount, -(amount as i128))?;
+    }
+
+    Ok(())
+}
 
+/// Creates buyer_bond as the buyer's associated token account if it doesn't
+/// exist and the accounts to create it were passed, then checks it belongs
+/// to `trader`. A delegate or relayer can't create one on the owner's behalf.
+fn prepare_buyer_bond(accounts: &Buy, trader: Pubkey) -> Result<()> {
+    let buyer_bond = accounts.buyer_bond.to_account_info();
+    if buyer_bond.data_is_empty() {
+        let (Some(mint), Some(associated_token_program), Some(system_program)) =
+            (&accounts.bond_mint, &accounts.associated_token_program, &accounts.system_program)
+        else {
+            return err!(ErrorCode::AccountNotInitialized);
+        };
+        if trader != accounts.buyer.key() {
+            return err!(MarketError::NotDelegated);
+        }
+        associated_token::create(CpiContext::new(
+            associated_token_program.to_account_info(),
+            associated_token::Create {
+                payer: accounts.buyer.to_account_info(),
+                associated_token: buyer_bond.clone(),
+                authority: accounts.buyer.to_account_info(),
+                mint: mint.to_account_info(),
+                system_program: system_program.to_account_info(),
+                token_program: accounts.token_program.to_account_info(),
+            },
+        ))?;
+    }
+    if *buyer_bond.owner != token::ID {
+        return err!(ErrorCode::AccountOwnedByWrongProgram);
+    }
+    let account = TokenAccount::try_deserialize(&mut &buyer_bond.try_borrow_data()?[..])?;
+    if account.owner != trader {
+        return err!(ErrorCode::ConstraintRaw);
+    }
     Ok(())
 }
 
-#[event]
-pub struct TradeEvent {
-    pub market: Pubkey,
-    pub trader: Pubkey,
-    pub side: TradeSide,
-    pub amount: u64,
-    pub price: u128,
+/// Stamps the trader's position with this trade, creating it at its PDA if
+/// it doesn't exist and system_program was passed. As with buyer_bond, a
+/// delegate or relayer can't create one on the owner's behalf.
+fn record_position_trade(accounts: &Buy, trader: Pubkey, now: i64) -> Result<()> {
+    let Some(info) = &accounts.investor_position else {
+        if accounts.market.requires_position() {
+            return err!(MarketError::PositionRequired);
+        }
+        return Ok(());
+    };
+    let mut position = load_investor_position(accounts, info, trader)?;
+    position.record_trade(&accounts.market, TradeSide::Buy, now)?;
+    position.try_serialize(&mut &mut info.try_borrow_mut_data()?[..])
 }
 
-#[derive(AnchorSerialize, AnchorDeserialize, Clone, PartialEq, Eq, Debug)]
-pub enum TradeSide {
-    Buy,
-    Sell,
+fn load_investor_position<'info>(
+    accounts: &Buy<'info>,
+    info: &AccountInfo<'info>,
+    trader: Pubkey,
+) -> Result<InvestorPosition> {
+    let market = accounts.market.key();
+    if info.data_is_empty() {
+        let Some(system_program) = &accounts.system_program else {
+            return err!(ErrorCode::AccountNotInitialized);
+        };
+        if trader != accounts.buyer.key() {
+            return err!(MarketError::NotDelegated);
+        }
+        let (address, bump) = Pubkey::find_program_address(&[b"position", market.as_ref(), trader.as_ref()], &crate::ID);
+        if info.key() != address {
+            return err!(ErrorCode::ConstraintSeeds);
+        }
+        create_position_account(accounts, system_program, info, &[b"position", market.as_ref(), trader.as_ref(), &[bump]])?;
+        return Ok(InvestorPosition {
+            market,
+            owner: trader,
+            last_trade_ts: 0,
+            bump,
+            last_side: None,
+            flip_count: 0,
+            flip_window_start_ts: 0,
+        });
+    }
+    if *info.owner != crate::ID {
+        return err!(ErrorCode::AccountOwnedByWrongProgram);
+    }
+    let position = InvestorPosition::try_deserialize(&mut &info.try_borrow_data()?[..])?;
+    let address = Pubkey::create_program_address(
+        &[b"position", market.as_ref(), position.owner.as_ref(), &[position.bump]],
+        &crate::ID,
+    )
+    .map_err(|_| error!(ErrorCode::ConstraintSeeds))?;
+    if info.key() != address {
+        return err!(ErrorCode::ConstraintSeeds);
+    }
+    if position.owner != trader {
+        return err!(MarketError::Unauthorized);
+    }
+    Ok(position)
+}
+
+/// Allocates the position PDA owned by this program, topping up rather than
+/// creating when someone has already sent it lamports
+fn create_position_account<'info>(
+    accounts: &Buy<'info>,
+    system_program: &Program<'info, System>,
+    position: &AccountInfo<'info>,
+    seeds: &[&[u8]],
+) -> Result<()> {
+    let program = system_program.to_account_info();
+    let payer = accounts.buyer.to_account_info();
+    let rent = Rent::get()?.minimum_balance(InvestorPosition::LEN);
+    let signer = &[seeds];
+    if position.lamports() == 0 {
+        return system_program::create_account(
+            CpiContext::new_with_signer(program, system_program::CreateAccount { from: payer, to: position.clone() }, signer),
+            rent,
+            InvestorPosition::LEN as u64,
+            &crate::ID,
+        );
+    }
+    let top_up = rent.saturating_sub(position.lamports());
+    if top_up > 0 {
+        system_program::transfer(
+            CpiContext::new(program.clone(), system_program::Transfer { from: payer, to: position.clone() }),
+            top_up,
+        )?;
+    }
+    system_program::allocate(
+        CpiContext::new_with_signer(program.clone(), system_program::Allocate { account_to_allocate: position.clone() }, signer),
+        InvestorPosition::LEN as u64,
+    )?;
+    system_program::assign(
+        CpiContext::new_with_signer(program, system_program::Assign { account_to_assign: position.clone() }, signer),
+        &crate::ID,
+    )
 }
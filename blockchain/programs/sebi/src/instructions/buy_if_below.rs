@@ -0,0 +1,9 @@
+use anchor_lang::prelude::*;
+use crate::instructions::buy::{self, Buy};
+
+/// Same accounts and checks as buy, plus a price ceiling: the effective
+/// price (auction or attestation included) must be <= max_price, and the
+/// total cost <= max_cost.
+pub fn handler(ctx: Context<Buy>, amount: u64, max_price: u128, max_cost: u64) -> Result<()> {
+    buy::execute(ctx, amount, max_price, max_cost)
+}
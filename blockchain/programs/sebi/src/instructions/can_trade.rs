@@ -0,0 +1,161 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token::TokenAccount;
+use crate::state::{InvestorPosition, Market};
+use crate::clock;
+use crate::events::TradeSide;
+
+#[derive(Accounts)]
+pub struct CanTrade<'info> {
+    pub market: Account<'info, Market>,
+
+    /// CHECK: the wallet being checked; it does not sign
+    pub trader: UncheckedAccount<'info>,
+
+    #[account(constraint = trader_usdc.owner == trader.key() && trader_usdc.mint == market.usdc_mint)]
+    pub trader_usdc: Account<'info, TokenAccount>,
+
+    /// Left out for a first-time buyer, whose bond account buy would create;
+    /// a sell without it is reported as InsufficientBalance
+    #[account(constraint = trader_bond.owner == trader.key() && trader_bond.mint == market.bond_mint)]
+    pub trader_bond: Option<Account<'info, TokenAccount>>,
+
+    #[account(constraint = vault_bond.key() == market.vault_bond)]
+    pub vault_bond: Account<'info, TokenAccount>,
+
+    #[account(constraint = vault_usdc.key() == market.vault_usdc)]
+    pub vault_usdc: Account<'info, TokenAccount>,
+
+    /// CHECK: the trader's position, as buy and sell would be passed it. May
+    /// not exist yet, since buy creates it when given system_program.
+    #[account(seeds = [b"position", market.key().as_ref(), trader.key().as_ref()], bump)]
+    pub investor_position: Option<UncheckedAccount<'info>>,
+}
+
+/// First gate a trade would fail, in the order buy and sell check them
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, Debug)]
+#[repr(u8)]
+pub enum TradeBlock {
+    None = 0,
+    Terminated = 1,
+    Paused = 2,
+    Phase = 3,
+    SellDisabled = 4,
+    TradeTooLarge = 5,
+    LowInventory = 6,
+    /// vault_bond holds fewer bonds than the buy
+    InsufficientInventory = 7,
+    /// vault_usdc can't pay the sell, before any insurance fallback
+    InsufficientVaultFunds = 8,
+    /// trader_usdc can't pay the buy, or trader_bond holds fewer bonds than the sell
+    InsufficientBalance = 9,
+    /// the price strays further from the last trade than max_slippage_bps
+    Slippage = 10,
+    /// vault_usdc has not yet reached min_initial_funding
+    NotFunded = 11,
+    /// the trade's quote amount exceeds max_notional
+    NotionalTooLarge = 12,
+    /// investor_position traded within the market's trade_cooldown_secs
+    TradeCooldown = 13,
+    /// the market has a trade cooldown or wash check and no investor_position
+    /// was passed, or, for a sell, it doesn't exist yet
+    PositionRequired = 14,
+    /// the trade would take investor_position past the market's wash_flip_threshold
+    WashTradingSuspected = 15,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug)]
+pub struct TradeCheck {
+    pub allowed: bool,
+    /// A `TradeBlock` discriminant
+    pub reason_code: u8,
+}
+
+/// Pre-flights a buy or sell of `amount` at the stored or auction price,
+/// returning the result via return data without mutating anything.
+/// Attested prices, insurance and compliance callbacks are not evaluated.
+pub fn handler(ctx: Context<CanTrade>, side: TradeSide, amount: u64) -> Result<TradeCheck> {
+    let now = clock::now()?;
+    let mut market = (*ctx.accounts.market).clone();
+    market.sync_auction_price(now)?;
+
+    let position = match &ctx.accounts.investor_position {
+        Some(info) if !info.data_is_empty() => Some(InvestorPosition::try_deserialize(&mut &info.try_borrow_data()?[..])?),
+        _ => None,
+    };
+    let block = match side {
+        TradeSide::Buy => buy_block(&market, ctx.accounts, position.as_ref(), amount, now)?,
+        TradeSide::Sell => sell_block(&market, ctx.accounts, position.as_ref(), amount, now)?,
+    };
+    Ok(TradeCheck { allowed: block == TradeBlock::None, reason_code: block as u8 })
+}
+
+fn buy_block(market: &Market, accounts: &CanTrade, position: Option<&InvestorPosition>, amount: u64, now: i64) -> Result<TradeBlock> {
+    let inventory = accounts.vault_bond.amount;
+    Ok(if market.terminated {
+        TradeBlock::Terminated
+    } else if market.is_halted(now) {
+        TradeBlock::Paused
+    } else if !market.phase.allows_buy() {
+        TradeBlock::Phase
+    } else if market.exceeds_max_trade(amount) {
+        TradeBlock::TradeTooLarge
+    } else if market.low_inventory_paused && inventory < market.low_inventory_threshold {
+        TradeBlock::LowInventory
+    } else if market.exceeds_slippage(market.price_per_token) {
+        TradeBlock::Slippage
+    } else if market.exceeds_max_notional(market.buy_cost(amount)?) {
+        TradeBlock::NotionalTooLarge
+    } else if let Some(block) = position_block(market, position, &TradeSide::Buy, accounts.investor_position.is_some(), now) {
+        block
+    } else if inventory < amount {
+        TradeBlock::InsufficientInventory
+    } else if accounts.trader_usdc.amount < market.buy_cost(amount)? {
+        TradeBlock::InsufficientBalance
+    } else {
+        TradeBlock::None
+    })
+}
+
+fn sell_block(market: &Market, accounts: &CanTrade, position: Option<&InvestorPosition>, amount: u64, now: i64) -> Result<TradeBlock> {
+    Ok(if market.terminated {
+        TradeBlock::Terminated
+    } else if market.is_halted(now) {
+        TradeBlock::Paused
+    } else if !market.phase.allows_sell() {
+        TradeBlock::Phase
+    } else if !market.sell_enabled {
+        TradeBlock::SellDisabled
+    } else if market.exceeds_max_trade(amount) {
+        TradeBlock::TradeTooLarge
+    } else if market.exceeds_slippage(market.price_per_token) {
+        TradeBlock::Slippage
+    } else if market.exceeds_max_notional(market.sell_proceeds(amount)?) {
+        TradeBlock::NotionalTooLarge
+    } else if !market.is_funded(accounts.vault_usdc.amount) {
+        TradeBlock::NotFunded
+    } else if accounts.vault_usdc.amount < market.sell_proceeds(amount)? {
+        TradeBlock::InsufficientVaultFunds
+    } else if let Some(block) = position_block(market, position, &TradeSide::Sell, false, now) {
+        block
+    } else if accounts.trader_bond.as_ref().map_or(0, |bond| bond.amount) < amount {
+        TradeBlock::InsufficientBalance
+    } else {
+        TradeBlock::None
+    })
+}
+
+/// `creatable` when the trade would create a passed position that doesn't exist yet
+fn position_block(
+    market: &Market,
+    position: Option<&InvestorPosition>,
+    side: &TradeSide,
+    creatable: bool,
+    now: i64,
+) -> Option<TradeBlock> {
+    match position {
+        Some(position) if position.in_cooldown(market.trade_cooldown_secs, now) => Some(TradeBlock::TradeCooldown),
+        Some(position) if position.is_wash_suspected(market, side, now) => Some(TradeBlock::WashTradingSuspected),
+        None if market.requires_position() && !creatable => Some(TradeBlock::PositionRequired),
+        _ => None,
+    }
+}
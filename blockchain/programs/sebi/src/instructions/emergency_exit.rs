@@ -0,0 +1,86 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token::{self, Token, TokenAccount, Transfer};
+use crate::state::Market;
+use crate::errors::MarketError;
+use crate::events::EmergencyExitEvent;
+
+#[derive(Accounts)]
+pub struct EmergencyExit<'info> {
+    #[account(mut, seeds = [b"market", market.bond_mint.as_ref()], bump = market.bump)]
+    pub market: Account<'info, Market>,
+
+    pub holder: Signer<'info>,
+
+    #[account(mut, constraint = holder_bond.owner == holder.key())]
+    pub holder_bond: Account<'info, TokenAccount>,
+
+    #[account(mut, constraint = holder_usdc.owner == holder.key())]
+    pub holder_usdc: Account<'info, TokenAccount>,
+
+    #[account(mut, constraint = vault_bond.key() == market.vault_bond)]
+    pub vault_bond: Account<'info, TokenAccount>,
+
+    #[account(mut, constraint = vault_usdc.key() == market.vault_usdc)]
+    pub vault_usdc: Account<'info, TokenAccount>,
+
+    pub token_program: Program<'info, Token>,
+}
+
+/// Returns `amount` bonds for their share of vault_usdc at
+/// `Market::emergency_exit_rate`. Ignores pause, phase and sell_enabled;
+/// only the admin's emergency_exit_enabled opt-in gates it.
+pub fn handler(ctx: Context<EmergencyExit>, amount: u64) -> Result<()> {
+    let market = &ctx.accounts.market;
+    if !market.emergency_exit_enabled {
+        return err!(MarketError::EmergencyExitDisabled);
+    }
+    // registered redemption claims are owed vault_usdc first
+    if market.redemption_pending {
+        return err!(MarketError::RedemptionPending);
+    }
+    if market.vault_authority != market.key() {
+        return err!(MarketError::InvalidVaultAuthority);
+    }
+
+    let rate = market.emergency_exit_rate(amount, ctx.accounts.vault_usdc.amount)?;
+    let paid = market.sell_proceeds_at(amount, rate)?;
+
+    token::transfer(
+        CpiContext::new(
+            ctx.accounts.token_program.to_account_info(),
+            Transfer {
+                from: ctx.accounts.holder_bond.to_account_info(),
+                to: ctx.accounts.vault_bond.to_account_info(),
+                authority: ctx.accounts.holder.to_account_info(),
+            },
+        ),
+        amount,
+    )?;
+
+    let seeds = market.signer_seeds();
+    let signer = &[&seeds[..]];
+    if paid > 0 {
+        token::transfer(
+            CpiContext::new_with_signer(
+                ctx.accounts.token_program.to_account_info(),
+                Transfer {
+                    from: ctx.accounts.vault_usdc.to_account_info(),
+                    to: ctx.accounts.holder_usdc.to_account_info(),
+                    authority: ctx.accounts.market.to_account_info(),
+                },
+                signer,
+            ),
+            paid,
+        )?;
+    }
+
+    ctx.accounts.market.record_sell(amount, paid)?;
+    emit!(EmergencyExitEvent {
+        market: ctx.accounts.market.key(),
+        holder: ctx.accounts.holder.key(),
+        amount,
+        paid,
+        rate,
+    });
+    Ok(())
+}
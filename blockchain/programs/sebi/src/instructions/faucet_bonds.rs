@@ -0,0 +1,65 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token::{self, Token, TokenAccount, Transfer};
+use crate::state::{FaucetClaim, Market};
+use crate::errors::MarketError;
+use crate::clock;
+
+#[derive(Accounts)]
+pub struct FaucetBonds<'info> {
+    #[account(seeds = [b"market", market.bond_mint.as_ref()], bump = market.bump)]
+    pub market: Account<'info, Market>,
+
+    #[account(
+        init_if_needed,
+        payer = wallet,
+        space = FaucetClaim::LEN,
+        seeds = [b"faucet", market.key().as_ref(), wallet.key().as_ref()],
+        bump
+    )]
+    pub faucet_claim: Account<'info, FaucetClaim>,
+
+    #[account(mut)]
+    pub wallet: Signer<'info>,
+
+    #[account(mut, constraint = wallet_bond.owner == wallet.key() && wallet_bond.mint == market.bond_mint)]
+    pub wallet_bond: Account<'info, TokenAccount>,
+
+    #[account(mut, constraint = vault_bond.key() == market.vault_bond)]
+    pub vault_bond: Account<'info, TokenAccount>,
+
+    pub token_program: Program<'info, Token>,
+    pub system_program: Program<'info, System>,
+}
+
+/// Devnet only: gives any wallet up to FAUCET_BONDS_PER_DAY vault bonds a
+/// day for free. Compiled in solely under the `devnet-faucet` feature,
+/// which must never be enabled for a mainnet build. Faucet bonds bypass
+/// trade accounting, so net_bonds_out and realized_pnl don't see them.
+pub fn handler(ctx: Context<FaucetBonds>, amount: u64) -> Result<()> {
+    let claim = &mut ctx.accounts.faucet_claim;
+    claim.market = ctx.accounts.market.key();
+    claim.wallet = ctx.accounts.wallet.key();
+    claim.bump = ctx.bumps.faucet_claim;
+    claim.claim(amount, clock::now()?)?;
+
+    let market = &ctx.accounts.market;
+    if market.vault_authority != market.key() {
+        return err!(MarketError::InvalidVaultAuthority);
+    }
+    let seeds = market.signer_seeds();
+    let signer = &[&seeds[..]];
+    token::transfer(
+        CpiContext::new_with_signer(
+            ctx.accounts.token_program.to_account_info(),
+            Transfer {
+                from: ctx.accounts.vault_bond.to_account_info(),
+                to: ctx.accounts.wallet_bond.to_account_info(),
+                authority: ctx.accounts.market.to_account_info(),
+            },
+            signer,
+        ),
+        amount,
+    )?;
+    msg!("Faucet sent {} bonds, {} today", amount, ctx.accounts.faucet_claim.claimed);
+    Ok(())
+}
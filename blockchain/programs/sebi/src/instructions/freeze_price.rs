@@ -0,0 +1,34 @@
+use anchor_lang::prelude::*;
+use crate::state::Market;
+use crate::errors::MarketError;
+use crate::clock;
+
+#[derive(Accounts)]
+pub struct FreezePrice<'info> {
+    #[account(mut, has_one = admin, constraint = !market.terminated @ MarketError::MarketTerminated)]
+    pub market: Account<'info, Market>,
+    pub admin: Signer<'info>,
+}
+
+/// Irreversible: there is deliberately no unfreeze instruction.
+pub fn handler(ctx: Context<FreezePrice>) -> Result<()> {
+    let market = &mut ctx.accounts.market;
+    if ctx.accounts.admin.key() != market.admin {
+        return err!(MarketError::Unauthorized);
+    }
+    if market.price_frozen {
+        return err!(MarketError::PriceFrozen);
+    }
+    // freezing after an auction pins its end_price
+    if market.auction_enabled {
+        let now = clock::now()?;
+        if market.is_auction_running(now) {
+            return err!(MarketError::AuctionInProgress);
+        }
+        market.sync_auction_price(now)?;
+        market.auction_enabled = false;
+    }
+    market.price_frozen = true;
+    msg!("Price frozen at {}", market.price_per_token);
+    Ok(())
+}
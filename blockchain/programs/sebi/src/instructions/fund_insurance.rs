@@ -0,0 +1,46 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token::{self, Token, TokenAccount, Transfer};
+use crate::state::Market;
+use crate::errors::MarketError;
+use crate::events::InsuranceFundedEvent;
+
+#[derive(Accounts)]
+pub struct FundInsurance<'info> {
+    #[account(mut, constraint = !market.terminated @ MarketError::MarketTerminated)]
+    pub market: Account<'info, Market>,
+
+    pub funder: Signer<'info>,
+
+    #[account(mut, constraint = funder_usdc.owner == funder.key())]
+    pub funder_usdc: Account<'info, TokenAccount>,
+
+    #[account(mut, constraint = insurance_vault.key() == market.insurance_vault)]
+    pub insurance_vault: Account<'info, TokenAccount>,
+
+    pub token_program: Program<'info, Token>,
+}
+
+pub fn handler(ctx: Context<FundInsurance>, amount: u64) -> Result<()> {
+    token::transfer(
+        CpiContext::new(
+            ctx.accounts.token_program.to_account_info(),
+            Transfer {
+                from: ctx.accounts.funder_usdc.to_account_info(),
+                to: ctx.accounts.insurance_vault.to_account_info(),
+                authority: ctx.accounts.funder.to_account_info(),
+            },
+        ),
+        amount,
+    )?;
+
+    let market = &mut ctx.accounts.market;
+    market.insurance_balance = market.insurance_balance.checked_add(amount).ok_or(MarketError::MathOverflow)?;
+
+    emit!(InsuranceFundedEvent {
+        market: market.key(),
+        funder: ctx.accounts.funder.key(),
+        amount,
+        balance: market.insurance_balance,
+    });
+    Ok(())
+}
@@ -0,0 +1,16 @@
+use anchor_lang::prelude::*;
+use crate::state::{PriceHistory, PricePoint};
+
+#[derive(Accounts)]
+pub struct GetPriceHistory<'info> {
+    /// CHECK: only used to derive the history PDA
+    pub market: UncheckedAccount<'info>,
+
+    #[account(seeds = [b"price_history", market.key().as_ref()], bump = price_history.bump)]
+    pub price_history: Account<'info, PriceHistory>,
+}
+
+/// Returns the stored price points, oldest first, via return data.
+pub fn handler(ctx: Context<GetPriceHistory>) -> Result<Vec<PricePoint>> {
+    Ok(ctx.accounts.price_history.chronological())
+}
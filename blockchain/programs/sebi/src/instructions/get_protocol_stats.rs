@@ -0,0 +1,25 @@
+use anchor_lang::prelude::*;
+use crate::state::ProtocolStats;
+
+#[derive(Accounts)]
+pub struct GetProtocolStats<'info> {
+    #[account(seeds = [b"protocol_stats"], bump = protocol_stats.bump)]
+    pub protocol_stats: Account<'info, ProtocolStats>,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug)]
+pub struct ProtocolStatsView {
+    pub markets_created: u64,
+    pub trade_count: u64,
+    pub total_volume: u128,
+}
+
+/// Returns the cross-market totals via return data.
+pub fn handler(ctx: Context<GetProtocolStats>) -> Result<ProtocolStatsView> {
+    let stats = &ctx.accounts.protocol_stats;
+    Ok(ProtocolStatsView {
+        markets_created: stats.markets_created,
+        trade_count: stats.trade_count,
+        total_volume: stats.total_volume,
+    })
+}
@@ -0,0 +1,14 @@
+use anchor_lang::prelude::*;
+use crate::state::RegistryPage;
+
+#[derive(Accounts)]
+#[instruction(page: u64)]
+pub struct GetRegistryPage<'info> {
+    #[account(seeds = [b"registry_page".as_ref(), &page.to_le_bytes()], bump = registry_page.bump)]
+    pub registry_page: Account<'info, RegistryPage>,
+}
+
+/// Returns the market keys stored on one registry page via return data.
+pub fn handler(ctx: Context<GetRegistryPage>, _page: u64) -> Result<Vec<Pubkey>> {
+    Ok(ctx.accounts.registry_page.markets.clone())
+}
@@ -0,0 +1,36 @@
+use anchor_lang::prelude::*;
+use crate::program::Sebi;
+use crate::state::ProgramConfig;
+
+#[derive(Accounts)]
+pub struct InitConfig<'info> {
+    #[account(
+        init,
+        payer = authority,
+        space = ProgramConfig::LEN,
+        seeds = [b"config"],
+        bump
+    )]
+    pub config: Account<'info, ProgramConfig>,
+
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    /// Only the upgrade authority may create the config
+    #[account(constraint = program.programdata_address()? == Some(program_data.key()))]
+    pub program: Program<'info, Sebi>,
+
+    #[account(constraint = program_data.upgrade_authority_address == Some(authority.key()))]
+    pub program_data: Account<'info, ProgramData>,
+
+    pub system_program: Program<'info, System>,
+}
+
+pub fn handler(ctx: Context<InitConfig>) -> Result<()> {
+    let config = &mut ctx.accounts.config;
+    config.authority = ctx.accounts.authority.key();
+    config.approved_quote_mints = Vec::new();
+    config.bump = ctx.bumps.config;
+    msg!("Program config authority: {}", config.authority);
+    Ok(())
+}
@@ -0,0 +1,30 @@
+use anchor_lang::prelude::*;
+use crate::state::{DailyStats, Market};
+
+#[derive(Accounts)]
+pub struct InitDailyStats<'info> {
+    pub market: Account<'info, Market>,
+
+    #[account(
+        init,
+        payer = payer,
+        space = DailyStats::LEN,
+        seeds = [b"daily_stats", market.key().as_ref()],
+        bump
+    )]
+    pub daily_stats: Account<'info, DailyStats>,
+
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+/// Permissionless; the account only accumulates trades whose callers pass it.
+pub fn handler(ctx: Context<InitDailyStats>) -> Result<()> {
+    let stats = &mut ctx.accounts.daily_stats;
+    stats.market = ctx.accounts.market.key();
+    stats.bump = ctx.bumps.daily_stats;
+    msg!("Daily stats initialized for {}", stats.market);
+    Ok(())
+}
@@ -0,0 +1,47 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token::{Mint, Token, TokenAccount};
+use crate::state::Market;
+use crate::errors::MarketError;
+
+#[derive(Accounts)]
+pub struct InitInsuranceVault<'info> {
+    #[account(
+        mut,
+        has_one = admin,
+        has_one = usdc_mint,
+        constraint = !market.terminated @ MarketError::MarketTerminated
+    )]
+    pub market: Account<'info, Market>,
+
+    pub usdc_mint: Account<'info, Mint>,
+
+    #[account(
+        init,
+        payer = admin,
+        seeds = [b"insurance", market.key().as_ref()],
+        bump,
+        token::mint = usdc_mint,
+        token::authority = market
+    )]
+    pub insurance_vault: Account<'info, TokenAccount>,
+
+    #[account(mut)]
+    pub admin: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+    pub token_program: Program<'info, Token>,
+    pub rent: Sysvar<'info, Rent>,
+}
+
+pub fn handler(ctx: Context<InitInsuranceVault>) -> Result<()> {
+    let market = &mut ctx.accounts.market;
+    if ctx.accounts.admin.key() != market.admin {
+        return err!(MarketError::Unauthorized);
+    }
+    if market.insurance_vault != Pubkey::default() {
+        return err!(MarketError::InsuranceAlreadyInitialized);
+    }
+    market.insurance_vault = ctx.accounts.insurance_vault.key();
+    msg!("Insurance vault: {}", market.insurance_vault);
+    Ok(())
+}
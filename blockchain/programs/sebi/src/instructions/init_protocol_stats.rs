@@ -0,0 +1,30 @@
+use anchor_lang::prelude::*;
+use crate::state::{ProgramConfig, ProtocolStats};
+
+#[derive(Accounts)]
+pub struct InitProtocolStats<'info> {
+    #[account(
+        init,
+        payer = authority,
+        space = ProtocolStats::LEN,
+        seeds = [b"protocol_stats"],
+        bump
+    )]
+    pub protocol_stats: Account<'info, ProtocolStats>,
+
+    #[account(seeds = [b"config"], bump = config.bump, has_one = authority)]
+    pub config: Account<'info, ProgramConfig>,
+
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+/// Creates the stats singleton with zeroed totals. Markets created earlier
+/// are not back-filled.
+pub fn handler(ctx: Context<InitProtocolStats>) -> Result<()> {
+    ctx.accounts.protocol_stats.bump = ctx.bumps.protocol_stats;
+    msg!("Protocol stats initialized");
+    Ok(())
+}
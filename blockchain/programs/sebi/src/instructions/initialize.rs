@@ -1,9 +1,12 @@
 use anchor_lang::prelude::*;
-use anchor_spl::token::{self, Mint, Token, TokenAccount};
-use crate::state::Market;
+use anchor_spl::token::{Mint, Token, TokenAccount};
+use crate::state::{AdminMarketCount, AuctionConfig, EventVerbosity, Market, MarketPhase, MarketRegistry, PriceBasis, PriceHistory, ProgramConfig, ProtocolStats, RegistryPage};
+use crate::errors::MarketError;
+use crate::constants::MAX_PRICE_SCALE;
+use crate::clock;
 
 #[derive(Accounts)]
-#[instruction(price_per_token: u128)]
+#[instruction(price_per_token: u128, price_scale: u32, sell_enabled: bool, auction: Option<AuctionConfig>, price_basis: PriceBasis, lot_size: u64)]
 pub struct InitializeMarket<'info> {
     #[account(
         init,
@@ -36,6 +39,50 @@ pub struct InitializeMarket<'info> {
     )]
     pub vault_usdc: Account<'info, TokenAccount>,
 
+    #[account(
+        init,
+        payer = admin,
+        space = PriceHistory::LEN,
+        seeds = [b"price_history", market.key().as_ref()],
+        bump
+    )]
+    pub price_history: Account<'info, PriceHistory>,
+
+    #[account(seeds = [b"config"], bump = config.bump)]
+    pub config: Account<'info, ProgramConfig>,
+
+    #[account(
+        init_if_needed,
+        payer = admin,
+        space = MarketRegistry::LEN,
+        seeds = [b"registry"],
+        bump
+    )]
+    pub registry: Account<'info, MarketRegistry>,
+
+    /// Page the new market is appended to: market_count / RegistryPage::MAX_MARKETS
+    #[account(
+        init_if_needed,
+        payer = admin,
+        space = RegistryPage::LEN,
+        seeds = [b"registry_page".as_ref(), &(registry.market_count / RegistryPage::MAX_MARKETS as u64).to_le_bytes()],
+        bump
+    )]
+    pub registry_page: Account<'info, RegistryPage>,
+
+    #[account(
+        init_if_needed,
+        payer = admin,
+        space = AdminMarketCount::LEN,
+        seeds = [b"admin_markets", admin.key().as_ref()],
+        bump
+    )]
+    pub admin_market_count: Account<'info, AdminMarketCount>,
+
+    /// Program-wide totals; optional so markets can be created before it is created
+    #[account(mut, seeds = [b"protocol_stats"], bump = protocol_stats.bump)]
+    pub protocol_stats: Option<Account<'info, ProtocolStats>>,
+
     #[account(mut)]
     pub admin: Signer<'info>,
 
@@ -44,17 +91,96 @@ pub struct InitializeMarket<'info> {
     pub rent: Sysvar<'info, Rent>,
 }
 
-pub fn handler(ctx: Context<InitializeMarket>, price_per_token: u128) -> Result<()> {
+pub fn handler(
+    ctx: Context<InitializeMarket>,
+    price_per_token: u128,
+    price_scale: u32,
+    sell_enabled: bool,
+    auction: Option<AuctionConfig>,
+    price_basis: PriceBasis,
+    lot_size: u64,
+) -> Result<()> {
+    if ctx.accounts.bond_mint.key() == ctx.accounts.usdc_mint.key() {
+        return err!(MarketError::SameMint);
+    }
+    if !ctx.accounts.config.is_quote_mint_approved(&ctx.accounts.usdc_mint.key()) {
+        return err!(MarketError::UnapprovedQuoteMint);
+    }
+    let max_markets = ctx.accounts.config.max_markets_per_admin;
+    if max_markets > 0 && ctx.accounts.admin_market_count.count >= max_markets {
+        return err!(MarketError::MarketLimitReached);
+    }
+    if price_scale > MAX_PRICE_SCALE {
+        return err!(MarketError::InvalidPriceScale);
+    }
+    if auction.is_some_and(|a| a.start_ts >= a.end_ts) {
+        return err!(MarketError::InvalidAuction);
+    }
+    if price_basis == PriceBasis::PerLot && lot_size == 0 {
+        return err!(MarketError::InvalidLotSize);
+    }
+
+    let now = clock::now()?;
     let market = &mut ctx.accounts.market;
     market.bond_mint = ctx.accounts.bond_mint.key();
     market.usdc_mint = ctx.accounts.usdc_mint.key();
     market.price_per_token = price_per_token;
+    market.price_scale = price_scale;
+    market.price_basis = price_basis;
+    market.lot_size = lot_size;
+    market.bond_decimals = ctx.accounts.bond_mint.decimals;
+    market.usdc_decimals = ctx.accounts.usdc_mint.decimals;
+    market.decimals_set = true;
+    market.refresh_display_price()?;
+    // an auction overrides price_per_token from the start
+    if let Some(auction) = auction {
+        market.auction_enabled = true;
+        market.auction = auction;
+        market.sync_auction_price(now)?;
+    }
     market.vault_bond = ctx.accounts.vault_bond.key();
     market.vault_usdc = ctx.accounts.vault_usdc.key();
     market.admin = ctx.accounts.admin.key();
+    market.price_updater = market.admin;
     market.paused = false;
-    market.bump = *ctx.bumps.get("market").unwrap();
+    market.sell_enabled = sell_enabled;
+    market.phase = MarketPhase::Active;
+    market.event_verbosity = EventVerbosity::Full;
+    market.version = Market::VERSION;
+    market.refresh_status();
+
+    // re-derive from the stored seeds so a bad bump fails here, not on the first signed CPI
+    market.bump = ctx.bumps.market;
+    let derived = Pubkey::create_program_address(&market.signer_seeds(), ctx.program_id)
+        .map_err(|_| MarketError::InvalidBump)?;
+    if derived != market.key() {
+        return err!(MarketError::InvalidBump);
+    }
+    market.vault_authority = market.key();
+
+    let market_key = market.key();
+    let history = &mut ctx.accounts.price_history;
+    history.market = market_key;
+    history.bump = ctx.bumps.price_history;
+    history.record(market.price_per_token, now);
+
+    let registry = &mut ctx.accounts.registry;
+    let page = &mut ctx.accounts.registry_page;
+    registry.bump = ctx.bumps.registry;
+    page.page = registry.market_count / RegistryPage::MAX_MARKETS as u64;
+    page.bump = ctx.bumps.registry_page;
+    page.markets.push(market_key);
+    registry.market_count = registry.market_count.checked_add(1).ok_or(MarketError::MathOverflow)?;
+
+    let admin_count = &mut ctx.accounts.admin_market_count;
+    admin_count.admin = ctx.accounts.admin.key();
+    admin_count.bump = ctx.bumps.admin_market_count;
+    admin_count.count = admin_count.count.checked_add(1).ok_or(MarketError::MathOverflow)?;
+
+    if let Some(stats) = &mut ctx.accounts.protocol_stats {
+        stats.markets_created = stats.markets_created.checked_add(1).ok_or(MarketError::MathOverflow)?;
+    }
 
-    msg!("Market initialized at price: {}", price_per_token);
+    msg!("Market initialized at price: {}", market.price_per_token);
     Ok(())
 }
@@ -0,0 +1,84 @@
+use anchor_lang::prelude::*;
+use anchor_lang::system_program::{self, Transfer};
+use anchor_spl::token::{Mint, TokenAccount};
+use crate::state::{Market, MarketV0, PriceHistory};
+use crate::errors::MarketError;
+use crate::clock;
+
+#[derive(Accounts)]
+pub struct MigrateMarket<'info> {
+    /// CHECK: a Market account in the v0 layout or an older version; its
+    /// discriminator, layout and admin are checked in the handler
+    #[account(mut, owner = crate::ID)]
+    pub market: UncheckedAccount<'info>,
+
+    /// v0 markets predate price history, so theirs is created here
+    #[account(
+        init_if_needed,
+        payer = admin,
+        space = PriceHistory::LEN,
+        seeds = [b"price_history", market.key().as_ref()],
+        bump
+    )]
+    pub price_history: Account<'info, PriceHistory>,
+
+    /// The market's bond mint and vault; a v0 market counts every bond
+    /// outside the vault as outstanding
+    pub bond_mint: Account<'info, Mint>,
+    pub vault_bond: Account<'info, TokenAccount>,
+
+    #[account(mut)]
+    pub admin: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+/// Rewrites a market account into the current layout, growing it to
+/// Market::LEN with the admin paying the extra rent. Fields the old layout
+/// lacked start at their defaults.
+pub fn handler(ctx: Context<MigrateMarket>) -> Result<()> {
+    let info = ctx.accounts.market.to_account_info();
+    let from_v0;
+    let market = {
+        let data = info.try_borrow_data()?;
+        if data.len() < 8 || data[..8] != *Market::DISCRIMINATOR {
+            return err!(MarketError::InvalidMarketAccount);
+        }
+        from_v0 = data.len() == MarketV0::LEN;
+        if from_v0 {
+            let legacy = MarketV0::try_from_slice(&data[8..]).map_err(|_| MarketError::InvalidMarketAccount)?;
+            if ctx.accounts.bond_mint.key() != legacy.bond_mint || ctx.accounts.vault_bond.key() != legacy.vault_bond {
+                return err!(MarketError::InvalidMarketAccount);
+            }
+            let outstanding = ctx.accounts.bond_mint.supply.saturating_sub(ctx.accounts.vault_bond.amount);
+            legacy.upgrade(info.key(), outstanding)?
+        } else {
+            Market::from_older_version(&data[8..])?
+        }
+    };
+    if ctx.accounts.admin.key() != market.admin {
+        return err!(MarketError::Unauthorized);
+    }
+
+    let rent = Rent::get()?.minimum_balance(Market::LEN).saturating_sub(info.lamports());
+    if rent > 0 {
+        system_program::transfer(
+            CpiContext::new(
+                ctx.accounts.system_program.to_account_info(),
+                Transfer { from: ctx.accounts.admin.to_account_info(), to: info.clone() },
+            ),
+            rent,
+        )?;
+    }
+    info.resize(Market::LEN)?;
+    market.try_serialize(&mut &mut info.try_borrow_mut_data()?[..])?;
+
+    let history = &mut ctx.accounts.price_history;
+    if from_v0 && history.market == Pubkey::default() {
+        history.market = info.key();
+        history.bump = ctx.bumps.price_history;
+        history.record(market.price_per_token, clock::now()?);
+    }
+    msg!("Market migrated to layout version {}", Market::VERSION);
+    Ok(())
+}
@@ -0,0 +1,88 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token::{Mint, Token, TokenAccount};
+use crate::state::{Market, ProgramConfig};
+use crate::errors::MarketError;
+use crate::events::QuoteMintMigratedEvent;
+
+#[derive(Accounts)]
+pub struct MigrateQuoteMint<'info> {
+    #[account(
+        mut,
+        has_one = admin,
+        has_one = vault_usdc,
+        constraint = !market.terminated @ MarketError::MarketTerminated
+    )]
+    pub market: Account<'info, Market>,
+
+    #[account(mut)]
+    pub admin: Signer<'info>,
+
+    pub vault_usdc: Account<'info, TokenAccount>,
+
+    pub new_usdc_mint: Account<'info, Mint>,
+
+    #[account(
+        init,
+        payer = admin,
+        token::mint = new_usdc_mint,
+        token::authority = market
+    )]
+    pub new_vault_usdc: Account<'info, TokenAccount>,
+
+    #[account(seeds = [b"config"], bump = config.bump)]
+    pub config: Account<'info, ProgramConfig>,
+
+    pub system_program: Program<'info, System>,
+    pub token_program: Program<'info, Token>,
+    pub rent: Sysvar<'info, Rent>,
+}
+
+/// Re-points usdc_mint and vault_usdc at a freshly created vault for
+/// `new_usdc_mint`. The admin first empties the old vault (withdraw), and
+/// the new mint must share its decimals so prices and the quote ledgers
+/// keep their meaning. The old vault is left in place, empty.
+pub fn handler(ctx: Context<MigrateQuoteMint>) -> Result<()> {
+    let market = &ctx.accounts.market;
+    if ctx.accounts.admin.key() != market.admin {
+        return err!(MarketError::Unauthorized);
+    }
+    let new_mint = &ctx.accounts.new_usdc_mint;
+    if new_mint.key() == market.bond_mint {
+        return err!(MarketError::SameMint);
+    }
+    if !ctx.accounts.config.is_quote_mint_approved(&new_mint.key()) {
+        return err!(MarketError::UnapprovedQuoteMint);
+    }
+    if new_mint.decimals != market.usdc_decimals {
+        return err!(MarketError::QuoteDecimalsMismatch);
+    }
+    if ctx.accounts.vault_usdc.amount > 0 {
+        return err!(MarketError::QuoteVaultNotEmpty);
+    }
+    if market.redemption_pending {
+        return err!(MarketError::RedemptionPending);
+    }
+    // the new vault is owned by the market PDA, as the old one was before any rotation
+    if market.vault_authority != market.key() {
+        return err!(MarketError::InvalidVaultAuthority);
+    }
+    // the insurance vault is a PDA fixed to the old mint and can't be recreated
+    if market.insurance_vault != Pubkey::default() {
+        return err!(MarketError::InsuranceAlreadyInitialized);
+    }
+
+    let market = &mut ctx.accounts.market;
+    let old_mint = market.usdc_mint;
+    let old_vault = market.vault_usdc;
+    market.usdc_mint = new_mint.key();
+    market.vault_usdc = ctx.accounts.new_vault_usdc.key();
+
+    emit!(QuoteMintMigratedEvent {
+        market: market.key(),
+        old_mint,
+        new_mint: market.usdc_mint,
+        old_vault,
+        new_vault: market.vault_usdc,
+    });
+    Ok(())
+}
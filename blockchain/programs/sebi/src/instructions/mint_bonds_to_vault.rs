@@ -0,0 +1,59 @@
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::program_option::COption;
+use anchor_spl::token::{self, Mint, MintTo, Token, TokenAccount};
+use crate::state::Market;
+use crate::errors::MarketError;
+
+#[derive(Accounts)]
+pub struct MintBondsToVault<'info> {
+    #[account(
+        mut,
+        has_one = admin,
+        has_one = bond_mint,
+        seeds = [b"market", market.bond_mint.as_ref()],
+        bump = market.bump,
+        constraint = !market.terminated @ MarketError::MarketTerminated
+    )]
+    pub market: Account<'info, Market>,
+    pub admin: Signer<'info>,
+
+    #[account(mut)]
+    pub bond_mint: Account<'info, Mint>,
+
+    #[account(mut, constraint = vault_bond.key() == market.vault_bond)]
+    pub vault_bond: Account<'info, TokenAccount>,
+
+    pub token_program: Program<'info, Token>,
+}
+
+/// Issues `amount` new bonds straight into vault_bond. Only works on
+/// markets whose PDA holds the bond mint authority.
+pub fn handler(ctx: Context<MintBondsToVault>, amount: u64) -> Result<()> {
+    let market = &ctx.accounts.market;
+    if ctx.accounts.admin.key() != market.admin {
+        return err!(MarketError::Unauthorized);
+    }
+    if ctx.accounts.bond_mint.mint_authority != COption::Some(market.key()) {
+        return err!(MarketError::NotMintAuthority);
+    }
+
+    let seeds = market.signer_seeds();
+    let signer = &[&seeds[..]];
+    token::mint_to(
+        CpiContext::new_with_signer(
+            ctx.accounts.token_program.to_account_info(),
+            MintTo {
+                mint: ctx.accounts.bond_mint.to_account_info(),
+                to: ctx.accounts.vault_bond.to_account_info(),
+                authority: ctx.accounts.market.to_account_info(),
+            },
+            signer,
+        ),
+        amount,
+    )?;
+
+    let market = &mut ctx.accounts.market;
+    market.total_issued = market.total_issued.checked_add(amount).ok_or(MarketError::MathOverflow)?;
+    msg!("Minted {} bonds, {} issued in total", amount, market.total_issued);
+    Ok(())
+}
@@ -0,0 +1,142 @@
+#![allow(ambiguous_glob_reexports)]
+
+pub mod initialize;
+pub mod init_config;
+pub mod set_quote_mint_approval;
+pub mod buy;
+pub mod buy_if_below;
+pub mod sell;
+pub mod sell_batch;
+pub mod update_price;
+pub mod set_price_tick;
+pub mod freeze_price;
+pub mod set_low_inventory_threshold;
+pub mod set_max_trade_amount;
+pub mod pause;
+pub mod pause_with_grace;
+pub mod set_circuit_breaker_authority;
+pub mod set_phase;
+pub mod withdraw;
+pub mod rescue_tokens;
+pub mod get_registry_page;
+pub mod init_insurance_vault;
+pub mod fund_insurance;
+pub mod program_info;
+pub mod tvl;
+pub mod refresh_display_price;
+pub mod set_decimals;
+pub mod get_price_history;
+pub mod twap;
+pub mod set_price_updater;
+pub mod rotate_vault_authority;
+pub mod terminate;
+pub mod init_protocol_stats;
+pub mod get_protocol_stats;
+pub mod open_redemption;
+pub mod register_redemption;
+pub mod settle_redemptions;
+pub mod mint_bonds_to_vault;
+pub mod set_compliance_program;
+pub mod set_withdraw_cooldown;
+pub mod can_trade;
+pub mod set_event_verbosity;
+pub mod set_trading_delegate;
+pub mod revoke_trading_delegate;
+pub mod set_max_slippage_bps;
+pub mod init_daily_stats;
+pub mod set_burn_unsold_at_maturity;
+pub mod burn_unsold;
+pub mod set_min_initial_funding;
+pub mod emergency_exit;
+pub mod set_emergency_exit_enabled;
+pub mod validate_config;
+pub mod set_notify_program;
+pub mod set_max_markets_per_admin;
+pub mod migrate_quote_mint;
+#[cfg(feature = "devnet-faucet")]
+pub mod faucet_bonds;
+pub mod set_max_notional;
+pub mod set_approved_payee;
+pub mod requote_and_buy;
+pub mod requote_and_sell;
+pub mod update_prices_batch;
+pub mod set_max_cumulative_withdraw;
+pub mod authorize_relayer;
+pub mod revoke_relayer;
+pub mod required_backing;
+pub mod migrate_market;
+pub mod open_investor_position;
+pub mod set_trade_cooldown;
+pub mod withdraw_insurance;
+pub mod set_price_guard;
+pub mod set_wash_check;
+
+pub use initialize::*;
+pub use init_config::*;
+pub use set_quote_mint_approval::*;
+pub use buy::*;
+pub use sell::*;
+pub use sell_batch::*;
+pub use update_price::*;
+pub use set_price_tick::*;
+pub use freeze_price::*;
+pub use set_low_inventory_threshold::*;
+pub use set_max_trade_amount::*;
+pub use pause::*;
+pub use pause_with_grace::*;
+pub use set_circuit_breaker_authority::*;
+pub use set_phase::*;
+pub use withdraw::*;
+pub use rescue_tokens::*;
+pub use get_registry_page::*;
+pub use init_insurance_vault::*;
+pub use fund_insurance::*;
+pub use program_info::*;
+pub use tvl::*;
+pub use refresh_display_price::*;
+pub use set_decimals::*;
+pub use get_price_history::*;
+pub use twap::*;
+pub use set_price_updater::*;
+pub use rotate_vault_authority::*;
+pub use terminate::*;
+pub use init_protocol_stats::*;
+pub use get_protocol_stats::*;
+pub use open_redemption::*;
+pub use register_redemption::*;
+pub use settle_redemptions::*;
+pub use mint_bonds_to_vault::*;
+pub use set_compliance_program::*;
+pub use set_withdraw_cooldown::*;
+pub use can_trade::*;
+pub use set_event_verbosity::*;
+pub use set_trading_delegate::*;
+pub use revoke_trading_delegate::*;
+pub use set_max_slippage_bps::*;
+pub use init_daily_stats::*;
+pub use set_burn_unsold_at_maturity::*;
+pub use burn_unsold::*;
+pub use set_min_initial_funding::*;
+pub use emergency_exit::*;
+pub use set_emergency_exit_enabled::*;
+pub use validate_config::*;
+pub use set_notify_program::*;
+pub use set_max_markets_per_admin::*;
+pub use migrate_quote_mint::*;
+#[cfg(feature = "devnet-faucet")]
+pub use faucet_bonds::*;
+pub use set_max_notional::*;
+pub use set_approved_payee::*;
+pub use requote_and_buy::*;
+pub use requote_and_sell::*;
+pub use update_prices_batch::*;
+pub use set_max_cumulative_withdraw::*;
+pub use authorize_relayer::*;
+pub use revoke_relayer::*;
+pub use required_backing::*;
+pub use migrate_market::*;
+pub use open_investor_position::*;
+pub use set_trade_cooldown::*;
+pub use withdraw_insurance::*;
+pub use set_price_guard::*;
+pub use set_wash_check::*;
@@ -0,0 +1,33 @@
+use anchor_lang::prelude::*;
+use crate::state::{InvestorPosition, Market};
+use crate::errors::MarketError;
+
+#[derive(Accounts)]
+pub struct OpenInvestorPosition<'info> {
+    #[account(constraint = !market.terminated @ MarketError::MarketTerminated)]
+    pub market: Account<'info, Market>,
+
+    #[account(
+        init_if_needed,
+        payer = owner,
+        space = InvestorPosition::LEN,
+        seeds = [b"position", market.key().as_ref(), owner.key().as_ref()],
+        bump
+    )]
+    pub investor_position: Account<'info, InvestorPosition>,
+
+    #[account(mut)]
+    pub owner: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+/// Idempotent: reopening keeps the recorded last trade, so it can't reset a cooldown.
+pub fn handler(ctx: Context<OpenInvestorPosition>) -> Result<()> {
+    let position = &mut ctx.accounts.investor_position;
+    position.market = ctx.accounts.market.key();
+    position.owner = ctx.accounts.owner.key();
+    position.bump = ctx.bumps.investor_position;
+    msg!("Investor position opened for {}", position.owner);
+    Ok(())
+}
@@ -0,0 +1,55 @@
+use anchor_lang::prelude::*;
+use crate::state::{Market, MarketPhase, RedemptionWindow};
+use crate::errors::MarketError;
+use crate::clock;
+use crate::math::u64_to_i64;
+
+#[derive(Accounts)]
+pub struct OpenRedemption<'info> {
+    #[account(mut, has_one = admin)]
+    pub market: Account<'info, Market>,
+
+    #[account(
+        init,
+        payer = admin,
+        space = RedemptionWindow::LEN,
+        seeds = [b"redemption", market.key().as_ref()],
+        bump
+    )]
+    pub redemption_window: Account<'info, RedemptionWindow>,
+
+    #[account(mut)]
+    pub admin: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+/// Opens the market's one redemption window for `window_secs`. Holders are
+/// owed `redemption_price` per bond, in price_per_token units, and share
+/// vault_usdc pro rata if it falls short. A terminated market can't reach
+/// Matured, so it may open its window from any phase.
+pub fn handler(ctx: Context<OpenRedemption>, redemption_price: u128, window_secs: u64) -> Result<()> {
+    let market = &mut ctx.accounts.market;
+    if ctx.accounts.admin.key() != market.admin {
+        return err!(MarketError::Unauthorized);
+    }
+    if market.phase != MarketPhase::Matured && !market.terminated {
+        return err!(MarketError::InvalidPhase);
+    }
+    if window_secs == 0 {
+        return err!(MarketError::InvalidRedemptionWindow);
+    }
+
+    let end_ts = clock::now()?
+        .checked_add(u64_to_i64(window_secs)?)
+        .ok_or(MarketError::MathOverflow)?;
+    let window = &mut ctx.accounts.redemption_window;
+    window.market = market.key();
+    window.redemption_price = redemption_price;
+    window.end_ts = end_ts;
+    window.bump = ctx.bumps.redemption_window;
+    market.redemption_pending = true;
+
+    msg!("Redemption open until {} at price {}", end_ts, redemption_price);
+    Ok(())
+}
@@ -1,20 +1,33 @@
 use anchor_lang::prelude::*;
 use crate::state::Market;
 use crate::errors::MarketError;
+use crate::clock;
+use crate::events::PauseEvent;
 
 #[derive(Accounts)]
 pub struct Pause<'info> {
-    #[account(mut, has_one = admin)]
+    #[account(
+        mut,
+        constraint = !market.terminated @ MarketError::MarketTerminated,
+        constraint = market.is_pause_authority(&admin.key()) @ MarketError::Unauthorized
+    )]
     pub market: Account<'info, Market>,
+    /// The market admin or its circuit breaker; keeps the `admin` name existing clients pass
     pub admin: Signer<'info>,
 }
 
 pub fn handler(ctx: Context<Pause>) -> Result<()> {
     let market = &mut ctx.accounts.market;
-    if ctx.accounts.admin.key() != market.admin {
-        return err!(MarketError::Unauthorized);
-    }
+    let now = clock::now()?;
     market.paused = !market.paused;
+    market.refresh_status();
+    market.pause_effective_ts = if market.paused { now } else { 0 };
     msg!("Paused state: {}", market.paused);
+
+    emit!(PauseEvent {
+        market: market.key(),
+        paused: market.paused,
+        effective_ts: market.pause_effective_ts,
+    });
     Ok(())
 }
@@ -0,0 +1,41 @@
+use anchor_lang::prelude::*;
+use crate::state::Market;
+use crate::errors::MarketError;
+use crate::clock;
+use crate::events::PauseEvent;
+use crate::math::u64_to_i64;
+
+#[derive(Accounts)]
+pub struct PauseWithGrace<'info> {
+    #[account(
+        mut,
+        constraint = !market.terminated @ MarketError::MarketTerminated,
+        constraint = market.is_pause_authority(&admin.key()) @ MarketError::Unauthorized
+    )]
+    pub market: Account<'info, Market>,
+    /// The market admin or its circuit breaker; keeps the `admin` name existing clients pass
+    pub admin: Signer<'info>,
+}
+
+/// Schedules a halt `grace_secs` from now; buy/sell keep working until then.
+/// A grace of 0 pauses immediately. Unpause with `pause`.
+pub fn handler(ctx: Context<PauseWithGrace>, grace_secs: u64) -> Result<()> {
+    let market = &mut ctx.accounts.market;
+    if market.paused {
+        return err!(MarketError::MarketPaused);
+    }
+
+    let now = clock::now()?;
+    let grace = u64_to_i64(grace_secs)?;
+    market.paused = true;
+    market.refresh_status();
+    market.pause_effective_ts = now.checked_add(grace).ok_or(MarketError::MathOverflow)?;
+    msg!("Pause scheduled at {}", market.pause_effective_ts);
+
+    emit!(PauseEvent {
+        market: market.key(),
+        paused: true,
+        effective_ts: market.pause_effective_ts,
+    });
+    Ok(())
+}
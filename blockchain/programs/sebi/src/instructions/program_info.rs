@@ -0,0 +1,23 @@
+use anchor_lang::prelude::*;
+use crate::constants::{PROGRAM_VERSION, SUPPORTED_FEATURES};
+
+// the cpi client needs an 'info lifetime, so the view carries one harmless account
+#[derive(Accounts)]
+pub struct GetProgramInfo<'info> {
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug)]
+pub struct ProgramInfo {
+    pub program_version: u32,
+    /// OR of the `FEATURE_*` bits in `constants`
+    pub supported_features_bitmask: u64,
+}
+
+/// Reports the deployed version and capabilities via return data.
+pub fn handler(_ctx: Context<GetProgramInfo>) -> Result<ProgramInfo> {
+    Ok(ProgramInfo {
+        program_version: PROGRAM_VERSION,
+        supported_features_bitmask: SUPPORTED_FEATURES,
+    })
+}
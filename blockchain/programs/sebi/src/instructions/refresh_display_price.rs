@@ -0,0 +1,18 @@
+use anchor_lang::prelude::*;
+use crate::state::Market;
+use crate::errors::MarketError;
+
+#[derive(Accounts)]
+pub struct RefreshDisplayPrice<'info> {
+    #[account(mut, constraint = !market.terminated @ MarketError::MarketTerminated)]
+    pub market: Account<'info, Market>,
+}
+
+/// Permissionless: recomputes Market.display_price from the stored price,
+/// scale and decimals, and returns it. Lets markets created before the field
+/// existed populate it without a price change.
+pub fn handler(ctx: Context<RefreshDisplayPrice>) -> Result<u64> {
+    let market = &mut ctx.accounts.market;
+    market.refresh_display_price()?;
+    Ok(market.display_price)
+}
@@ -0,0 +1,84 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token::{self, Token, TokenAccount, Transfer};
+use crate::state::{Market, RedemptionClaim, RedemptionWindow};
+use crate::errors::MarketError;
+use crate::clock;
+
+#[derive(Accounts)]
+pub struct RegisterRedemption<'info> {
+    #[account(
+        mut,
+        seeds = [b"market", market.bond_mint.as_ref()],
+        bump = market.bump
+    )]
+    pub market: Account<'info, Market>,
+
+    #[account(
+        mut,
+        seeds = [b"redemption", market.key().as_ref()],
+        bump = redemption_window.bump,
+        has_one = market
+    )]
+    pub redemption_window: Account<'info, RedemptionWindow>,
+
+    #[account(
+        init_if_needed,
+        payer = holder,
+        space = RedemptionClaim::LEN,
+        seeds = [b"redemption_claim", market.key().as_ref(), holder.key().as_ref()],
+        bump
+    )]
+    pub claim: Account<'info, RedemptionClaim>,
+
+    #[account(mut)]
+    pub holder: Signer<'info>,
+
+    #[account(mut, constraint = holder_bond.owner == holder.key())]
+    pub holder_bond: Account<'info, TokenAccount>,
+
+    #[account(mut, constraint = vault_bond.key() == market.vault_bond)]
+    pub vault_bond: Account<'info, TokenAccount>,
+
+    pub token_program: Program<'info, Token>,
+    pub system_program: Program<'info, System>,
+}
+
+/// Hands `amount` bonds to the vault against a claim paid at window close.
+/// A holder may register several times; amounts accumulate on one claim.
+pub fn handler(ctx: Context<RegisterRedemption>, amount: u64) -> Result<()> {
+    let window = &ctx.accounts.redemption_window;
+    if clock::now()? >= window.end_ts {
+        return err!(MarketError::RedemptionWindowClosed);
+    }
+    let owed = ctx.accounts.market.sell_proceeds_at(amount, window.redemption_price)?;
+
+    token::transfer(
+        CpiContext::new(
+            ctx.accounts.token_program.to_account_info(),
+            Transfer {
+                from: ctx.accounts.holder_bond.to_account_info(),
+                to: ctx.accounts.vault_bond.to_account_info(),
+                authority: ctx.accounts.holder.to_account_info(),
+            },
+        ),
+        amount,
+    )?;
+
+    let window = &mut ctx.accounts.redemption_window;
+    let claim = &mut ctx.accounts.claim;
+    if claim.holder == Pubkey::default() {
+        claim.market = window.market;
+        claim.holder = ctx.accounts.holder.key();
+        claim.bump = ctx.bumps.claim;
+        window.claim_count = window.claim_count.checked_add(1).ok_or(MarketError::MathOverflow)?;
+    }
+    claim.bonds = claim.bonds.checked_add(amount).ok_or(MarketError::MathOverflow)?;
+    claim.owed = claim.owed.checked_add(owed).ok_or(MarketError::MathOverflow)?;
+    window.total_bonds = window.total_bonds.checked_add(amount).ok_or(MarketError::MathOverflow)?;
+    window.total_owed = window.total_owed.checked_add(owed).ok_or(MarketError::MathOverflow)?;
+    let market = &mut ctx.accounts.market;
+    market.total_pending_redemption = market.total_pending_redemption.checked_add(owed).ok_or(MarketError::MathOverflow)?;
+
+    msg!("Registered {} bonds for redemption, {} owed", amount, owed);
+    Ok(())
+}
@@ -0,0 +1,27 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token::TokenAccount;
+use crate::state::Market;
+use crate::errors::MarketError;
+use crate::clock;
+
+#[derive(Accounts)]
+pub struct RequiredBacking<'info> {
+    pub market: Account<'info, Market>,
+
+    #[account(constraint = vault_usdc.key() == market.vault_usdc)]
+    pub vault_usdc: Account<'info, TokenAccount>,
+}
+
+/// Returns the USDC vault_usdc lacks to buy back every outstanding bond at
+/// the stored or auction price, plus any pending redemption claims, via
+/// return data; 0 when fully backed. withdraw prices bonds the same way and
+/// releases no USDC while this is above 0.
+pub fn handler(ctx: Context<RequiredBacking>) -> Result<u128> {
+    let mut market = (*ctx.accounts.market).clone();
+    market.sync_auction_price(clock::now()?)?;
+    let required = market
+        .backing_required()?
+        .checked_add(market.total_pending_redemption as u128)
+        .ok_or(MarketError::MathOverflow)?;
+    Ok(required.saturating_sub(ctx.accounts.vault_usdc.amount as u128))
+}
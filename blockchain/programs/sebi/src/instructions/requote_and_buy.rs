@@ -0,0 +1,36 @@
+use anchor_lang::prelude::*;
+use crate::state::PriceHistory;
+use crate::errors::MarketError;
+use crate::clock;
+use crate::instructions::buy::{self, *};
+use crate::instructions::update_price;
+
+#[derive(Accounts)]
+pub struct RequoteAndBuy<'info> {
+    /// buy's accounts; `trade.buyer` must be the market's price_updater
+    pub trade: Buy<'info>,
+
+    #[account(mut, seeds = [b"price_history", trade.market.key().as_ref()], bump = price_history.bump)]
+    pub price_history: Account<'info, PriceHistory>,
+}
+
+/// Sets the stored price and buys against it in one instruction, so no other
+/// trade can land between the two. The price passes update_price's checks
+/// and the buy passes every check buy applies, including the change
+/// limit and update cooldown. Without both of those a run of requotes could
+/// walk the price arbitrarily far in one transaction, so the market must set them.
+pub fn handler<'info>(ctx: Context<'_, '_, 'info, 'info, RequoteAndBuy<'info>>, new_price: u128, amount: u64) -> Result<()> {
+    let accounts = &mut ctx.accounts.trade;
+    let updater = accounts.market.price_updater;
+    if updater == Pubkey::default() || accounts.buyer.key() != updater {
+        return err!(MarketError::Unauthorized);
+    }
+    // nothing else limits how far the updater moves the price it trades at
+    if !accounts.market.has_price_guard() {
+        return err!(MarketError::PriceGuardRequired);
+    }
+    update_price::apply(&mut accounts.market, &mut ctx.accounts.price_history, new_price, clock::now()?)?;
+    msg!("Price updated to {}", new_price);
+
+    buy::handler(Context::new(ctx.program_id, accounts, ctx.remaining_accounts, ctx.bumps.trade), amount)
+}
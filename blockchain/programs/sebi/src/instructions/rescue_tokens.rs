@@ -0,0 +1,55 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token::{self, Token, TokenAccount, Transfer};
+use crate::state::Market;
+use crate::errors::MarketError;
+
+#[derive(Accounts)]
+pub struct RescueTokens<'info> {
+    #[account(has_one = admin, seeds = [b"market", market.bond_mint.as_ref()], bump = market.bump)]
+    pub market: Account<'info, Market>,
+    pub admin: Signer<'info>,
+
+    /// Stray token account held by the market PDA
+    #[account(mut, constraint = source.owner == market.key())]
+    pub source: Account<'info, TokenAccount>,
+
+    #[account(mut, constraint = destination.mint == source.mint)]
+    pub destination: Account<'info, TokenAccount>,
+
+    pub token_program: Program<'info, Token>,
+}
+
+pub fn handler(ctx: Context<RescueTokens>) -> Result<()> {
+    let market = &ctx.accounts.market;
+    if ctx.accounts.admin.key() != market.admin {
+        return err!(MarketError::Unauthorized);
+    }
+
+    // protocol liquidity only leaves through withdraw, insurance through sell or withdraw_insurance
+    let source_key = ctx.accounts.source.key();
+    if source_key == market.vault_bond
+        || source_key == market.vault_usdc
+        || source_key == market.insurance_vault
+    {
+        return err!(MarketError::CannotRescueVault);
+    }
+
+    let amount = ctx.accounts.source.amount;
+    let seeds = market.signer_seeds();
+    let signer = &[&seeds[..]];
+    token::transfer(
+        CpiContext::new_with_signer(
+            ctx.accounts.token_program.to_account_info(),
+            Transfer {
+                from: ctx.accounts.source.to_account_info(),
+                to: ctx.accounts.destination.to_account_info(),
+                authority: ctx.accounts.market.to_account_info(),
+            },
+            signer,
+        ),
+        amount,
+    )?;
+
+    msg!("Rescued {} tokens of mint {}", amount, ctx.accounts.source.mint);
+    Ok(())
+}
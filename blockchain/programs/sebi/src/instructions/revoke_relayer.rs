@@ -0,0 +1,27 @@
+use anchor_lang::prelude::*;
+use crate::state::{Market, RelayerAuthorization};
+
+#[derive(Accounts)]
+pub struct RevokeRelayer<'info> {
+    pub market: Account<'info, Market>,
+
+    #[account(
+        mut,
+        close = owner,
+        seeds = [b"relayer", market.key().as_ref(), owner.key().as_ref()],
+        bump = relayer_authorization.bump,
+        has_one = market,
+        has_one = owner
+    )]
+    pub relayer_authorization: Account<'info, RelayerAuthorization>,
+
+    #[account(mut)]
+    pub owner: Signer<'info>,
+}
+
+/// Closes the record, returning its rent. As with trading delegates, any SPL
+/// approval given to the relayer is left for the owner to revoke.
+pub fn handler(ctx: Context<RevokeRelayer>) -> Result<()> {
+    msg!("Relayer {} revoked", ctx.accounts.relayer_authorization.relayer);
+    Ok(())
+}
@@ -0,0 +1,27 @@
+use anchor_lang::prelude::*;
+use crate::state::{Market, TradingDelegate};
+
+#[derive(Accounts)]
+pub struct RevokeTradingDelegate<'info> {
+    pub market: Account<'info, Market>,
+
+    #[account(
+        mut,
+        close = owner,
+        seeds = [b"delegate", market.key().as_ref(), owner.key().as_ref()],
+        bump = trading_delegate.bump,
+        has_one = market,
+        has_one = owner
+    )]
+    pub trading_delegate: Account<'info, TradingDelegate>,
+
+    #[account(mut)]
+    pub owner: Signer<'info>,
+}
+
+/// Closes the record, returning its rent. Any SPL approval the owner gave
+/// the delegate is left for the owner to revoke with the token program.
+pub fn handler(ctx: Context<RevokeTradingDelegate>) -> Result<()> {
+    msg!("Trading delegate {} revoked", ctx.accounts.trading_delegate.delegate);
+    Ok(())
+}
@@ -0,0 +1,82 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token::{self, SetAuthority, Token, TokenAccount};
+use anchor_spl::token::spl_token::instruction::AuthorityType;
+use crate::state::Market;
+use crate::errors::MarketError;
+use crate::clock;
+use crate::events::VaultAuthorityRotatedEvent;
+
+#[derive(Accounts)]
+pub struct RotateVaultAuthority<'info> {
+    #[account(
+        mut,
+        has_one = admin,
+        seeds = [b"market", market.bond_mint.as_ref()],
+        bump = market.bump,
+        constraint = !market.terminated @ MarketError::MarketTerminated
+    )]
+    pub market: Account<'info, Market>,
+    pub admin: Signer<'info>,
+
+    #[account(mut, constraint = vault_bond.key() == market.vault_bond)]
+    pub vault_bond: Account<'info, TokenAccount>,
+
+    #[account(mut, constraint = vault_usdc.key() == market.vault_usdc)]
+    pub vault_usdc: Account<'info, TokenAccount>,
+
+    /// Required once the market has an insurance vault, which rotates with the others
+    #[account(mut, constraint = insurance_vault.key() == market.insurance_vault)]
+    pub insurance_vault: Option<Account<'info, TokenAccount>>,
+
+    pub token_program: Program<'info, Token>,
+}
+
+/// Recovery path: hands ownership of both vaults, and the insurance vault if
+/// there is one, from the market PDA to `new_authority` (e.g. a multisig).
+/// The market can no longer sign for the vaults afterwards, so buy, sell,
+/// withdraw and withdraw_insurance stop working; this runs once, only while
+/// trading is halted. insurance_balance is left as credited.
+pub fn handler(ctx: Context<RotateVaultAuthority>, new_authority: Pubkey) -> Result<()> {
+    let market = &ctx.accounts.market;
+    if ctx.accounts.admin.key() != market.admin {
+        return err!(MarketError::Unauthorized);
+    }
+    if !market.is_halted(clock::now()?) {
+        return err!(MarketError::MarketNotHalted);
+    }
+    let market_key = market.key();
+    if market.vault_authority != market_key || new_authority == market_key || new_authority == Pubkey::default() {
+        return err!(MarketError::InvalidVaultAuthority);
+    }
+    // sells are the insurance vault's only outflow, so it can't be left with the PDA
+    let insurance = ctx.accounts.insurance_vault.as_ref();
+    if market.insurance_vault != Pubkey::default() && insurance.is_none() {
+        return err!(MarketError::InsuranceVaultRequired);
+    }
+
+    let seeds = market.signer_seeds();
+    let signer = &[&seeds[..]];
+    for vault in [Some(&ctx.accounts.vault_bond), Some(&ctx.accounts.vault_usdc), insurance].into_iter().flatten() {
+        token::set_authority(
+            CpiContext::new_with_signer(
+                ctx.accounts.token_program.to_account_info(),
+                SetAuthority {
+                    current_authority: ctx.accounts.market.to_account_info(),
+                    account_or_mint: vault.to_account_info(),
+                },
+                signer,
+            ),
+            AuthorityType::AccountOwner,
+            Some(new_authority),
+        )?;
+    }
+
+    ctx.accounts.market.vault_authority = new_authority;
+    emit!(VaultAuthorityRotatedEvent {
+        market: market_key,
+        old_authority: market_key,
+        new_authority,
+    });
+    msg!("Vault authority rotated to {}", new_authority);
+    Ok(())
+}
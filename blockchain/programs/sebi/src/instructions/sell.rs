@@ -1,20 +1,34 @@
 use anchor_lang::prelude::*;
+use anchor_lang::solana_program::sysvar::instructions as sysvar_instructions;
 use anchor_spl::token::{self, Token, TokenAccount, Transfer};
-use crate::state::Market;
+use crate::state::{trader_for, DailyStats, InvestorPosition, Market, ProtocolStats, RelayerAuthorization, TradingDelegate};
 use crate::errors::MarketError;
+use crate::clock;
+#[cfg(feature = "invariant-checks")]
+use crate::invariants;
+use crate::attestation;
+use crate::compliance;
+use crate::attribution;
+use crate::notify::{self, TradeNotification};
+use crate::events::{InsuranceTappedEvent, TradeEvent, TradeSide};
 
 #[derive(Accounts)]
 pub struct Sell<'info> {
-    #[account(mut, seeds = [b"market", market.bond_mint.as_ref()], bump = market.bump)]
+    #[account(
+        mut,
+        seeds = [b"market", market.bond_mint.as_ref()],
+        bump = market.bump,
+        constraint = !market.terminated @ MarketError::MarketTerminated
+    )]
     pub market: Account<'info, Market>,
 
     #[account(mut)]
     pub seller: Signer<'info>,
 
-    #[account(mut, constraint = seller_bond.owner == seller.key())]
+    #[account(mut, constraint = seller_bond.owner == trader_for(&trading_delegate, &relayer_authorization, seller.key()))]
     pub seller_bond: Account<'info, TokenAccount>,
 
-    #[account(mut, constraint = seller_usdc.owner == seller.key())]
+    #[account(mut, constraint = seller_usdc.owner == trader_for(&trading_delegate, &relayer_authorization, seller.key()))]
     pub seller_usdc: Account<'info, TokenAccount>,
 
     #[account(mut, constraint = vault_bond.key() == market.vault_bond)]
@@ -23,20 +37,134 @@ pub struct Sell<'info> {
     #[account(mut, constraint = vault_usdc.key() == market.vault_usdc)]
     pub vault_usdc: Account<'info, TokenAccount>,
 
+    /// Backstops a USDC shortfall; only needed when vault_usdc can't cover the sale
+    #[account(mut, constraint = insurance_vault.key() == market.insurance_vault)]
+    pub insurance_vault: Option<Account<'info, TokenAccount>>,
+
+    /// CHECK: instructions sysvar, pinned by address. When present, the
+    /// preceding Ed25519 instruction must carry a PriceAttestation to trade at.
+    #[account(address = sysvar_instructions::ID)]
+    pub instructions: Option<UncheckedAccount<'info>>,
+
+    /// Program-wide totals; optional so trading works before it is created
+    #[account(mut, seeds = [b"protocol_stats"], bump = protocol_stats.bump)]
+    pub protocol_stats: Option<Account<'info, ProtocolStats>>,
+
+    /// Today's candle; optional like protocol_stats
+    #[account(mut, seeds = [b"daily_stats", market.key().as_ref()], bump = daily_stats.bump)]
+    pub daily_stats: Option<Account<'info, DailyStats>>,
+
+    /// CHECK: compared against market.compliance_program; required only
+    /// when the market has one
+    pub compliance_program: Option<UncheckedAccount<'info>>,
+
+    /// Present when `seller` trades for trading_delegate.owner, whose token
+    /// accounts must have approved `seller` through SPL `approve`
+    #[account(
+        seeds = [b"delegate", market.key().as_ref(), trading_delegate.owner.as_ref()],
+        bump = trading_delegate.bump,
+        constraint = trading_delegate.delegate == seller.key() @ MarketError::NotDelegated
+    )]
+    pub trading_delegate: Option<Account<'info, TradingDelegate>>,
+
+    /// Present when `seller` relays for relayer_authorization.owner; the trade's
+    /// quote amount is debited from its allowance
+    #[account(
+        mut,
+        seeds = [b"relayer", market.key().as_ref(), relayer_authorization.owner.as_ref()],
+        bump = relayer_authorization.bump,
+        constraint = relayer_authorization.relayer == seller.key() @ MarketError::NotDelegated
+    )]
+    pub relayer_authorization: Option<Account<'info, RelayerAuthorization>>,
+
+    /// CHECK: compared against market.notify_program; called once the trade settles
+    pub notify_program: Option<UncheckedAccount<'info>>,
+
     pub token_program: Program<'info, Token>,
+
+    /// CHECK: instructions sysvar, pinned by address; read only to attribute
+    /// a CPI trade to its calling program. Direct callers leave it out; CPI
+    /// callers pass it here, or pass `instructions` if also attesting a price.
+    #[account(address = sysvar_instructions::ID)]
+    pub instructions_sysvar: Option<UncheckedAccount<'info>>,
+
+    /// The trader's position, stamped with this trade; required while the
+    /// market has a trade cooldown
+    #[account(
+        mut,
+        seeds = [b"position", market.key().as_ref(), investor_position.owner.as_ref()],
+        bump = investor_position.bump,
+        constraint = investor_position.owner == trader_for(&trading_delegate, &relayer_authorization, seller.key()) @ MarketError::Unauthorized
+    )]
+    pub investor_position: Option<Account<'info, InvestorPosition>>,
+}
+
+impl<'info> Sell<'info> {
+    /// Either copy of the instructions sysvar the caller passed
+    pub fn attribution_sysvar(&self) -> Option<&AccountInfo<'info>> {
+        self.instructions_sysvar.as_ref().or(self.instructions.as_ref()).map(AsRef::as_ref)
+    }
 }
 
 pub fn handler(ctx: Context<Sell>, amount: u64) -> Result<()> {
+    let now = clock::now()?;
+    ctx.accounts.market.sync_auction_price(now)?;
     let market = &ctx.accounts.market;
-    if market.paused {
-        return err!(MarketError::MarketPaused);
+    market.check_sell(amount, now)?;
+    let trader = trader_for(&ctx.accounts.trading_delegate, &ctx.accounts.relayer_authorization, ctx.accounts.seller.key());
+
+    // total_price = amount * price_per_token / 10^price_scale, rounded down
+    let price_u128 = match &ctx.accounts.instructions {
+        Some(instructions) => attestation::verified_price(instructions, market, now)?,
+        None => market.price_per_token,
+    };
+    if market.exceeds_slippage(price_u128) {
+        return err!(MarketError::SlippageExceeded);
+    }
+    let total_price_u64 = market.sell_proceeds_at(amount, price_u128)?;
+    if market.exceeds_max_notional(total_price_u64) {
+        return err!(MarketError::NotionalTooLarge);
     }
 
-    let price_u128 = market.price_per_token;
-    let amount_u128 = amount as u128;
-    let total_price_u128 = price_u128.checked_mul(amount_u128).ok_or(MarketError::MathOverflow)?;
-    let total_price_u64 = total_price_u128.try_into().map_err(|_| MarketError::MathOverflow)?;
+    // vault_usdc must cover the sale, drawing any shortfall from the insurance fund
+    let vault_balance = ctx.accounts.vault_usdc.amount;
+    if !market.is_funded(vault_balance) {
+        return err!(MarketError::MarketNotFunded);
+    }
+    let shortfall = total_price_u64.saturating_sub(vault_balance);
+    if shortfall > 0 {
+        match &ctx.accounts.insurance_vault {
+            Some(insurance) if market.insurance_balance >= shortfall && insurance.amount >= shortfall => {}
+            _ => return err!(MarketError::InsufficientVaultFunds),
+        }
+    }
+
+    if ctx.accounts.trading_delegate.is_some() && ctx.accounts.relayer_authorization.is_some() {
+        return err!(MarketError::NotDelegated);
+    }
+    // the hook screens the signer, so it can't vouch for a delegated owner
+    let acting_for_owner = ctx.accounts.trading_delegate.is_some() || ctx.accounts.relayer_authorization.is_some();
+    if acting_for_owner && market.compliance_program != Pubkey::default() {
+        return err!(MarketError::ComplianceRejected);
+    }
+    if let Some(auth) = &mut ctx.accounts.relayer_authorization {
+        auth.spend(total_price_u64, now)?;
+    }
+    match &mut ctx.accounts.investor_position {
+        Some(position) => position.record_trade(market, TradeSide::Sell, now)?,
+        None if market.requires_position() => return err!(MarketError::PositionRequired),
+        None => {}
+    }
+    compliance::check_trade(
+        ctx.accounts.compliance_program.as_ref(),
+        market,
+        &ctx.accounts.seller.to_account_info(),
+        TradeSide::Sell,
+        amount,
+        price_u128,
+    )?;
 
+    // every precondition has passed; no tokens move before this point
     // transfer bond tokens from seller -> vault (seller signs)
     let cpi_accounts_bond = Transfer {
         from: ctx.accounts.seller_bond.to_account_info(),
@@ -48,32 +176,87 @@ pub fn handler(ctx: Context<Sell>, amount: u64) -> Result<()> {
         amount,
     )?;
 
-    // ensure vault_usdc has enough balance (optional check)
-    let vault_balance = ctx.accounts.vault_usdc.amount;
-    if vault_balance < total_price_u64 {
-        return err!(MarketError::InsufficientVaultFunds);
-    }
-
     // transfer USDC from vault -> seller, signed by PDA
-    let seeds = &[b"market", market.bond_mint.as_ref(), &[market.bump]];
+    let seeds = market.signer_seeds();
     let signer = &[&seeds[..]];
-    let cpi_accounts_usdc = Transfer {
-        from: ctx.accounts.vault_usdc.to_account_info(),
-        to: ctx.accounts.seller_usdc.to_account_info(),
-        authority: ctx.accounts.market.to_account_info(),
-    };
-    token::transfer(
-        CpiContext::new_with_signer(ctx.accounts.token_program.to_account_info(), cpi_accounts_usdc, signer),
-        total_price_u64,
-    )?;
+    let from_vault = total_price_u64 - shortfall;
+    if from_vault > 0 {
+        let cpi_accounts_usdc = Transfer {
+            from: ctx.accounts.vault_usdc.to_account_info(),
+            to: ctx.accounts.seller_usdc.to_account_info(),
+            authority: ctx.accounts.market.to_account_info(),
+        };
+        token::transfer(
+            CpiContext::new_with_signer(ctx.accounts.token_program.to_account_info(), cpi_accounts_usdc, signer),
+            from_vault,
+        )?;
+    }
 
-    emit!(TradeEvent {
+    if shortfall > 0 {
+        let insurance = ctx.accounts.insurance_vault.as_ref().ok_or(MarketError::InsufficientVaultFunds)?;
+        let cpi_accounts_insurance = Transfer {
+            from: insurance.to_account_info(),
+            to: ctx.accounts.seller_usdc.to_account_info(),
+            authority: ctx.accounts.market.to_account_info(),
+        };
+        token::transfer(
+            CpiContext::new_with_signer(ctx.accounts.token_program.to_account_info(), cpi_accounts_insurance, signer),
+            shortfall,
+        )?;
+
+        let market = &mut ctx.accounts.market;
+        market.insurance_balance = market.insurance_balance.checked_sub(shortfall).ok_or(MarketError::MathOverflow)?;
+        emit!(InsuranceTappedEvent {
+            market: market.key(),
+            amount: shortfall,
+            remaining: market.insurance_balance,
+        });
+    }
+
+    ctx.accounts.market.record_sell(amount, total_price_u64)?;
+    ctx.accounts.market.last_trade_price = price_u128;
+    ctx.accounts.market.funded = true;
+    if let Some(stats) = &mut ctx.accounts.protocol_stats {
+        stats.record_trade(total_price_u64)?;
+    }
+    if let Some(daily) = &mut ctx.accounts.daily_stats {
+        if let Some(candle) = daily.record(price_u128, total_price_u64, now)? {
+            emit!(candle);
+        }
+    }
+
+    TradeEvent {
         market: ctx.accounts.market.key(),
-        trader: ctx.accounts.seller.key(),
+        trader,
         side: TradeSide::Sell,
         amount,
         price: price_u128,
-    });
+        reference_price: ctx.accounts.market.price_per_token,
+        via_program: attribution::via_program(ctx.accounts.attribution_sysvar())?,
+    }
+    .emit_at(ctx.accounts.market.event_verbosity);
+
+    notify::notify_trade(
+        ctx.accounts.notify_program.as_ref(),
+        &ctx.accounts.market,
+        &TradeNotification {
+            market: ctx.accounts.market.key(),
+            trader,
+            side: TradeSide::Sell,
+            amount,
+            price: price_u128,
+            quote_amount: total_price_u64,
+        },
+    )?;
+
+    #[cfg(feature = "invariant-checks")]
+    {
+        let (usdc_before, bond_before) = (ctx.accounts.vault_usdc.amount, ctx.accounts.vault_bond.amount);
+        ctx.accounts.vault_usdc.reload()?;
+        ctx.accounts.vault_bond.reload()?;
+        invariants::check_balance_delta(usdc_before, ctx.accounts.vault_usdc.amount, -(from_vault as i128))?;
+        invariants::check_balance_delta(bond_before, ctx.accounts.vault_bond.amount, amount as i128)?;
+    }
 
     Ok(())
 }
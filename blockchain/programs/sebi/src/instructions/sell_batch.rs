@@ -0,0 +1,160 @@
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::sysvar::instructions as sysvar_instructions;
+use anchor_spl::token::{self, Token, TokenAccount, Transfer};
+use crate::state::{Market, ProtocolStats};
+use crate::errors::MarketError;
+use crate::clock;
+use crate::attribution;
+use crate::events::{TradeEvent, TradeSide};
+
+/// remaining_accounts per market: [market, seller_bond, seller_usdc, vault_bond, vault_usdc]
+pub const SELL_BATCH_GROUP_LEN: usize = 5;
+
+/// Most markets one sell_batch may cover; larger batches run out of compute
+pub const MAX_SELL_BATCH: usize = 8;
+
+#[derive(Accounts)]
+pub struct SellBatch<'info> {
+    #[account(mut)]
+    pub seller: Signer<'info>,
+
+    /// Program-wide totals; optional so trading works before it is created
+    #[account(mut, seeds = [b"protocol_stats"], bump = protocol_stats.bump)]
+    pub protocol_stats: Option<Account<'info, ProtocolStats>>,
+
+    pub token_program: Program<'info, Token>,
+
+    /// CHECK: instructions sysvar, pinned by address; attributes the sells
+    /// to a calling program. Only CPI callers need to pass it.
+    #[account(address = sysvar_instructions::ID)]
+    pub instructions_sysvar: Option<UncheckedAccount<'info>>,
+}
+
+/// Sells amounts[i] into the i-th market group for at least min_proceeds[i]
+/// and returns total proceeds. Any failed group reverts the whole batch.
+/// There is no insurance fallback, so each vault_usdc must cover its sale.
+pub fn handler<'info>(
+    ctx: Context<'_, '_, 'info, 'info, SellBatch<'info>>,
+    amounts: Vec<u64>,
+    min_proceeds: Vec<u64>,
+) -> Result<u64> {
+    if amounts.len() > MAX_SELL_BATCH {
+        return err!(MarketError::BatchTooLarge);
+    }
+    let groups = ctx.remaining_accounts;
+    if amounts.is_empty()
+        || min_proceeds.len() != amounts.len()
+        || groups.len() != amounts.len() * SELL_BATCH_GROUP_LEN
+    {
+        return err!(MarketError::InvalidBatch);
+    }
+
+    let now = clock::now()?;
+    let seller = &ctx.accounts.seller;
+    let via_program = attribution::via_program(ctx.accounts.instructions_sysvar.as_ref().map(AsRef::as_ref))?;
+    let token_program = ctx.accounts.token_program.to_account_info();
+    let mut total: u64 = 0;
+
+    // each group is loaded, settled and written back before the next one is
+    // read, so a market listed twice sees the state left by its earlier fill
+    for ((group, &amount), &min) in groups.chunks(SELL_BATCH_GROUP_LEN).zip(&amounts).zip(&min_proceeds) {
+        if !group[0].is_writable {
+            return err!(MarketError::InvalidBatch);
+        }
+        let mut market: Account<'info, Market> = Account::try_from(&group[0])?;
+        if market.terminated {
+            return err!(MarketError::MarketTerminated);
+        }
+        // groups carry no compliance callback, so hooked markets sell one at a time
+        if market.compliance_program != Pubkey::default() {
+            return err!(MarketError::ComplianceRejected);
+        }
+        // nor an investor position, so cooldown and wash-checked markets do too
+        if market.requires_position() {
+            return err!(MarketError::PositionRequired);
+        }
+        // nor a notify callback; optional notifications are skipped
+        if market.notify_program != Pubkey::default() && market.notify_required {
+            return err!(MarketError::NotifyFailed);
+        }
+        let seller_bond: Account<'info, TokenAccount> = Account::try_from(&group[1])?;
+        let seller_usdc: Account<'info, TokenAccount> = Account::try_from(&group[2])?;
+        let vault_bond: Account<'info, TokenAccount> = Account::try_from(&group[3])?;
+        let vault_usdc: Account<'info, TokenAccount> = Account::try_from(&group[4])?;
+        if seller_bond.owner != seller.key()
+            || seller_usdc.owner != seller.key()
+            || vault_bond.key() != market.vault_bond
+            || vault_usdc.key() != market.vault_usdc
+        {
+            return err!(MarketError::InvalidBatch);
+        }
+
+        market.sync_auction_price(now)?;
+        market.check_sell(amount, now)?;
+        if market.exceeds_slippage(market.price_per_token) {
+            return err!(MarketError::SlippageExceeded);
+        }
+        let proceeds = market.sell_proceeds(amount)?;
+        if market.exceeds_max_notional(proceeds) {
+            return err!(MarketError::NotionalTooLarge);
+        }
+        if proceeds < min {
+            return err!(MarketError::SlippageExceeded);
+        }
+        if !market.is_funded(vault_usdc.amount) {
+            return err!(MarketError::MarketNotFunded);
+        }
+        if vault_usdc.amount < proceeds {
+            return err!(MarketError::InsufficientVaultFunds);
+        }
+
+        token::transfer(
+            CpiContext::new(
+                token_program.clone(),
+                Transfer {
+                    from: seller_bond.to_account_info(),
+                    to: vault_bond.to_account_info(),
+                    authority: seller.to_account_info(),
+                },
+            ),
+            amount,
+        )?;
+
+        let seeds = market.signer_seeds();
+        let signer = &[&seeds[..]];
+        token::transfer(
+            CpiContext::new_with_signer(
+                token_program.clone(),
+                Transfer {
+                    from: vault_usdc.to_account_info(),
+                    to: seller_usdc.to_account_info(),
+                    authority: market.to_account_info(),
+                },
+                signer,
+            ),
+            proceeds,
+        )?;
+
+        market.record_sell(amount, proceeds)?;
+        market.last_trade_price = market.price_per_token;
+        market.funded = true;
+        if let Some(stats) = &mut ctx.accounts.protocol_stats {
+            stats.record_trade(proceeds)?;
+        }
+        TradeEvent {
+            market: market.key(),
+            trader: seller.key(),
+            side: TradeSide::Sell,
+            amount,
+            price: market.price_per_token,
+            reference_price: market.price_per_token,
+            via_program,
+        }
+        .emit_at(market.event_verbosity);
+        market.exit(&crate::ID)?;
+
+        total = total.checked_add(proceeds).ok_or(MarketError::MathOverflow)?;
+    }
+
+    Ok(total)
+}
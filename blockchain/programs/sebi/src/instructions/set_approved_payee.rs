@@ -0,0 +1,38 @@
+use anchor_lang::prelude::*;
+use crate::state::Market;
+use crate::errors::MarketError;
+use crate::clock;
+use crate::constants::PAYEE_TIMELOCK_SECS;
+use crate::events::ApprovedPayeeEvent;
+
+#[derive(Accounts)]
+pub struct SetApprovedPayee<'info> {
+    #[account(mut, has_one = admin, constraint = !market.terminated @ MarketError::MarketTerminated)]
+    pub market: Account<'info, Market>,
+    pub admin: Signer<'info>,
+}
+
+/// Lets withdraw pay token accounts owned by `payee` after PAYEE_TIMELOCK_SECS.
+/// Replacing the payee restarts the delay; the default pubkey revokes at once.
+pub fn handler(ctx: Context<SetApprovedPayee>, payee: Pubkey) -> Result<()> {
+    let market = &mut ctx.accounts.market;
+    if ctx.accounts.admin.key() != market.admin {
+        return err!(MarketError::Unauthorized);
+    }
+
+    let now = clock::now()?;
+    market.approved_payee = payee;
+    market.payee_effective_ts = if payee == Pubkey::default() {
+        0
+    } else {
+        now.checked_add(PAYEE_TIMELOCK_SECS).ok_or(MarketError::MathOverflow)?
+    };
+    msg!("Approved payee {} from {}", payee, market.payee_effective_ts);
+
+    emit!(ApprovedPayeeEvent {
+        market: market.key(),
+        payee,
+        effective_ts: market.payee_effective_ts,
+    });
+    Ok(())
+}
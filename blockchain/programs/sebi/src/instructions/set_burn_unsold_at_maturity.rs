@@ -0,0 +1,20 @@
+use anchor_lang::prelude::*;
+use crate::state::Market;
+use crate::errors::MarketError;
+
+#[derive(Accounts)]
+pub struct SetBurnUnsoldAtMaturity<'info> {
+    #[account(mut, has_one = admin, constraint = !market.terminated @ MarketError::MarketTerminated)]
+    pub market: Account<'info, Market>,
+    pub admin: Signer<'info>,
+}
+
+pub fn handler(ctx: Context<SetBurnUnsoldAtMaturity>, burn_unsold_at_maturity: bool) -> Result<()> {
+    let market = &mut ctx.accounts.market;
+    if ctx.accounts.admin.key() != market.admin {
+        return err!(MarketError::Unauthorized);
+    }
+    market.burn_unsold_at_maturity = burn_unsold_at_maturity;
+    msg!("Burn unsold at maturity set to {}", burn_unsold_at_maturity);
+    Ok(())
+}
@@ -0,0 +1,21 @@
+use anchor_lang::prelude::*;
+use crate::state::Market;
+use crate::errors::MarketError;
+
+#[derive(Accounts)]
+pub struct SetCircuitBreakerAuthority<'info> {
+    #[account(mut, has_one = admin, constraint = !market.terminated @ MarketError::MarketTerminated)]
+    pub market: Account<'info, Market>,
+    pub admin: Signer<'info>,
+}
+
+/// The circuit breaker can only pause and unpause. Pubkey::default() removes it.
+pub fn handler(ctx: Context<SetCircuitBreakerAuthority>, authority: Pubkey) -> Result<()> {
+    let market = &mut ctx.accounts.market;
+    if ctx.accounts.admin.key() != market.admin {
+        return err!(MarketError::Unauthorized);
+    }
+    market.circuit_breaker_authority = authority;
+    msg!("Circuit breaker authority set to {}", authority);
+    Ok(())
+}
@@ -0,0 +1,22 @@
+use anchor_lang::prelude::*;
+use crate::state::Market;
+use crate::errors::MarketError;
+
+#[derive(Accounts)]
+pub struct SetComplianceProgram<'info> {
+    #[account(mut, has_one = admin, constraint = !market.terminated @ MarketError::MarketTerminated)]
+    pub market: Account<'info, Market>,
+    pub admin: Signer<'info>,
+}
+
+/// Pubkey::default() removes the hook. See `compliance::ComplianceCheck`
+/// for the interface the program must implement.
+pub fn handler(ctx: Context<SetComplianceProgram>, compliance_program: Pubkey) -> Result<()> {
+    let market = &mut ctx.accounts.market;
+    if ctx.accounts.admin.key() != market.admin {
+        return err!(MarketError::Unauthorized);
+    }
+    market.compliance_program = compliance_program;
+    msg!("Compliance program set to {}", compliance_program);
+    Ok(())
+}
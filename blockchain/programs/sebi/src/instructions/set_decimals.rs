@@ -0,0 +1,45 @@
+use anchor_lang::prelude::*;
+use crate::state::Market;
+use crate::errors::MarketError;
+use crate::events::{ConfigChangedEvent, ConfigField};
+
+#[derive(Accounts)]
+pub struct SetDecimals<'info> {
+    #[account(mut, has_one = admin, constraint = !market.terminated @ MarketError::MarketTerminated)]
+    pub market: Account<'info, Market>,
+    pub admin: Signer<'info>,
+}
+
+/// One-shot backfill for markets created before decimals were recorded.
+/// Changing decimals under a live market would silently reprice it, so this
+/// fails once they are set, including on every market initialized since.
+pub fn handler(ctx: Context<SetDecimals>, bond_decimals: u8, usdc_decimals: u8) -> Result<()> {
+    let market = &mut ctx.accounts.market;
+    if ctx.accounts.admin.key() != market.admin {
+        return err!(MarketError::Unauthorized);
+    }
+    if market.decimals_set {
+        return err!(MarketError::DecimalsAlreadySet);
+    }
+
+    let market_key = market.key();
+    emit!(ConfigChangedEvent {
+        market: market_key,
+        field: ConfigField::BondDecimals,
+        old_value: market.bond_decimals as u64,
+        new_value: bond_decimals as u64,
+    });
+    emit!(ConfigChangedEvent {
+        market: market_key,
+        field: ConfigField::UsdcDecimals,
+        old_value: market.usdc_decimals as u64,
+        new_value: usdc_decimals as u64,
+    });
+
+    market.bond_decimals = bond_decimals;
+    market.usdc_decimals = usdc_decimals;
+    market.decimals_set = true;
+    market.refresh_display_price()?;
+    msg!("Decimals set to bond {} / usdc {}", bond_decimals, usdc_decimals);
+    Ok(())
+}
@@ -0,0 +1,20 @@
+use anchor_lang::prelude::*;
+use crate::state::Market;
+use crate::errors::MarketError;
+
+#[derive(Accounts)]
+pub struct SetEmergencyExitEnabled<'info> {
+    #[account(mut, has_one = admin, constraint = !market.terminated @ MarketError::MarketTerminated)]
+    pub market: Account<'info, Market>,
+    pub admin: Signer<'info>,
+}
+
+pub fn handler(ctx: Context<SetEmergencyExitEnabled>, emergency_exit_enabled: bool) -> Result<()> {
+    let market = &mut ctx.accounts.market;
+    if ctx.accounts.admin.key() != market.admin {
+        return err!(MarketError::Unauthorized);
+    }
+    market.emergency_exit_enabled = emergency_exit_enabled;
+    msg!("Emergency exit enabled set to {}", emergency_exit_enabled);
+    Ok(())
+}
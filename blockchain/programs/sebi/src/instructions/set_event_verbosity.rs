@@ -0,0 +1,20 @@
+use anchor_lang::prelude::*;
+use crate::state::{EventVerbosity, Market};
+use crate::errors::MarketError;
+
+#[derive(Accounts)]
+pub struct SetEventVerbosity<'info> {
+    #[account(mut, has_one = admin, constraint = !market.terminated @ MarketError::MarketTerminated)]
+    pub market: Account<'info, Market>,
+    pub admin: Signer<'info>,
+}
+
+pub fn handler(ctx: Context<SetEventVerbosity>, event_verbosity: EventVerbosity) -> Result<()> {
+    let market = &mut ctx.accounts.market;
+    if ctx.accounts.admin.key() != market.admin {
+        return err!(MarketError::Unauthorized);
+    }
+    market.event_verbosity = event_verbosity;
+    msg!("Event verbosity: {:?}", event_verbosity);
+    Ok(())
+}
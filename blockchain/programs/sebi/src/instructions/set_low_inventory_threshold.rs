@@ -0,0 +1,20 @@
+use anchor_lang::prelude::*;
+use crate::state::Market;
+use crate::errors::MarketError;
+
+#[derive(Accounts)]
+pub struct SetLowInventoryThreshold<'info> {
+    #[account(mut, has_one = admin, constraint = !market.terminated @ MarketError::MarketTerminated)]
+    pub market: Account<'info, Market>,
+    pub admin: Signer<'info>,
+}
+
+pub fn handler(ctx: Context<SetLowInventoryThreshold>, threshold: u64) -> Result<()> {
+    let market = &mut ctx.accounts.market;
+    if ctx.accounts.admin.key() != market.admin {
+        return err!(MarketError::Unauthorized);
+    }
+    market.low_inventory_threshold = threshold;
+    msg!("Low inventory threshold set to {}", threshold);
+    Ok(())
+}
@@ -0,0 +1,27 @@
+use anchor_lang::prelude::*;
+use crate::state::Market;
+use crate::errors::MarketError;
+
+#[derive(Accounts)]
+pub struct SetMaxCumulativeWithdraw<'info> {
+    #[account(mut, has_one = admin, constraint = !market.terminated @ MarketError::MarketTerminated)]
+    pub market: Account<'info, Market>,
+    pub admin: Signer<'info>,
+}
+
+/// Caps lifetime USDC withdrawals. Once set the cap can only be lowered,
+/// so holders can rely on it.
+pub fn handler(ctx: Context<SetMaxCumulativeWithdraw>, max_cumulative_withdraw: u64) -> Result<()> {
+    let market = &mut ctx.accounts.market;
+    if ctx.accounts.admin.key() != market.admin {
+        return err!(MarketError::Unauthorized);
+    }
+    if market.max_cumulative_withdraw > 0
+        && (max_cumulative_withdraw == 0 || max_cumulative_withdraw > market.max_cumulative_withdraw)
+    {
+        return err!(MarketError::InvalidWithdrawCap);
+    }
+    market.max_cumulative_withdraw = max_cumulative_withdraw;
+    msg!("Cumulative withdraw cap set to {}", max_cumulative_withdraw);
+    Ok(())
+}
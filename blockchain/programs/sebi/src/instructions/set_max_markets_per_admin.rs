@@ -0,0 +1,17 @@
+use anchor_lang::prelude::*;
+use crate::state::ProgramConfig;
+
+#[derive(Accounts)]
+pub struct SetMaxMarketsPerAdmin<'info> {
+    #[account(mut, seeds = [b"config"], bump = config.bump, has_one = authority)]
+    pub config: Account<'info, ProgramConfig>,
+    pub authority: Signer<'info>,
+}
+
+/// 0 lifts the cap. Lowering it below an admin's existing count only blocks
+/// that admin's further markets.
+pub fn handler(ctx: Context<SetMaxMarketsPerAdmin>, max_markets_per_admin: u64) -> Result<()> {
+    ctx.accounts.config.max_markets_per_admin = max_markets_per_admin;
+    msg!("Max markets per admin set to {}", max_markets_per_admin);
+    Ok(())
+}
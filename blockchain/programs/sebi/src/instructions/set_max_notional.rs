@@ -0,0 +1,21 @@
+use anchor_lang::prelude::*;
+use crate::state::Market;
+use crate::errors::MarketError;
+
+#[derive(Accounts)]
+pub struct SetMaxNotional<'info> {
+    #[account(mut, has_one = admin, constraint = !market.terminated @ MarketError::MarketTerminated)]
+    pub market: Account<'info, Market>,
+    pub admin: Signer<'info>,
+}
+
+/// Caps the quote amount of each buy or sell; 0 disables the cap.
+pub fn handler(ctx: Context<SetMaxNotional>, max_notional: u64) -> Result<()> {
+    let market = &mut ctx.accounts.market;
+    if ctx.accounts.admin.key() != market.admin {
+        return err!(MarketError::Unauthorized);
+    }
+    market.max_notional = max_notional;
+    msg!("Max notional set to {}", max_notional);
+    Ok(())
+}
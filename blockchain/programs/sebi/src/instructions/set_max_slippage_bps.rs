@@ -0,0 +1,21 @@
+use anchor_lang::prelude::*;
+use crate::state::Market;
+use crate::errors::MarketError;
+
+#[derive(Accounts)]
+pub struct SetMaxSlippageBps<'info> {
+    #[account(mut, has_one = admin, constraint = !market.terminated @ MarketError::MarketTerminated)]
+    pub market: Account<'info, Market>,
+    pub admin: Signer<'info>,
+}
+
+/// Bounds each trade against last_trade_price; 0 disables the guard.
+pub fn handler(ctx: Context<SetMaxSlippageBps>, max_slippage_bps: u16) -> Result<()> {
+    let market = &mut ctx.accounts.market;
+    if ctx.accounts.admin.key() != market.admin {
+        return err!(MarketError::Unauthorized);
+    }
+    market.max_slippage_bps = max_slippage_bps;
+    msg!("Max slippage set to {} bps", max_slippage_bps);
+    Ok(())
+}
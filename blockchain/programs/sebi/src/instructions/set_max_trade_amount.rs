@@ -0,0 +1,20 @@
+use anchor_lang::prelude::*;
+use crate::state::Market;
+use crate::errors::MarketError;
+
+#[derive(Accounts)]
+pub struct SetMaxTradeAmount<'info> {
+    #[account(mut, has_one = admin, constraint = !market.terminated @ MarketError::MarketTerminated)]
+    pub market: Account<'info, Market>,
+    pub admin: Signer<'info>,
+}
+
+pub fn handler(ctx: Context<SetMaxTradeAmount>, max_trade_amount: u64) -> Result<()> {
+    let market = &mut ctx.accounts.market;
+    if ctx.accounts.admin.key() != market.admin {
+        return err!(MarketError::Unauthorized);
+    }
+    market.max_trade_amount = max_trade_amount;
+    msg!("Max trade amount set to {}", max_trade_amount);
+    Ok(())
+}
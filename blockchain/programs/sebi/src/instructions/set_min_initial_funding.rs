@@ -0,0 +1,21 @@
+use anchor_lang::prelude::*;
+use crate::state::Market;
+use crate::errors::MarketError;
+
+#[derive(Accounts)]
+pub struct SetMinInitialFunding<'info> {
+    #[account(mut, has_one = admin, constraint = !market.terminated @ MarketError::MarketTerminated)]
+    pub market: Account<'info, Market>,
+    pub admin: Signer<'info>,
+}
+
+/// Has no effect once the market has been funded.
+pub fn handler(ctx: Context<SetMinInitialFunding>, min_initial_funding: u64) -> Result<()> {
+    let market = &mut ctx.accounts.market;
+    if ctx.accounts.admin.key() != market.admin {
+        return err!(MarketError::Unauthorized);
+    }
+    market.min_initial_funding = min_initial_funding;
+    msg!("Min initial funding set to {}", min_initial_funding);
+    Ok(())
+}
@@ -0,0 +1,27 @@
+use anchor_lang::prelude::*;
+use crate::state::Market;
+use crate::errors::MarketError;
+
+#[derive(Accounts)]
+pub struct SetNotifyProgram<'info> {
+    #[account(mut, has_one = admin, constraint = !market.terminated @ MarketError::MarketTerminated)]
+    pub market: Account<'info, Market>,
+    pub admin: Signer<'info>,
+}
+
+/// Pubkey::default() removes the callback. See `notify::TradeNotification`
+/// for the interface the program must implement.
+pub fn handler(ctx: Context<SetNotifyProgram>, notify_program: Pubkey, notify_required: bool) -> Result<()> {
+    let market = &mut ctx.accounts.market;
+    if ctx.accounts.admin.key() != market.admin {
+        return err!(MarketError::Unauthorized);
+    }
+    // the runtime allows direct self-recursion, so this would let a trade re-enter
+    if notify_program == crate::ID {
+        return err!(MarketError::InvalidNotifyProgram);
+    }
+    market.notify_program = notify_program;
+    market.notify_required = notify_required;
+    msg!("Notify program set to {} (required: {})", notify_program, notify_required);
+    Ok(())
+}
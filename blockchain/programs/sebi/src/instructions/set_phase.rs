@@ -0,0 +1,29 @@
+use anchor_lang::prelude::*;
+use crate::state::{Market, MarketPhase};
+use crate::errors::MarketError;
+
+#[derive(Accounts)]
+pub struct SetPhase<'info> {
+    #[account(mut, has_one = admin, constraint = !market.terminated @ MarketError::MarketTerminated)]
+    pub market: Account<'info, Market>,
+    pub admin: Signer<'info>,
+}
+
+pub fn handler(ctx: Context<SetPhase>, phase: MarketPhase) -> Result<()> {
+    let market = &mut ctx.accounts.market;
+    if ctx.accounts.admin.key() != market.admin {
+        return err!(MarketError::Unauthorized);
+    }
+    // Closed is terminal
+    if market.phase == MarketPhase::Closed {
+        return err!(MarketError::InvalidPhase);
+    }
+    // claims were registered against a matured market
+    if market.redemption_pending {
+        return err!(MarketError::RedemptionPending);
+    }
+    market.phase = phase;
+    market.refresh_status();
+    msg!("Market phase: {:?}", phase);
+    Ok(())
+}
@@ -0,0 +1,22 @@
+use anchor_lang::prelude::*;
+use crate::state::Market;
+use crate::errors::MarketError;
+
+#[derive(Accounts)]
+pub struct SetPriceGuard<'info> {
+    #[account(mut, has_one = admin, constraint = !market.terminated @ MarketError::MarketTerminated)]
+    pub market: Account<'info, Market>,
+    pub admin: Signer<'info>,
+}
+
+/// Bounds every later price update, requotes and batches included; 0 disables either half
+pub fn handler(ctx: Context<SetPriceGuard>, max_price_change_bps: u16, price_update_cooldown_secs: u64) -> Result<()> {
+    let market = &mut ctx.accounts.market;
+    if ctx.accounts.admin.key() != market.admin {
+        return err!(MarketError::Unauthorized);
+    }
+    market.max_price_change_bps = max_price_change_bps;
+    market.price_update_cooldown_secs = price_update_cooldown_secs;
+    msg!("Price changes limited to {} bps every {}s", max_price_change_bps, price_update_cooldown_secs);
+    Ok(())
+}
@@ -0,0 +1,21 @@
+use anchor_lang::prelude::*;
+use crate::state::Market;
+use crate::errors::MarketError;
+
+#[derive(Accounts)]
+pub struct SetPriceUpdater<'info> {
+    #[account(mut, has_one = admin, constraint = !market.terminated @ MarketError::MarketTerminated)]
+    pub market: Account<'info, Market>,
+    pub admin: Signer<'info>,
+}
+
+/// Rotating the key invalidates every outstanding attestation it signed.
+pub fn handler(ctx: Context<SetPriceUpdater>, price_updater: Pubkey) -> Result<()> {
+    let market = &mut ctx.accounts.market;
+    if ctx.accounts.admin.key() != market.admin {
+        return err!(MarketError::Unauthorized);
+    }
+    market.price_updater = price_updater;
+    msg!("Price updater set to {}", price_updater);
+    Ok(())
+}
@@ -0,0 +1,26 @@
+use anchor_lang::prelude::*;
+use crate::state::ProgramConfig;
+use crate::errors::MarketError;
+
+#[derive(Accounts)]
+pub struct SetQuoteMintApproval<'info> {
+    #[account(mut, seeds = [b"config"], bump = config.bump, has_one = authority)]
+    pub config: Account<'info, ProgramConfig>,
+    pub authority: Signer<'info>,
+}
+
+/// Adds or removes a quote mint from the allowlist. Both directions are idempotent.
+pub fn handler(ctx: Context<SetQuoteMintApproval>, mint: Pubkey, approved: bool) -> Result<()> {
+    let config = &mut ctx.accounts.config;
+    let listed = config.approved_quote_mints.contains(&mint);
+    if approved && !listed {
+        if config.approved_quote_mints.len() >= ProgramConfig::MAX_QUOTE_MINTS {
+            return err!(MarketError::QuoteMintListFull);
+        }
+        config.approved_quote_mints.push(mint);
+    } else if !approved && listed {
+        config.approved_quote_mints.retain(|m| m != &mint);
+    }
+    msg!("Quote mint {} approved: {}", mint, approved);
+    Ok(())
+}
@@ -0,0 +1,21 @@
+use anchor_lang::prelude::*;
+use crate::state::Market;
+use crate::errors::MarketError;
+
+#[derive(Accounts)]
+pub struct SetTradeCooldown<'info> {
+    #[account(mut, has_one = admin, constraint = !market.terminated @ MarketError::MarketTerminated)]
+    pub market: Account<'info, Market>,
+    pub admin: Signer<'info>,
+}
+
+/// Applies from the next trade, measured from each trader's last one
+pub fn handler(ctx: Context<SetTradeCooldown>, trade_cooldown_secs: u64) -> Result<()> {
+    let market = &mut ctx.accounts.market;
+    if ctx.accounts.admin.key() != market.admin {
+        return err!(MarketError::Unauthorized);
+    }
+    market.trade_cooldown_secs = trade_cooldown_secs;
+    msg!("Trade cooldown set to {}s", trade_cooldown_secs);
+    Ok(())
+}
@@ -0,0 +1,34 @@
+use anchor_lang::prelude::*;
+use crate::state::{Market, TradingDelegate};
+use crate::errors::MarketError;
+
+#[derive(Accounts)]
+pub struct SetTradingDelegate<'info> {
+    #[account(constraint = !market.terminated @ MarketError::MarketTerminated)]
+    pub market: Account<'info, Market>,
+
+    #[account(
+        init_if_needed,
+        payer = owner,
+        space = TradingDelegate::LEN,
+        seeds = [b"delegate", market.key().as_ref(), owner.key().as_ref()],
+        bump
+    )]
+    pub trading_delegate: Account<'info, TradingDelegate>,
+
+    #[account(mut)]
+    pub owner: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+/// Replaces any existing delegate for this owner and market.
+pub fn handler(ctx: Context<SetTradingDelegate>, delegate: Pubkey) -> Result<()> {
+    let record = &mut ctx.accounts.trading_delegate;
+    record.market = ctx.accounts.market.key();
+    record.owner = ctx.accounts.owner.key();
+    record.delegate = delegate;
+    record.bump = ctx.bumps.trading_delegate;
+    msg!("Trading delegate for {} set to {}", record.owner, delegate);
+    Ok(())
+}
@@ -0,0 +1,23 @@
+use anchor_lang::prelude::*;
+use crate::state::Market;
+use crate::errors::MarketError;
+
+#[derive(Accounts)]
+pub struct SetWashCheck<'info> {
+    #[account(mut, has_one = admin, constraint = !market.terminated @ MarketError::MarketTerminated)]
+    pub market: Account<'info, Market>,
+    pub admin: Signer<'info>,
+}
+
+/// Caps each trader at `wash_flip_threshold` side flips per `wash_window_secs`;
+/// 0 in either disables the check
+pub fn handler(ctx: Context<SetWashCheck>, wash_window_secs: u64, wash_flip_threshold: u16) -> Result<()> {
+    let market = &mut ctx.accounts.market;
+    if ctx.accounts.admin.key() != market.admin {
+        return err!(MarketError::Unauthorized);
+    }
+    market.wash_window_secs = wash_window_secs;
+    market.wash_flip_threshold = wash_flip_threshold;
+    msg!("Wash check set to {} flips per {}s", wash_flip_threshold, wash_window_secs);
+    Ok(())
+}
@@ -0,0 +1,21 @@
+use anchor_lang::prelude::*;
+use crate::state::Market;
+use crate::errors::MarketError;
+
+#[derive(Accounts)]
+pub struct SetWithdrawCooldown<'info> {
+    #[account(mut, has_one = admin)]
+    pub market: Account<'info, Market>,
+    pub admin: Signer<'info>,
+}
+
+/// Applies from the next withdrawal, measured from the last one made
+pub fn handler(ctx: Context<SetWithdrawCooldown>, withdraw_cooldown: u64) -> Result<()> {
+    let market = &mut ctx.accounts.market;
+    if ctx.accounts.admin.key() != market.admin {
+        return err!(MarketError::Unauthorized);
+    }
+    market.withdraw_cooldown = withdraw_cooldown;
+    msg!("Withdraw cooldown set to {}s", withdraw_cooldown);
+    Ok(())
+}
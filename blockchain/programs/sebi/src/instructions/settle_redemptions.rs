@@ -0,0 +1,113 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token::{self, Token, TokenAccount, Transfer};
+use crate::state::{Market, RedemptionClaim, RedemptionWindow};
+use crate::errors::MarketError;
+use crate::clock;
+use crate::events::RedemptionPaidEvent;
+
+/// remaining_accounts per claim: [claim, holder_usdc]
+pub const SETTLE_GROUP_LEN: usize = 2;
+
+/// Most claims one settle_redemptions call may pay; crank the rest in later calls
+pub const MAX_SETTLE_BATCH: usize = 16;
+
+#[derive(Accounts)]
+pub struct SettleRedemptions<'info> {
+    #[account(mut, seeds = [b"market", market.bond_mint.as_ref()], bump = market.bump)]
+    pub market: Account<'info, Market>,
+
+    #[account(
+        mut,
+        seeds = [b"redemption", market.key().as_ref()],
+        bump = redemption_window.bump,
+        has_one = market
+    )]
+    pub redemption_window: Account<'info, RedemptionWindow>,
+
+    #[account(mut, constraint = vault_usdc.key() == market.vault_usdc)]
+    pub vault_usdc: Account<'info, TokenAccount>,
+
+    pub token_program: Program<'info, Token>,
+}
+
+/// Permissionless crank once the window has closed. The first call fixes the
+/// USDC available to claims; every call pays the listed claims their pro-rata
+/// share. Once the last claim is paid the market may withdraw again.
+pub fn handler<'info>(ctx: Context<'_, '_, 'info, 'info, SettleRedemptions<'info>>) -> Result<()> {
+    if ctx.remaining_accounts.len() > MAX_SETTLE_BATCH * SETTLE_GROUP_LEN {
+        return err!(MarketError::BatchTooLarge);
+    }
+    let groups = ctx.remaining_accounts.chunks_exact(SETTLE_GROUP_LEN);
+    if !groups.remainder().is_empty() {
+        return err!(MarketError::InvalidBatch);
+    }
+
+    let window = &mut ctx.accounts.redemption_window;
+    if clock::now()? < window.end_ts {
+        return err!(MarketError::RedemptionWindowOpen);
+    }
+    if !window.settled {
+        window.available = ctx.accounts.vault_usdc.amount.min(window.total_owed);
+        window.settled = true;
+    }
+
+    let market = &ctx.accounts.market;
+    let seeds = market.signer_seeds();
+    let signer = &[&seeds[..]];
+    let (mut redeemed_bonds, mut paid_total, mut settled_owed) = (0u64, 0u64, 0u64);
+    for group in groups {
+        if !group[0].is_writable {
+            return err!(MarketError::InvalidBatch);
+        }
+        let mut claim: Account<'info, RedemptionClaim> = Account::try_from(&group[0])?;
+        let holder_usdc: Account<'info, TokenAccount> = Account::try_from(&group[1])?;
+        if claim.market != market.key()
+            || holder_usdc.owner != claim.holder
+            || holder_usdc.mint != market.usdc_mint
+        {
+            return err!(MarketError::InvalidBatch);
+        }
+        if claim.paid {
+            return err!(MarketError::RedemptionAlreadyPaid);
+        }
+
+        let paid = window.payout(claim.owed)?;
+        if paid > 0 {
+            token::transfer(
+                CpiContext::new_with_signer(
+                    ctx.accounts.token_program.to_account_info(),
+                    Transfer {
+                        from: ctx.accounts.vault_usdc.to_account_info(),
+                        to: holder_usdc.to_account_info(),
+                        authority: market.to_account_info(),
+                    },
+                    signer,
+                ),
+                paid,
+            )?;
+        }
+
+        redeemed_bonds = redeemed_bonds.checked_add(claim.bonds).ok_or(MarketError::MathOverflow)?;
+        paid_total = paid_total.checked_add(paid).ok_or(MarketError::MathOverflow)?;
+        settled_owed = settled_owed.checked_add(claim.owed).ok_or(MarketError::MathOverflow)?;
+        claim.paid = true;
+        claim.exit(&crate::ID)?;
+        window.paid_count = window.paid_count.checked_add(1).ok_or(MarketError::MathOverflow)?;
+        emit!(RedemptionPaidEvent {
+            market: market.key(),
+            holder: claim.holder,
+            bonds: claim.bonds,
+            owed: claim.owed,
+            paid,
+        });
+    }
+
+    // redeemed bonds are back in the vault and no longer need backing
+    let market = &mut ctx.accounts.market;
+    market.record_sell(redeemed_bonds, paid_total)?;
+    market.total_pending_redemption = market.total_pending_redemption.saturating_sub(settled_owed);
+    if window.paid_count == window.claim_count {
+        market.redemption_pending = false;
+    }
+    Ok(())
+}
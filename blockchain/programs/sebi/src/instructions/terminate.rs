@@ -0,0 +1,31 @@
+use anchor_lang::prelude::*;
+use crate::state::Market;
+use crate::errors::MarketError;
+use crate::clock;
+use crate::events::MarketTerminatedEvent;
+
+#[derive(Accounts)]
+pub struct Terminate<'info> {
+    #[account(mut, has_one = admin, constraint = !market.terminated @ MarketError::MarketTerminated)]
+    pub market: Account<'info, Market>,
+    pub admin: Signer<'info>,
+}
+
+/// Irreversible: there is deliberately no un-terminate. The account stays for
+/// the record. Holders exit through a redemption window, and withdraw stays
+/// open for whatever the outstanding bonds don't need.
+pub fn handler(ctx: Context<Terminate>) -> Result<()> {
+    let market = &mut ctx.accounts.market;
+    if ctx.accounts.admin.key() != market.admin {
+        return err!(MarketError::Unauthorized);
+    }
+    market.terminated = true;
+    market.refresh_status();
+    emit!(MarketTerminatedEvent {
+        market: market.key(),
+        admin: market.admin,
+        ts: clock::now()?,
+    });
+    msg!("Market terminated");
+    Ok(())
+}
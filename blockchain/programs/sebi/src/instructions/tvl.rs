@@ -0,0 +1,26 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token::TokenAccount;
+use crate::state::Market;
+use crate::errors::MarketError;
+
+#[derive(Accounts)]
+pub struct Tvl<'info> {
+    pub market: Account<'info, Market>,
+
+    #[account(constraint = vault_bond.key() == market.vault_bond)]
+    pub vault_bond: Account<'info, TokenAccount>,
+
+    #[account(constraint = vault_usdc.key() == market.vault_usdc)]
+    pub vault_usdc: Account<'info, TokenAccount>,
+}
+
+/// Returns vault_usdc + vault_bond valued at the current price, in USDC base
+/// units, via return data. Bonds are valued with the same scaling and
+/// round-down rule as sell.
+pub fn handler(ctx: Context<Tvl>) -> Result<u128> {
+    let market = &ctx.accounts.market;
+    let bond_value = market.inventory_value(ctx.accounts.vault_bond.amount)?;
+    (ctx.accounts.vault_usdc.amount as u128)
+        .checked_add(bond_value)
+        .ok_or(error!(MarketError::MathOverflow))
+}
@@ -0,0 +1,19 @@
+use anchor_lang::prelude::*;
+use crate::state::PriceHistory;
+use crate::clock;
+
+#[derive(Accounts)]
+pub struct Twap<'info> {
+    /// CHECK: only used to derive the history PDA
+    pub market: UncheckedAccount<'info>,
+
+    #[account(seeds = [b"price_history", market.key().as_ref()], bump = price_history.bump)]
+    pub price_history: Account<'info, PriceHistory>,
+}
+
+/// Returns the time-weighted average of price_per_token over the last
+/// `window_secs`, in the market's price units, via return data. Only the
+/// stored history counts, so the lookback is capped at PriceHistory::CAPACITY updates.
+pub fn handler(ctx: Context<Twap>, window_secs: u64) -> Result<u128> {
+    ctx.accounts.price_history.twap(clock::now()?, window_secs)
+}
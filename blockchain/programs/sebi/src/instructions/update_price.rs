@@ -1,11 +1,16 @@
 use anchor_lang::prelude::*;
-use crate::state::Market;
+use crate::state::{Market, PriceHistory};
 use crate::errors::MarketError;
+use crate::clock;
 
 #[derive(Accounts)]
 pub struct UpdatePrice<'info> {
-    #[account(mut, has_one = admin)]
+    #[account(mut, has_one = admin, constraint = !market.terminated @ MarketError::MarketTerminated)]
     pub market: Account<'info, Market>,
+
+    #[account(mut, seeds = [b"price_history", market.key().as_ref()], bump = price_history.bump)]
+    pub price_history: Account<'info, PriceHistory>,
+
     pub admin: Signer<'info>,
 }
 
@@ -14,7 +19,31 @@ pub fn handler(ctx: Context<UpdatePrice>, new_price: u128) -> Result<()> {
     if ctx.accounts.admin.key() != market.admin {
         return err!(MarketError::Unauthorized);
     }
-    market.price_per_token = new_price;
+    apply(market, &mut ctx.accounts.price_history, new_price, clock::now()?)?;
     msg!("Price updated to {}", new_price);
     Ok(())
 }
+
+/// Price change shared by update_price, update_prices_batch and the
+/// requote_and_* instructions, so each enforces the change limit and cooldown
+pub fn apply(market: &mut Market, history: &mut PriceHistory, new_price: u128, now: i64) -> Result<()> {
+    if market.price_frozen {
+        return err!(MarketError::PriceFrozen);
+    }
+    // the admin takes the price back once the auction schedule has run out
+    if market.auction_enabled {
+        if market.is_auction_running(now) {
+            return err!(MarketError::AuctionInProgress);
+        }
+        market.auction_enabled = false;
+    }
+    if !market.is_on_tick(new_price) {
+        return err!(MarketError::InvalidTick);
+    }
+    market.check_price_update(new_price, now)?;
+    market.price_per_token = new_price;
+    market.last_price_update_ts = now;
+    market.refresh_display_price()?;
+    history.record(new_price, now);
+    Ok(())
+}
@@ -0,0 +1,58 @@
+use anchor_lang::prelude::*;
+use crate::state::{Market, PriceHistory};
+use crate::errors::MarketError;
+use crate::clock;
+use crate::instructions::update_price;
+
+/// remaining_accounts per market: [market, price_history]
+pub const PRICE_BATCH_GROUP_LEN: usize = 2;
+
+/// Most markets one update_prices_batch may reprice
+pub const MAX_PRICE_BATCH: usize = 16;
+
+#[derive(Accounts)]
+pub struct UpdatePricesBatch<'info> {
+    pub admin: Signer<'info>,
+}
+
+/// Sets new_prices[i] on the i-th market group, applying update_price's
+/// checks to each, including its change limit and cooldown. `admin` must
+/// administer every market; any failure reverts the whole batch.
+pub fn handler<'info>(
+    ctx: Context<'_, '_, 'info, 'info, UpdatePricesBatch<'info>>,
+    new_prices: Vec<u128>,
+) -> Result<()> {
+    if new_prices.len() > MAX_PRICE_BATCH {
+        return err!(MarketError::BatchTooLarge);
+    }
+    let groups = ctx.remaining_accounts;
+    if new_prices.is_empty() || groups.len() != new_prices.len() * PRICE_BATCH_GROUP_LEN {
+        return err!(MarketError::InvalidBatch);
+    }
+
+    let now = clock::now()?;
+    let admin = ctx.accounts.admin.key();
+    // written back per group, so a market listed twice ends at its last price
+    for (group, &new_price) in groups.chunks(PRICE_BATCH_GROUP_LEN).zip(&new_prices) {
+        if !group[0].is_writable || !group[1].is_writable {
+            return err!(MarketError::InvalidBatch);
+        }
+        let mut market: Account<'info, Market> = Account::try_from(&group[0])?;
+        let mut history: Account<'info, PriceHistory> = Account::try_from(&group[1])?;
+        if history.market != market.key() {
+            return err!(MarketError::InvalidBatch);
+        }
+        if market.admin != admin {
+            return err!(MarketError::Unauthorized);
+        }
+        if market.terminated {
+            return err!(MarketError::MarketTerminated);
+        }
+
+        update_price::apply(&mut market, &mut history, new_price, now)?;
+        msg!("Price of {} updated to {}", market.key(), new_price);
+        market.exit(&crate::ID)?;
+        history.exit(&crate::ID)?;
+    }
+    Ok(())
+}
@@ -0,0 +1,69 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token::TokenAccount;
+use crate::state::Market;
+use crate::clock;
+
+#[derive(Accounts)]
+pub struct ValidateConfig<'info> {
+    pub market: Account<'info, Market>,
+
+    #[account(constraint = vault_bond.key() == market.vault_bond)]
+    pub vault_bond: Account<'info, TokenAccount>,
+
+    #[account(constraint = vault_usdc.key() == market.vault_usdc)]
+    pub vault_usdc: Account<'info, TokenAccount>,
+}
+
+// Problem bits returned by validate_config; 0 means ready to launch
+/// price_per_token is zero
+pub const CONFIG_ZERO_PRICE: u32 = 1 << 0;
+/// bond/usdc decimals were never recorded
+pub const CONFIG_DECIMALS_UNSET: u32 = 1 << 1;
+/// vault_bond holds no inventory to sell
+pub const CONFIG_NO_INVENTORY: u32 = 1 << 2;
+/// vault_usdc is below min_initial_funding, so sells are still held
+pub const CONFIG_NOT_FUNDED: u32 = 1 << 3;
+/// vault_usdc can't buy back every outstanding bond at the current price
+pub const CONFIG_UNDER_BACKED: u32 = 1 << 4;
+/// a vault holds a different mint than the market trades
+pub const CONFIG_VAULT_MINT_MISMATCH: u32 = 1 << 5;
+/// the vaults were handed off, so the market PDA can't sign for them
+pub const CONFIG_VAULT_AUTHORITY_ROTATED: u32 = 1 << 6;
+/// insurance_balance is credited but no insurance vault exists
+pub const CONFIG_INSURANCE_UNBACKED: u32 = 1 << 7;
+/// price_per_token is off the configured price_tick
+pub const CONFIG_OFF_TICK: u32 = 1 << 8;
+/// trading is halted by a pause
+pub const CONFIG_HALTED: u32 = 1 << 9;
+/// the phase allows no buying, or the market is terminated
+pub const CONFIG_NOT_TRADING: u32 = 1 << 10;
+
+/// Launch-readiness checklist. Returns the CONFIG_* bits of every failed
+/// check via return data instead of erroring, so one call lists them all.
+pub fn handler(ctx: Context<ValidateConfig>) -> Result<u32> {
+    let now = clock::now()?;
+    let market = &ctx.accounts.market;
+    let vault_bond = &ctx.accounts.vault_bond;
+    let vault_usdc = &ctx.accounts.vault_usdc;
+
+    let checks = [
+        (market.price_per_token == 0, CONFIG_ZERO_PRICE),
+        (!market.decimals_set, CONFIG_DECIMALS_UNSET),
+        (vault_bond.amount == 0, CONFIG_NO_INVENTORY),
+        (!market.is_funded(vault_usdc.amount), CONFIG_NOT_FUNDED),
+        ((vault_usdc.amount as u128) < market.backing_required()?, CONFIG_UNDER_BACKED),
+        (
+            vault_bond.mint != market.bond_mint || vault_usdc.mint != market.usdc_mint,
+            CONFIG_VAULT_MINT_MISMATCH,
+        ),
+        (market.vault_authority != market.key(), CONFIG_VAULT_AUTHORITY_ROTATED),
+        (
+            market.insurance_balance > 0 && market.insurance_vault == Pubkey::default(),
+            CONFIG_INSURANCE_UNBACKED,
+        ),
+        (!market.is_on_tick(market.price_per_token), CONFIG_OFF_TICK),
+        (market.is_halted(now), CONFIG_HALTED),
+        (market.terminated || !market.phase.allows_buy(), CONFIG_NOT_TRADING),
+    ];
+    Ok(checks.iter().filter(|(failed, _)| *failed).fold(0, |mask, (_, bit)| mask | bit))
+}
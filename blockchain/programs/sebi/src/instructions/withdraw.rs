@@ -2,6 +2,9 @@ use anchor_lang::prelude::*;
 use anchor_spl::token::{self, Token, TokenAccount, Transfer};
 use crate::state::Market;
 use crate::errors::MarketError;
+use crate::clock;
+#[cfg(feature = "invariant-checks")]
+use crate::invariants;
 
 #[derive(Accounts)]
 pub struct Withdraw<'info> {
@@ -9,7 +12,8 @@ pub struct Withdraw<'info> {
     pub market: Account<'info, Market>,
     pub admin: Signer<'info>,
 
-    #[account(mut, constraint = destination.owner == admin.key())]
+    /// Owned by admin, or by market.approved_payee once its timelock has passed
+    #[account(mut)]
     pub destination: Account<'info, TokenAccount>,
 
     #[account(mut, constraint = vault_bond.key() == market.vault_bond)]
@@ -22,11 +26,41 @@ pub struct Withdraw<'info> {
 }
 
 pub fn handler(ctx: Context<Withdraw>, amount: u64, is_usdc: bool) -> Result<()> {
-    let market = &ctx.accounts.market;
-    if ctx.accounts.admin.key() != market.admin {
+    if ctx.accounts.admin.key() != ctx.accounts.market.admin {
         return err!(MarketError::Unauthorized);
     }
-    let seeds = &[b"market", market.bond_mint.as_ref(), &[market.bump]];
+
+    let now = clock::now()?;
+    // value outstanding bonds at the price a sell would pay right now
+    ctx.accounts.market.sync_auction_price(now)?;
+    let market = &ctx.accounts.market;
+    if market.in_withdraw_cooldown(now) {
+        return err!(MarketError::WithdrawCooldown);
+    }
+    if !market.is_approved_payee(&ctx.accounts.destination.owner, now) {
+        return err!(MarketError::UnapprovedPayee);
+    }
+
+    // Solvency: vault_usdc.amount - amount >= max(net_bonds_out, 0) * price_per_token / 10^price_scale,
+    // i.e. every outstanding bond can still be sold back at the current (auction) price.
+    if is_usdc {
+        let remaining = ctx.accounts.vault_usdc.amount
+            .checked_sub(amount)
+            .ok_or(MarketError::InsufficientVaultFunds)?;
+        // registered redemption claims have first call on vault_usdc; their
+        // bonds still count in backing_required until paid, so this is conservative
+        let unreserved = remaining
+            .checked_sub(market.total_pending_redemption)
+            .ok_or(MarketError::RedemptionClaimsOutstanding)?;
+        if (unreserved as u128) < market.backing_required()? {
+            return err!(MarketError::WouldBeInsolvent);
+        }
+        if market.exceeds_withdraw_cap(amount)? {
+            return err!(MarketError::WithdrawCapReached);
+        }
+    }
+
+    let seeds = market.signer_seeds();
     let signer = &[&seeds[..]];
 
     if is_usdc {
@@ -57,5 +91,19 @@ pub fn handler(ctx: Context<Withdraw>, amount: u64, is_usdc: bool) -> Result<()>
         )?;
     }
 
+    ctx.accounts.market.last_withdraw_ts = now;
+    if is_usdc {
+        let market = &mut ctx.accounts.market;
+        market.cumulative_withdrawn = market.cumulative_withdrawn.checked_add(amount).ok_or(MarketError::MathOverflow)?;
+    }
+
+    #[cfg(feature = "invariant-checks")]
+    {
+        let vault = if is_usdc { &mut ctx.accounts.vault_usdc } else { &mut ctx.accounts.vault_bond };
+        let before = vault.amount;
+        vault.reload()?;
+        invariants::check_balance_delta(before, vault.amount, -(amount as i128))?;
+    }
+
     Ok(())
 }
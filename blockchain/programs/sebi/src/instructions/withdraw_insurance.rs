@@ -0,0 +1,63 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token::{self, Token, TokenAccount, Transfer};
+use crate::state::Market;
+use crate::errors::MarketError;
+use crate::clock;
+use crate::events::InsuranceWithdrawnEvent;
+
+#[derive(Accounts)]
+pub struct WithdrawInsurance<'info> {
+    #[account(mut, has_one = admin, seeds = [b"market", market.bond_mint.as_ref()], bump = market.bump)]
+    pub market: Account<'info, Market>,
+    pub admin: Signer<'info>,
+
+    #[account(mut, constraint = insurance_vault.key() == market.insurance_vault)]
+    pub insurance_vault: Account<'info, TokenAccount>,
+
+    /// Owned by admin, or by market.approved_payee once its timelock has passed
+    #[account(mut)]
+    pub destination: Account<'info, TokenAccount>,
+
+    pub token_program: Program<'info, Token>,
+}
+
+/// Returns credited insurance to the admin once no sell can draw on it:
+/// the market is terminated, never buys back, or is past Active.
+pub fn handler(ctx: Context<WithdrawInsurance>, amount: u64) -> Result<()> {
+    let market = &ctx.accounts.market;
+    if ctx.accounts.admin.key() != market.admin {
+        return err!(MarketError::Unauthorized);
+    }
+    if !market.terminated && market.sell_enabled && market.phase.allows_sell() {
+        return err!(MarketError::InsuranceBacksSells);
+    }
+    if !market.is_approved_payee(&ctx.accounts.destination.owner, clock::now()?) {
+        return err!(MarketError::UnapprovedPayee);
+    }
+    let remaining = market.insurance_balance.checked_sub(amount).ok_or(MarketError::InsufficientVaultFunds)?;
+
+    let seeds = market.signer_seeds();
+    let signer = &[&seeds[..]];
+    token::transfer(
+        CpiContext::new_with_signer(
+            ctx.accounts.token_program.to_account_info(),
+            Transfer {
+                from: ctx.accounts.insurance_vault.to_account_info(),
+                to: ctx.accounts.destination.to_account_info(),
+                authority: ctx.accounts.market.to_account_info(),
+            },
+            signer,
+        ),
+        amount,
+    )?;
+
+    let market = &mut ctx.accounts.market;
+    market.insurance_balance = remaining;
+    emit!(InsuranceWithdrawnEvent {
+        market: market.key(),
+        destination: ctx.accounts.destination.key(),
+        amount,
+        remaining,
+    });
+    Ok(())
+}
@@ -0,0 +1,28 @@
+//! Post-condition checks compiled in by the `invariant-checks` feature.
+//! They reload the vaults after a handler's transfers and confirm each one
+//! moved by exactly what the handler accounted for.
+use anchor_lang::prelude::*;
+use crate::errors::MarketError;
+
+/// `after` must equal `before + delta`
+pub fn check_balance_delta(before: u64, after: u64, delta: i128) -> Result<()> {
+    if after as i128 - before as i128 != delta {
+        msg!("invariant: balance {} -> {}, expected delta {}", before, after, delta);
+        return err!(MarketError::InvariantViolated);
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn balance_delta_must_match_exactly() {
+        assert!(check_balance_delta(100, 150, 50).is_ok());
+        assert!(check_balance_delta(100, 40, -60).is_ok());
+        assert!(check_balance_delta(0, u64::MAX, u64::MAX as i128).is_ok());
+        assert!(check_balance_delta(100, 151, 50).is_err());
+        assert!(check_balance_delta(100, 100, -1).is_err());
+    }
+}
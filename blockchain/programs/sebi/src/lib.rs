@@ -1,9 +1,23 @@
+// Anchor 0.31 IDL codegen still calls `AccountInfo::realloc`, deprecated in solana 2.2.
+#![allow(deprecated)]
 use anchor_lang::prelude::*;
+pub mod constants;
 pub mod state;
 pub mod errors;
+pub mod events;
+pub mod math;
+pub mod clock;
+pub mod attestation;
+pub mod compliance;
+pub mod attribution;
+pub mod notify;
+#[cfg(any(test, feature = "invariant-checks"))]
+pub mod invariants;
 pub mod instructions;
 
 use instructions::*;
+use state::{AuctionConfig, EventVerbosity, MarketPhase, PriceBasis, PricePoint};
+use events::TradeSide;
 
 declare_id!("FPrNfqSjEL59H3PAEzXK9gU9VwAFXLrMwyFeNZ3dKb7o");
 
@@ -11,38 +25,370 @@ declare_id!("FPrNfqSjEL59H3PAEzXK9gU9VwAFXLrMwyFeNZ3dKb7o");
 pub mod sebi {
     use super::*;
 
+    pub fn init_config(ctx: Context<InitConfig>) -> Result<()> {
+        init_config::handler(ctx)
+    }
+
+    pub fn set_quote_mint_approval(
+        ctx: Context<SetQuoteMintApproval>,
+        mint: Pubkey,
+        approved: bool,
+    ) -> Result<()> {
+        set_quote_mint_approval::handler(ctx, mint, approved)
+    }
+
     pub fn initialize_market(
         ctx: Context<InitializeMarket>,
         price_per_token: u128,
+        price_scale: u32,
+        sell_enabled: bool,
+        auction: Option<AuctionConfig>,
+        price_basis: PriceBasis,
+        lot_size: u64,
     ) -> Result<()> {
-        initialize::handler(ctx, price_per_token)
+        initialize::handler(ctx, price_per_token, price_scale, sell_enabled, auction, price_basis, lot_size)
     }
 
     pub fn buy(ctx: Context<Buy>, amount: u64) -> Result<()> {
         buy::handler(ctx, amount)
     }
 
+    pub fn buy_if_below(ctx: Context<Buy>, amount: u64, max_price: u128, max_cost: u64) -> Result<()> {
+        buy_if_below::handler(ctx, amount, max_price, max_cost)
+    }
+
     pub fn sell(ctx: Context<Sell>, amount: u64) -> Result<()> {
         sell::handler(ctx, amount)
     }
 
+    pub fn sell_batch<'info>(
+        ctx: Context<'_, '_, 'info, 'info, SellBatch<'info>>,
+        amounts: Vec<u64>,
+        min_proceeds: Vec<u64>,
+    ) -> Result<u64> {
+        sell_batch::handler(ctx, amounts, min_proceeds)
+    }
+
     pub fn update_price(ctx: Context<UpdatePrice>, new_price: u128) -> Result<()> {
         update_price::handler(ctx, new_price)
     }
 
+    pub fn set_price_tick(ctx: Context<SetPriceTick>, price_tick: u128) -> Result<()> {
+        set_price_tick::handler(ctx, price_tick)
+    }
+
+    pub fn freeze_price(ctx: Context<FreezePrice>) -> Result<()> {
+        freeze_price::handler(ctx)
+    }
+
+    pub fn set_low_inventory_threshold(
+        ctx: Context<SetLowInventoryThreshold>,
+        threshold: u64,
+    ) -> Result<()> {
+        set_low_inventory_threshold::handler(ctx, threshold)
+    }
+
+    pub fn set_max_trade_amount(ctx: Context<SetMaxTradeAmount>, max_trade_amount: u64) -> Result<()> {
+        set_max_trade_amount::handler(ctx, max_trade_amount)
+    }
+
     pub fn pause(ctx: Context<Pause>) -> Result<()> {
         pause::handler(ctx)
     }
 
+    pub fn pause_with_grace(ctx: Context<PauseWithGrace>, grace_secs: u64) -> Result<()> {
+        pause_with_grace::handler(ctx, grace_secs)
+    }
+
+    pub fn set_circuit_breaker_authority(ctx: Context<SetCircuitBreakerAuthority>, authority: Pubkey) -> Result<()> {
+        set_circuit_breaker_authority::handler(ctx, authority)
+    }
+
+    pub fn set_phase(ctx: Context<SetPhase>, phase: MarketPhase) -> Result<()> {
+        set_phase::handler(ctx, phase)
+    }
+
     pub fn withdraw(ctx: Context<Withdraw>, amount: u64, is_usdc: bool) -> Result<()> {
         withdraw::handler(ctx, amount, is_usdc)
     }
+
+    pub fn rescue_tokens(ctx: Context<RescueTokens>) -> Result<()> {
+        rescue_tokens::handler(ctx)
+    }
+
+    pub fn get_registry_page(ctx: Context<GetRegistryPage>, page: u64) -> Result<Vec<Pubkey>> {
+        get_registry_page::handler(ctx, page)
+    }
+
+    pub fn init_insurance_vault(ctx: Context<InitInsuranceVault>) -> Result<()> {
+        init_insurance_vault::handler(ctx)
+    }
+
+    pub fn fund_insurance(ctx: Context<FundInsurance>, amount: u64) -> Result<()> {
+        fund_insurance::handler(ctx, amount)
+    }
+
+    pub fn program_info(ctx: Context<GetProgramInfo>) -> Result<ProgramInfo> {
+        program_info::handler(ctx)
+    }
+
+    pub fn tvl(ctx: Context<Tvl>) -> Result<u128> {
+        tvl::handler(ctx)
+    }
+
+    pub fn refresh_display_price(ctx: Context<RefreshDisplayPrice>) -> Result<u64> {
+        refresh_display_price::handler(ctx)
+    }
+
+    pub fn set_decimals(ctx: Context<SetDecimals>, bond_decimals: u8, usdc_decimals: u8) -> Result<()> {
+        set_decimals::handler(ctx, bond_decimals, usdc_decimals)
+    }
+
+    pub fn get_price_history(ctx: Context<GetPriceHistory>) -> Result<Vec<PricePoint>> {
+        get_price_history::handler(ctx)
+    }
+
+    pub fn twap(ctx: Context<Twap>, window_secs: u64) -> Result<u128> {
+        twap::handler(ctx, window_secs)
+    }
+
+    pub fn set_price_updater(ctx: Context<SetPriceUpdater>, price_updater: Pubkey) -> Result<()> {
+        set_price_updater::handler(ctx, price_updater)
+    }
+
+    pub fn rotate_vault_authority(ctx: Context<RotateVaultAuthority>, new_authority: Pubkey) -> Result<()> {
+        rotate_vault_authority::handler(ctx, new_authority)
+    }
+
+    pub fn terminate(ctx: Context<Terminate>) -> Result<()> {
+        terminate::handler(ctx)
+    }
+
+    pub fn init_protocol_stats(ctx: Context<InitProtocolStats>) -> Result<()> {
+        init_protocol_stats::handler(ctx)
+    }
+
+    pub fn get_protocol_stats(ctx: Context<GetProtocolStats>) -> Result<ProtocolStatsView> {
+        get_protocol_stats::handler(ctx)
+    }
+
+    pub fn open_redemption(ctx: Context<OpenRedemption>, redemption_price: u128, window_secs: u64) -> Result<()> {
+        open_redemption::handler(ctx, redemption_price, window_secs)
+    }
+
+    pub fn register_redemption(ctx: Context<RegisterRedemption>, amount: u64) -> Result<()> {
+        register_redemption::handler(ctx, amount)
+    }
+
+    pub fn settle_redemptions<'info>(ctx: Context<'_, '_, 'info, 'info, SettleRedemptions<'info>>) -> Result<()> {
+        settle_redemptions::handler(ctx)
+    }
+
+    pub fn mint_bonds_to_vault(ctx: Context<MintBondsToVault>, amount: u64) -> Result<()> {
+        mint_bonds_to_vault::handler(ctx, amount)
+    }
+
+    pub fn set_compliance_program(ctx: Context<SetComplianceProgram>, compliance_program: Pubkey) -> Result<()> {
+        set_compliance_program::handler(ctx, compliance_program)
+    }
+
+    pub fn set_withdraw_cooldown(ctx: Context<SetWithdrawCooldown>, withdraw_cooldown: u64) -> Result<()> {
+        set_withdraw_cooldown::handler(ctx, withdraw_cooldown)
+    }
+
+    pub fn can_trade(ctx: Context<CanTrade>, side: TradeSide, amount: u64) -> Result<TradeCheck> {
+        can_trade::handler(ctx, side, amount)
+    }
+
+    pub fn set_event_verbosity(ctx: Context<SetEventVerbosity>, event_verbosity: EventVerbosity) -> Result<()> {
+        set_event_verbosity::handler(ctx, event_verbosity)
+    }
+
+    pub fn set_trading_delegate(ctx: Context<SetTradingDelegate>, delegate: Pubkey) -> Result<()> {
+        set_trading_delegate::handler(ctx, delegate)
+    }
+
+    pub fn revoke_trading_delegate(ctx: Context<RevokeTradingDelegate>) -> Result<()> {
+        revoke_trading_delegate::handler(ctx)
+    }
+
+    pub fn set_max_slippage_bps(ctx: Context<SetMaxSlippageBps>, max_slippage_bps: u16) -> Result<()> {
+        set_max_slippage_bps::handler(ctx, max_slippage_bps)
+    }
+
+    pub fn init_daily_stats(ctx: Context<InitDailyStats>) -> Result<()> {
+        init_daily_stats::handler(ctx)
+    }
+
+    pub fn set_burn_unsold_at_maturity(ctx: Context<SetBurnUnsoldAtMaturity>, burn_unsold_at_maturity: bool) -> Result<()> {
+        set_burn_unsold_at_maturity::handler(ctx, burn_unsold_at_maturity)
+    }
+
+    pub fn burn_unsold(ctx: Context<BurnUnsold>) -> Result<()> {
+        burn_unsold::handler(ctx)
+    }
+
+    pub fn set_min_initial_funding(ctx: Context<SetMinInitialFunding>, min_initial_funding: u64) -> Result<()> {
+        set_min_initial_funding::handler(ctx, min_initial_funding)
+    }
+
+    pub fn emergency_exit(ctx: Context<EmergencyExit>, amount: u64) -> Result<()> {
+        emergency_exit::handler(ctx, amount)
+    }
+
+    pub fn set_emergency_exit_enabled(ctx: Context<SetEmergencyExitEnabled>, emergency_exit_enabled: bool) -> Result<()> {
+        set_emergency_exit_enabled::handler(ctx, emergency_exit_enabled)
+    }
+
+    pub fn validate_config(ctx: Context<ValidateConfig>) -> Result<u32> {
+        validate_config::handler(ctx)
+    }
+
+    pub fn set_notify_program(ctx: Context<SetNotifyProgram>, notify_program: Pubkey, notify_required: bool) -> Result<()> {
+        set_notify_program::handler(ctx, notify_program, notify_required)
+    }
+
+    pub fn set_max_markets_per_admin(ctx: Context<SetMaxMarketsPerAdmin>, max_markets_per_admin: u64) -> Result<()> {
+        set_max_markets_per_admin::handler(ctx, max_markets_per_admin)
+    }
+
+    pub fn migrate_quote_mint(ctx: Context<MigrateQuoteMint>) -> Result<()> {
+        migrate_quote_mint::handler(ctx)
+    }
+
+    #[cfg(feature = "devnet-faucet")]
+    pub fn faucet_bonds(ctx: Context<FaucetBonds>, amount: u64) -> Result<()> {
+        faucet_bonds::handler(ctx, amount)
+    }
+
+    pub fn set_max_notional(ctx: Context<SetMaxNotional>, max_notional: u64) -> Result<()> {
+        set_max_notional::handler(ctx, max_notional)
+    }
+
+    pub fn set_approved_payee(ctx: Context<SetApprovedPayee>, payee: Pubkey) -> Result<()> {
+        set_approved_payee::handler(ctx, payee)
+    }
+
+    pub fn requote_and_buy<'info>(ctx: Context<'_, '_, 'info, 'info, RequoteAndBuy<'info>>, new_price: u128, amount: u64) -> Result<()> {
+        requote_and_buy::handler(ctx, new_price, amount)
+    }
+
+    pub fn requote_and_sell<'info>(ctx: Context<'_, '_, 'info, 'info, RequoteAndSell<'info>>, new_price: u128, amount: u64) -> Result<()> {
+        requote_and_sell::handler(ctx, new_price, amount)
+    }
+
+    pub fn update_prices_batch<'info>(ctx: Context<'_, '_, 'info, 'info, UpdatePricesBatch<'info>>, new_prices: Vec<u128>) -> Result<()> {
+        update_prices_batch::handler(ctx, new_prices)
+    }
+
+    pub fn set_max_cumulative_withdraw(ctx: Context<SetMaxCumulativeWithdraw>, max_cumulative_withdraw: u64) -> Result<()> {
+        set_max_cumulative_withdraw::handler(ctx, max_cumulative_withdraw)
+    }
+
+    pub fn authorize_relayer(ctx: Context<AuthorizeRelayer>, relayer: Pubkey, max_notional: u64, expiry: i64) -> Result<()> {
+        authorize_relayer::handler(ctx, relayer, max_notional, expiry)
+    }
+
+    pub fn revoke_relayer(ctx: Context<RevokeRelayer>) -> Result<()> {
+        revoke_relayer::handler(ctx)
+    }
+
+    pub fn required_backing(ctx: Context<RequiredBacking>) -> Result<u128> {
+        required_backing::handler(ctx)
+    }
+
+    pub fn migrate_market(ctx: Context<MigrateMarket>) -> Result<()> {
+        migrate_market::handler(ctx)
+    }
+
+    pub fn open_investor_position(ctx: Context<OpenInvestorPosition>) -> Result<()> {
+        open_investor_position::handler(ctx)
+    }
+
+    pub fn set_trade_cooldown(ctx: Context<SetTradeCooldown>, trade_cooldown_secs: u64) -> Result<()> {
+        set_trade_cooldown::handler(ctx, trade_cooldown_secs)
+    }
+
+    pub fn withdraw_insurance(ctx: Context<WithdrawInsurance>, amount: u64) -> Result<()> {
+        withdraw_insurance::handler(ctx, amount)
+    }
+
+    pub fn set_price_guard(ctx: Context<SetPriceGuard>, max_price_change_bps: u16, price_update_cooldown_secs: u64) -> Result<()> {
+        set_price_guard::handler(ctx, max_price_change_bps, price_update_cooldown_secs)
+    }
+
+    pub fn set_wash_check(ctx: Context<SetWashCheck>, wash_window_secs: u64, wash_flip_threshold: u16) -> Result<()> {
+        set_wash_check::handler(ctx, wash_window_secs, wash_flip_threshold)
+    }
 }
 
 // Re-export contexts for use in modules
 pub use instructions::initialize::InitializeMarket;
+pub use instructions::init_config::InitConfig;
+pub use instructions::set_quote_mint_approval::SetQuoteMintApproval;
 pub use instructions::buy::Buy;
 pub use instructions::sell::Sell;
+pub use instructions::sell_batch::SellBatch;
 pub use instructions::update_price::UpdatePrice;
+pub use instructions::set_price_tick::SetPriceTick;
+pub use instructions::freeze_price::FreezePrice;
+pub use instructions::set_low_inventory_threshold::SetLowInventoryThreshold;
+pub use instructions::set_max_trade_amount::SetMaxTradeAmount;
 pub use instructions::pause::Pause;
+pub use instructions::pause_with_grace::PauseWithGrace;
+pub use instructions::set_circuit_breaker_authority::SetCircuitBreakerAuthority;
+pub use instructions::set_phase::SetPhase;
 pub use instructions::withdraw::Withdraw;
+pub use instructions::rescue_tokens::RescueTokens;
+pub use instructions::get_registry_page::GetRegistryPage;
+pub use instructions::init_insurance_vault::InitInsuranceVault;
+pub use instructions::fund_insurance::FundInsurance;
+pub use instructions::program_info::GetProgramInfo;
+pub use instructions::tvl::Tvl;
+pub use instructions::refresh_display_price::RefreshDisplayPrice;
+pub use instructions::set_decimals::SetDecimals;
+pub use instructions::get_price_history::GetPriceHistory;
+pub use instructions::twap::Twap;
+pub use instructions::set_price_updater::SetPriceUpdater;
+pub use instructions::rotate_vault_authority::RotateVaultAuthority;
+pub use instructions::terminate::Terminate;
+pub use instructions::init_protocol_stats::InitProtocolStats;
+pub use instructions::get_protocol_stats::GetProtocolStats;
+pub use instructions::open_redemption::OpenRedemption;
+pub use instructions::register_redemption::RegisterRedemption;
+pub use instructions::settle_redemptions::SettleRedemptions;
+pub use instructions::mint_bonds_to_vault::MintBondsToVault;
+pub use instructions::set_compliance_program::SetComplianceProgram;
+pub use instructions::set_withdraw_cooldown::SetWithdrawCooldown;
+pub use instructions::can_trade::CanTrade;
+pub use instructions::set_event_verbosity::SetEventVerbosity;
+pub use instructions::set_trading_delegate::SetTradingDelegate;
+pub use instructions::revoke_trading_delegate::RevokeTradingDelegate;
+pub use instructions::set_max_slippage_bps::SetMaxSlippageBps;
+pub use instructions::init_daily_stats::InitDailyStats;
+pub use instructions::set_burn_unsold_at_maturity::SetBurnUnsoldAtMaturity;
+pub use instructions::burn_unsold::BurnUnsold;
+pub use instructions::set_min_initial_funding::SetMinInitialFunding;
+pub use instructions::emergency_exit::EmergencyExit;
+pub use instructions::set_emergency_exit_enabled::SetEmergencyExitEnabled;
+pub use instructions::validate_config::ValidateConfig;
+pub use instructions::set_notify_program::SetNotifyProgram;
+pub use instructions::set_max_markets_per_admin::SetMaxMarketsPerAdmin;
+pub use instructions::migrate_quote_mint::MigrateQuoteMint;
+#[cfg(feature = "devnet-faucet")]
+pub use instructions::faucet_bonds::FaucetBonds;
+pub use instructions::set_max_notional::SetMaxNotional;
+pub use instructions::set_approved_payee::SetApprovedPayee;
+pub use instructions::requote_and_buy::RequoteAndBuy;
+pub use instructions::requote_and_sell::RequoteAndSell;
+pub use instructions::update_prices_batch::UpdatePricesBatch;
+pub use instructions::set_max_cumulative_withdraw::SetMaxCumulativeWithdraw;
+pub use instructions::authorize_relayer::AuthorizeRelayer;
+pub use instructions::revoke_relayer::RevokeRelayer;
+pub use instructions::required_backing::RequiredBacking;
+pub use instructions::migrate_market::MigrateMarket;
+pub use instructions::open_investor_position::OpenInvestorPosition;
+pub use instructions::set_trade_cooldown::SetTradeCooldown;
+pub use instructions::withdraw_insurance::WithdrawInsurance;
+pub use instructions::set_price_guard::SetPriceGuard;
+pub use instructions::set_wash_check::SetWashCheck;
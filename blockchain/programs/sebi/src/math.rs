@@ -0,0 +1,233 @@
+use anchor_lang::prelude::*;
+use crate::errors::MarketError;
+
+pub fn u128_to_u64(x: u128) -> Result<u64> {
+    u64::try_from(x).map_err(|_| error!(MarketError::MathOverflow))
+}
+
+pub fn u128_to_i128(x: u128) -> Result<i128> {
+    i128::try_from(x).map_err(|_| error!(MarketError::MathOverflow))
+}
+
+pub fn u64_to_i64(x: u64) -> Result<i64> {
+    i64::try_from(x).map_err(|_| error!(MarketError::MathOverflow))
+}
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum Rounding {
+    Down,
+    Up,
+}
+
+pub fn pow10(exp: u32) -> Result<u128> {
+    10u128.checked_pow(exp).ok_or(error!(MarketError::MathOverflow))
+}
+
+/// a * b / d with a full 256-bit intermediate product, so the multiplication
+/// itself never overflows; only a quotient above u128::MAX is an error.
+pub fn mul_div(a: u128, b: u128, d: u128, rounding: Rounding) -> Result<u128> {
+    if d == 0 {
+        return err!(MarketError::MathOverflow);
+    }
+    let (hi, lo) = widening_mul(a, b);
+    if hi >= d {
+        return err!(MarketError::MathOverflow);
+    }
+
+    let (quotient, remainder) = if hi == 0 {
+        (lo / d, lo % d)
+    } else {
+        // restoring long division of hi:lo by d; hi < d keeps the quotient within u128
+        let mut rem = hi;
+        let mut quotient: u128 = 0;
+        for i in (0..128).rev() {
+            let carry = rem >> 127;
+            rem = (rem << 1) | ((lo >> i) & 1);
+            quotient <<= 1;
+            if carry == 1 || rem >= d {
+                rem = rem.wrapping_sub(d);
+                quotient |= 1;
+            }
+        }
+        (quotient, rem)
+    };
+
+    if rounding == Rounding::Up && remainder > 0 {
+        quotient.checked_add(1).ok_or(error!(MarketError::MathOverflow))
+    } else {
+        Ok(quotient)
+    }
+}
+
+/// Returns (high, low) halves of the 256-bit product a * b.
+fn widening_mul(a: u128, b: u128) -> (u128, u128) {
+    const MASK: u128 = u64::MAX as u128;
+    let (a1, a0) = (a >> 64, a & MASK);
+    let (b1, b0) = (b >> 64, b & MASK);
+
+    let p00 = a0 * b0;
+    let p01 = a0 * b1;
+    let p10 = a1 * b0;
+    let p11 = a1 * b1;
+
+    // at most 3 * (2^64 - 1), so this cannot overflow
+    let mid = (p00 >> 64) + (p01 & MASK) + (p10 & MASK);
+    let lo = (p00 & MASK) | (mid << 64);
+    let hi = p11 + (p01 >> 64) + (p10 >> 64) + (mid >> 64);
+    (hi, lo)
+}
+
+/// Quote-token value of `amount` bonds: amount * price / denominator, where
+/// the denominator is 10^price_scale times the lot the price is quoted for.
+pub fn quote_amount(amount: u64, price: u128, denominator: u128, rounding: Rounding) -> Result<u64> {
+    let total = mul_div(amount as u128, price, denominator, rounding)?;
+    u128_to_u64(total)
+}
+
+/// Linear interpolation from start_price at start_ts to end_price at end_ts,
+/// clamped to the endpoints outside that window. The distance travelled is
+/// rounded down, so a decaying price never undershoots the schedule.
+pub fn interpolate_price(
+    start_price: u128,
+    end_price: u128,
+    start_ts: i64,
+    end_ts: i64,
+    now: i64,
+) -> Result<u128> {
+    if now <= start_ts {
+        return Ok(start_price);
+    }
+    if now >= end_ts {
+        return Ok(end_price);
+    }
+    let elapsed = (now - start_ts) as u128;
+    let duration = (end_ts - start_ts) as u128;
+    if end_price < start_price {
+        let drop = mul_div(start_price - end_price, elapsed, duration, Rounding::Down)?;
+        Ok(start_price - drop)
+    } else {
+        let rise = mul_div(end_price - start_price, elapsed, duration, Rounding::Down)?;
+        Ok(start_price + rise)
+    }
+}
+
+/// Whole quote tokens per whole bond, as a fixed-point integer with
+/// `precision` decimals, rounded down:
+/// price * 10^(bond_decimals + precision) / 10^(price_scale + quote_decimals)
+pub fn display_price(
+    price: u128,
+    price_scale: u32,
+    bond_decimals: u8,
+    quote_decimals: u8,
+    precision: u32,
+) -> Result<u64> {
+    let up = bond_decimals as u32 + precision;
+    let down = price_scale + quote_decimals as u32;
+    let value = if up >= down {
+        price.checked_mul(pow10(up - down)?).ok_or(error!(MarketError::MathOverflow))?
+    } else {
+        // a divisor beyond 10^38 exceeds any u128 price
+        pow10(down - up).map_or(0, |d| price / d)
+    };
+    u128_to_u64(value)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn is_overflow(err: Error) -> bool {
+        matches!(err, Error::AnchorError(e) if e.error_name == "MathOverflow")
+    }
+
+    #[test]
+    fn u128_to_u64_at_boundary() {
+        assert_eq!(u128_to_u64(0).unwrap(), 0);
+        assert_eq!(u128_to_u64(u64::MAX as u128).unwrap(), u64::MAX);
+        assert!(is_overflow(u128_to_u64(u64::MAX as u128 + 1).unwrap_err()));
+        assert!(is_overflow(u128_to_u64(u128::MAX).unwrap_err()));
+    }
+
+    #[test]
+    fn mul_div_small_values() {
+        assert_eq!(mul_div(6, 7, 4, Rounding::Down).unwrap(), 10);
+        assert_eq!(mul_div(6, 7, 4, Rounding::Up).unwrap(), 11);
+        assert_eq!(mul_div(8, 5, 4, Rounding::Up).unwrap(), 10);
+        assert_eq!(mul_div(0, u128::MAX, 1, Rounding::Up).unwrap(), 0);
+        assert!(is_overflow(mul_div(1, 1, 0, Rounding::Down).unwrap_err()));
+    }
+
+    #[test]
+    fn mul_div_wide_intermediate() {
+        // product exceeds u128 but the quotient fits
+        assert_eq!(mul_div(u128::MAX, u128::MAX, u128::MAX, Rounding::Down).unwrap(), u128::MAX);
+        assert_eq!(mul_div(u128::MAX, 1 << 64, 1 << 64, Rounding::Down).unwrap(), u128::MAX);
+        assert_eq!(mul_div(u128::MAX, 3, 6, Rounding::Down).unwrap(), u128::MAX / 2);
+        assert_eq!(mul_div(u128::MAX, 3, 6, Rounding::Up).unwrap(), u128::MAX / 2 + 1);
+        assert_eq!(mul_div(1 << 100, 1 << 100, 1 << 90, Rounding::Down).unwrap(), 1 << 110);
+    }
+
+    #[test]
+    fn mul_div_quotient_overflow() {
+        assert!(is_overflow(mul_div(u128::MAX, 2, 1, Rounding::Down).unwrap_err()));
+        assert!(is_overflow(mul_div(u128::MAX, u128::MAX, u128::MAX - 1, Rounding::Down).unwrap_err()));
+    }
+
+    #[test]
+    fn quote_amount_across_scales() {
+        // scale 0 keeps the legacy amount * price semantics
+        assert_eq!(quote_amount(2, 1_000_000, pow10(0).unwrap(), Rounding::Down).unwrap(), 2_000_000);
+        // 1.5 USDC (6 decimals) per bond expressed at scale 6
+        assert_eq!(quote_amount(3, 1_500_000_000_000, pow10(6).unwrap(), Rounding::Down).unwrap(), 4_500_000);
+        // sub-unit price per base unit: 0.25 at scale 2
+        assert_eq!(quote_amount(3, 25, pow10(2).unwrap(), Rounding::Down).unwrap(), 0);
+        assert_eq!(quote_amount(3, 25, pow10(2).unwrap(), Rounding::Up).unwrap(), 1);
+        assert_eq!(quote_amount(1_000, 25, pow10(2).unwrap(), Rounding::Down).unwrap(), 250);
+        // per-lot pricing: 1000 USDC per whole 6-decimal bond buys the same as 0.001 USDC per base unit
+        let lot = 1_000_000u128;
+        assert_eq!(quote_amount(2_500_000, 1_000_000_000, lot, Rounding::Down).unwrap(), 2_500_000_000);
+        assert_eq!(quote_amount(2_500_000, 1_000, 1, Rounding::Down).unwrap(), 2_500_000_000);
+        assert_eq!(quote_amount(1, 1_000_000_000, lot, Rounding::Down).unwrap(), 1_000);
+        assert_eq!(quote_amount(1, 999_999, lot, Rounding::Up).unwrap(), 1);
+        // 18-decimal fixed point price with a large amount
+        let price = 1_234_567_890_000_000_000u128; // 1.23456789
+        assert_eq!(quote_amount(1_000_000_000_000_000, price, pow10(18).unwrap(), Rounding::Down).unwrap(), 1_234_567_890_000_000);
+        assert!(is_overflow(quote_amount(u64::MAX, price, pow10(18).unwrap(), Rounding::Down).unwrap_err()));
+        assert_eq!(quote_amount(u64::MAX, price, pow10(38).unwrap(), Rounding::Up).unwrap(), 1);
+    }
+
+    #[test]
+    fn quote_amount_rejects_oversized_results() {
+        assert!(is_overflow(quote_amount(u64::MAX, 2, pow10(0).unwrap(), Rounding::Down).unwrap_err()));
+        assert!(is_overflow(pow10(39).unwrap_err()));
+    }
+
+    #[test]
+    fn display_price_normalizes_decimals() {
+        // 1 USDC (6 decimals) per 0-decimal bond
+        assert_eq!(display_price(1_000_000, 0, 0, 6, 6).unwrap(), 1_000_000);
+        // 1.5 USDC per whole 6-decimal bond, priced per base unit at scale 6
+        assert_eq!(display_price(1_500_000, 6, 6, 6, 6).unwrap(), 1_500_000);
+        // sub-precision prices truncate to 0
+        assert_eq!(display_price(25, 2, 0, 6, 6).unwrap(), 0);
+        assert_eq!(display_price(u128::MAX, 38, 0, 18, 6).unwrap(), 0);
+        assert!(is_overflow(display_price(u128::MAX, 0, 0, 0, 6).unwrap_err()));
+    }
+
+    #[test]
+    fn interpolate_price_samples_schedule() {
+        let at = |now| interpolate_price(1_000, 500, 100, 200, now).unwrap();
+        assert_eq!(at(0), 1_000);
+        assert_eq!(at(100), 1_000);
+        assert_eq!(at(125), 875);
+        assert_eq!(at(150), 750);
+        assert_eq!(at(199), 505);
+        assert_eq!(at(200), 500);
+        assert_eq!(at(10_000), 500);
+        // partial steps stay on the high side of a decay
+        assert_eq!(interpolate_price(10, 0, 0, 3, 1).unwrap(), 7);
+        // rising schedules work too
+        assert_eq!(interpolate_price(500, 1_000, 100, 200, 150).unwrap(), 750);
+        assert_eq!(interpolate_price(7, 7, 0, 10, 5).unwrap(), 7);
+    }
+}
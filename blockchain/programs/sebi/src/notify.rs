@@ -0,0 +1,91 @@
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::hash::hash;
+use anchor_lang::solana_program::instruction::Instruction;
+use anchor_lang::solana_program::program::{get_return_data, invoke};
+use crate::state::Market;
+use crate::errors::MarketError;
+use crate::compliance::is_approval;
+use crate::events::TradeSide;
+
+/// Arguments of the post-trade callback a market's notify_program must
+/// expose. In Anchor terms it is `on_trade(market, trader, side, amount,
+/// price, quote_amount)` with accounts [market], read-only: instruction data
+/// is sha256("global:on_trade")[..8] followed by borsh of this struct.
+///
+/// The call runs after the trade has settled. When notify_required is set
+/// the callee must be passed and set return data to borsh `true`; otherwise
+/// its return data is ignored and the call is skipped if the account is
+/// omitted. A callee that errors aborts the transaction either way, since
+/// Solana has no way to catch a failed CPI.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, PartialEq, Eq, Debug)]
+pub struct TradeNotification {
+    pub market: Pubkey,
+    pub trader: Pubkey,
+    pub side: TradeSide,
+    pub amount: u64,
+    pub price: u128,
+    /// Quote base units paid or received
+    pub quote_amount: u64,
+}
+
+pub fn instruction_data(notification: &TradeNotification) -> Result<Vec<u8>> {
+    let mut data = hash(b"global:on_trade").to_bytes()[..8].to_vec();
+    notification.serialize(&mut data)?;
+    Ok(data)
+}
+
+/// Sends `notification` to the market's notify_program, if it has one.
+/// Re-entry is ruled out twice over: the runtime rejects a callee calling
+/// back into this program, and set_notify_program refuses this program's
+/// own id, the one recursion the runtime allows.
+pub fn notify_trade<'info>(
+    program: Option<&UncheckedAccount<'info>>,
+    market: &Account<'info, Market>,
+    notification: &TradeNotification,
+) -> Result<()> {
+    if market.notify_program == Pubkey::default() {
+        return Ok(());
+    }
+    let Some(program) = program.filter(|p| p.key() == market.notify_program) else {
+        if market.notify_required {
+            return err!(MarketError::NotifyFailed);
+        }
+        return Ok(());
+    };
+
+    // flush the settled trade so the callee reads the market as it now stands
+    market.exit(&crate::ID)?;
+    let ix = Instruction {
+        program_id: program.key(),
+        accounts: vec![AccountMeta::new_readonly(market.key(), false)],
+        data: instruction_data(notification)?,
+    };
+    invoke(&ix, &[market.to_account_info(), program.to_account_info()])?;
+
+    if market.notify_required && !is_approval(program.key, get_return_data()) {
+        return err!(MarketError::NotifyFailed);
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn instruction_data_is_anchor_sighash_then_borsh() {
+        let notification = TradeNotification {
+            market: Pubkey::new_unique(),
+            trader: Pubkey::new_unique(),
+            side: TradeSide::Buy,
+            amount: 3,
+            price: 5,
+            quote_amount: 15,
+        };
+        let data = instruction_data(&notification).unwrap();
+        assert_eq!(&data[..8], &hash(b"global:on_trade").to_bytes()[..8]);
+        // market, trader pubkeys = 32*2, side u8 = 1, amount u64 = 8, price u128 = 16, quote_amount u64 = 8
+        assert_eq!(data.len(), 8 + 64 + 1 + 8 + 16 + 8);
+        assert_eq!(TradeNotification::try_from_slice(&data[8..]).unwrap(), notification);
+    }
+}
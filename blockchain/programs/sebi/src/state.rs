@@ -1,7 +1,16 @@
 use anchor_lang::prelude::*;
+use crate::errors::MarketError;
+use crate::constants::{BPS_DENOMINATOR, DISPLAY_PRICE_DECIMALS};
+use crate::events::{OhlcEvent, TradeSide};
+use crate::math::{display_price, interpolate_price, mul_div, pow10, quote_amount, u128_to_i128, u128_to_u64, Rounding};
 
 #[account]
 pub struct Market {
+    /// STATUS_* bits summarising paused, terminated and phase; first so it
+    /// sits at STATUS_OFFSET for getProgramAccounts memcmp filters
+    pub status: u8,
+    /// Layout version, at VERSION_OFFSET; migrate_market brings older accounts to VERSION
+    pub version: u8,
     pub bond_mint: Pubkey,
     pub usdc_mint: Pubkey,
     pub price_per_token: u128,
@@ -10,10 +19,1336 @@ pub struct Market {
     pub admin: Pubkey,
     pub paused: bool,
     pub bump: u8,
+    /// USDC received from buys minus USDC paid out for sells
+    pub net_quote_flow: i128,
+    /// Bonds sold out of the vault minus bonds bought back
+    pub net_bonds_out: i128,
+    /// Spread profit: net_quote_flow less net_bonds_out valued at the current price
+    pub realized_pnl: i128,
+    /// When `paused` is set, trading halts from this timestamp onward
+    pub pause_effective_ts: i64,
+    /// USDC token account backstopping sells; default until init_insurance_vault
+    pub insurance_vault: Pubkey,
+    /// USDC credited to the insurance fund and not yet drawn
+    pub insurance_balance: u64,
+    /// Prices must be a multiple of this; 0 or 1 disables the check
+    pub price_tick: u128,
+    /// Structural config, unlike `paused`: false for issue-only markets that never buy back
+    pub sell_enabled: bool,
+    pub phase: MarketPhase,
+    /// Buying halts once vault_bond falls below this; 0 disables
+    pub low_inventory_threshold: u64,
+    pub low_inventory_paused: bool,
+    /// price_per_token is fixed point: quote base units per bond base unit, times 10^price_scale
+    pub price_scale: u32,
+    /// One-way commitment that price_per_token never changes again
+    pub price_frozen: bool,
+    /// Per-transaction ceiling on buy/sell amount; 0 means unlimited
+    pub max_trade_amount: u64,
+    /// Decimals of bond_mint and usdc_mint, captured at initialization
+    pub bond_decimals: u8,
+    pub usdc_decimals: u8,
+    /// Whole USDC per whole bond with DISPLAY_PRICE_DECIMALS decimals; refreshed on every price change
+    pub display_price: u64,
+    /// When set, buys and sells price at auction.price_at(now) instead of a
+    /// price the admin sets; update_price is rejected until the auction ends
+    pub auction_enabled: bool,
+    pub auction: AuctionConfig,
+    /// bond_decimals/usdc_decimals are authoritative; false only on markets
+    /// that predate them, which may call set_decimals once
+    pub decimals_set: bool,
+    /// Irreversible kill switch: only withdraw, redemption and rescue_tokens still run
+    pub terminated: bool,
+    /// Key whose Ed25519-signed PriceAttestation buy/sell accept in place of price_per_token
+    pub price_updater: Pubkey,
+    /// Owner of vault_bond and vault_usdc: the market PDA until rotate_vault_authority
+    pub vault_authority: Pubkey,
+    /// May pause and unpause, nothing else; default when unset
+    pub circuit_breaker_authority: Pubkey,
+    /// A redemption window is open or has unpaid claims: phase changes wait, and USDC
+    /// withdrawals must leave total_pending_redemption in the vault
+    pub redemption_pending: bool,
+    /// Bonds minted into vault_bond by mint_bonds_to_vault; pre-funded supply is not counted
+    pub total_issued: u64,
+    /// Program CPI'd to approve every buy and sell; default when unset
+    pub compliance_program: Pubkey,
+    /// Minimum seconds between withdrawals; 0 disables
+    pub withdraw_cooldown: u64,
+    pub last_withdraw_ts: i64,
+    pub event_verbosity: EventVerbosity,
+    /// What price_per_token buys; see PriceBasis
+    pub price_basis: PriceBasis,
+    /// Bond base units per lot; only read under PriceBasis::PerLot
+    pub lot_size: u64,
+    /// Largest move from last_trade_price, in bps, a trade may execute at; 0 disables
+    pub max_slippage_bps: u16,
+    /// Unit price of the most recent buy or sell; 0 before the first
+    pub last_trade_price: u128,
+    /// Lets burn_unsold retire vault_bond once the market has matured
+    pub burn_unsold_at_maturity: bool,
+    /// vault_usdc balance sells wait for; 0 opens selling immediately
+    pub min_initial_funding: u64,
+    /// Latched once vault_usdc first reaches min_initial_funding
+    pub funded: bool,
+    /// Lets holders exit through emergency_exit, even while paused
+    pub emergency_exit_enabled: bool,
+    /// Program called after every trade; Pubkey::default() for none. See notify::TradeNotification
+    pub notify_program: Pubkey,
+    /// Whether trades fail unless notify_program is passed and acknowledges
+    pub notify_required: bool,
+    /// Largest quote amount one buy or sell may settle for; 0 disables the cap
+    pub max_notional: u64,
+    /// Non-admin owner withdraw may pay out to, once payee_effective_ts passes
+    pub approved_payee: Pubkey,
+    pub payee_effective_ts: i64,
+    /// Quote owed to redemption claims not yet settled; withdraw must leave it in vault_usdc
+    pub total_pending_redemption: u64,
+    /// Lifetime cap on USDC taken out through withdraw; 0 is unlimited
+    pub max_cumulative_withdraw: u64,
+    pub cumulative_withdrawn: u64,
+    /// Seconds a trader must wait between trades on this market; 0 disables
+    pub trade_cooldown_secs: u64,
+    /// Largest move from price_per_token, in bps, one price update may make; 0 disables
+    pub max_price_change_bps: u16,
+    /// Minimum seconds between price updates; 0 disables
+    pub price_update_cooldown_secs: u64,
+    /// When update_price or a requote last set the price; 0 before the first
+    pub last_price_update_ts: i64,
+    /// Window, in seconds, over which a trader's side flips are counted; 0 disables
+    pub wash_window_secs: u64,
+    /// Side flips a trader may make within wash_window_secs; 0 disables
+    pub wash_flip_threshold: u16,
+}
+
+/// Dutch auction schedule: linear from start_price to end_price over
+/// [start_ts, end_ts], held at the endpoints outside it
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, Debug, Default)]
+pub struct AuctionConfig {
+    pub start_price: u128,
+    pub end_price: u128,
+    pub start_ts: i64,
+    pub end_ts: i64,
+}
+
+impl AuctionConfig {
+    // start_price, end_price u128 = 16*2, start_ts, end_ts i64 = 8*2
+    pub const LEN: usize = (16 * 2) + (8 * 2);
+
+    pub fn price_at(&self, now: i64) -> Result<u128> {
+        interpolate_price(self.start_price, self.end_price, self.start_ts, self.end_ts, now)
+    }
+}
+
+/// Unit price_per_token is quoted for. Every quote amount is
+/// amount * price_per_token / (10^price_scale * lot), rounded per side,
+/// where lot is 1 for PerBaseUnit and market.lot_size for PerLot. Amounts
+/// and lot_size are in bond base units, so a 6-decimal bond priced per
+/// whole bond uses PerLot with lot_size 1_000_000.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, Debug)]
+pub enum PriceBasis {
+    PerBaseUnit,
+    PerLot,
+}
+
+/// How much a market logs per trade; state-change events are always emitted
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, Debug)]
+pub enum EventVerbosity {
+    /// No trade events
+    Off,
+    /// TradeSummaryEvent: side, amount and price only
+    Minimal,
+    /// TradeEvent with every field
+    Full,
+}
+
+/// Lifecycle stage of the bond. `paused` still applies on top of the phase.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, Debug)]
+pub enum MarketPhase {
+    /// Conditional trading before issuance: buys only
+    WhenIssued,
+    /// Full two-sided trading
+    Active,
+    /// Past maturity: no trading, funds leave through withdraw
+    Matured,
+    /// Terminal: no trading and no further phase changes
+    Closed,
+}
+
+impl MarketPhase {
+    pub fn allows_buy(&self) -> bool {
+        matches!(self, MarketPhase::WhenIssued | MarketPhase::Active)
+    }
+
+    pub fn allows_sell(&self) -> bool {
+        matches!(self, MarketPhase::Active)
+    }
 }
 
 impl Market {
     // 8 discriminator + fields:
+    // status u8 = 1, version u8 = 1
     // 32*5 pubkeys = 160, price u128 = 16, paused u8 =1, bump u8 =1
+    // net_quote_flow, net_bonds_out, realized_pnl i128 = 16*3, pause_effective_ts i64 = 8
+    // insurance_vault pubkey = 32, insurance_balance u64 = 8, price_tick u128 = 16
+    // sell_enabled u8 = 1, phase u8 = 1, low_inventory_threshold u64 = 8, low_inventory_paused u8 = 1
+    // price_scale u32 = 4, price_frozen u8 = 1, max_trade_amount u64 = 8
+    // bond_decimals u8 = 1, usdc_decimals u8 = 1, display_price u64 = 8
+    // auction_enabled u8 = 1, auction = AuctionConfig::LEN, decimals_set u8 = 1, terminated u8 = 1
+    // price_updater pubkey = 32, vault_authority pubkey = 32, circuit_breaker_authority pubkey = 32
+    // redemption_pending u8 = 1, total_issued u64 = 8, compliance_program pubkey = 32
+    // withdraw_cooldown u64 = 8, last_withdraw_ts i64 = 8
+    // event_verbosity u8 = 1, price_basis u8 = 1, lot_size u64 = 8
+    // max_slippage_bps u16 = 2, last_trade_price u128 = 16
+    // burn_unsold_at_maturity u8 = 1
+    // min_initial_funding u64 = 8, funded u8 = 1
+    // emergency_exit_enabled u8 = 1
+    // notify_program pubkey = 32, notify_required u8 = 1
+    // max_notional u64 = 8
+    // approved_payee pubkey = 32, payee_effective_ts i64 = 8
+    // total_pending_redemption u64 = 8
+    // max_cumulative_withdraw, cumulative_withdrawn u64 = 8*2
+    // trade_cooldown_secs u64 = 8
+    // max_price_change_bps u16 = 2, price_update_cooldown_secs u64 = 8, last_price_update_ts i64 = 8
+    // wash_window_secs u64 = 8, wash_flip_threshold u16 = 2
+    pub const LEN: usize = 8 + 1 + 1 + (32 * 5) + 16 + 1 + 1 + (16 * 3) + 8 + 32 + 8 + 16 + 1 + 1 + 8 + 1 + 4 + 1 + 8 + 1 + 1 + 8
+        + 1 + AuctionConfig::LEN + 1 + 1 + 32 + 32 + 32 + 1 + 8 + 32 + 8 + 8 + 1 + 1 + 8 + 2 + 16 + 1 + 8 + 1 + 1 + 32 + 1 + 8 + 32 + 8 + 8 + (8 * 2) + 8 + 2 + 8 + 8 + 8 + 2;
+
+    /// Byte offset of `status`, just past the discriminator
+    pub const STATUS_OFFSET: usize = 8;
+    /// Byte offset of `version`, right after `status`
+    pub const VERSION_OFFSET: usize = 9;
+    /// Current layout. Version 1 put status and version first and holds every
+    /// field up to max_cumulative_withdraw; later versions only append fields
+    /// whose zero value means "off", so migrate_market can zero-extend.
+    /// Version 2 appends trade_cooldown_secs; version 3 the price update
+    /// guard, max_price_change_bps through last_price_update_ts; version 4
+    /// the wash-trade check, wash_window_secs and wash_flip_threshold.
+    pub const VERSION: u8 = 4;
+    /// `paused` is set, whether or not a grace period is still running
+    pub const STATUS_PAUSED: u8 = 1 << 0;
+    pub const STATUS_TERMINATED: u8 = 1 << 1;
+    /// Bits 2-3 hold the MarketPhase discriminant
+    pub const STATUS_PHASE_SHIFT: u8 = 2;
+
+    /// Recomputes `status`; called wherever paused, terminated or phase change
+    pub fn refresh_status(&mut self) {
+        let mut status = (self.phase as u8) << Self::STATUS_PHASE_SHIFT;
+        if self.paused {
+            status |= Self::STATUS_PAUSED;
+        }
+        if self.terminated {
+            status |= Self::STATUS_TERMINATED;
+        }
+        self.status = status;
+    }
+
+    /// Account body (after the discriminator) of a market at an older version
+    /// but current field order, zero-extended into the current layout
+    pub fn from_older_version(body: &[u8]) -> Result<Market> {
+        if body.len() >= Self::LEN - 8 {
+            return err!(MarketError::AlreadyMigrated);
+        }
+        let mut padded = body.to_vec();
+        padded.resize(Self::LEN - 8, 0);
+        let mut market = Market::try_from_slice(&padded).map_err(|_| MarketError::InvalidMarketAccount)?;
+        if market.version == 0 || market.version >= Self::VERSION {
+            return err!(MarketError::InvalidMarketAccount);
+        }
+        market.version = Self::VERSION;
+        Ok(market)
+    }
+
+    /// Seeds the market PDA signs CPIs with, matching the `seeds` account constraints
+    pub fn signer_seeds(&self) -> [&[u8]; 3] {
+        [b"market", self.bond_mint.as_ref(), std::slice::from_ref(&self.bump)]
+    }
+
+    pub fn is_halted(&self, now: i64) -> bool {
+        self.paused && now >= self.pause_effective_ts
+    }
+
+    pub fn is_pause_authority(&self, key: &Pubkey) -> bool {
+        *key == self.admin || (self.circuit_breaker_authority != Pubkey::default() && *key == self.circuit_breaker_authority)
+    }
+
+    pub fn in_withdraw_cooldown(&self, now: i64) -> bool {
+        let cooldown = self.withdraw_cooldown.min(i64::MAX as u64) as i64;
+        self.withdraw_cooldown > 0 && self.last_withdraw_ts > 0 && now < self.last_withdraw_ts.saturating_add(cooldown)
+    }
+
+    /// Whether withdraw may pay a token account owned by `owner`
+    pub fn is_approved_payee(&self, owner: &Pubkey, now: i64) -> bool {
+        *owner == self.admin
+            || (self.approved_payee != Pubkey::default() && *owner == self.approved_payee && now >= self.payee_effective_ts)
+    }
+
+    /// Bond withdrawals don't count: the cap is in quote units
+    pub fn exceeds_withdraw_cap(&self, amount: u64) -> Result<bool> {
+        let total = self.cumulative_withdrawn.checked_add(amount).ok_or(MarketError::MathOverflow)?;
+        Ok(self.max_cumulative_withdraw > 0 && total > self.max_cumulative_withdraw)
+    }
+
+    pub fn exceeds_max_trade(&self, amount: u64) -> bool {
+        self.max_trade_amount > 0 && amount > self.max_trade_amount
+    }
+
+    /// Caps trade value in the quote token, where max_trade_amount caps bond units
+    pub fn exceeds_max_notional(&self, quote: u64) -> bool {
+        self.max_notional > 0 && quote > self.max_notional
+    }
+
+    pub fn is_on_tick(&self, price: u128) -> bool {
+        self.price_tick <= 1 || price.checked_rem(self.price_tick) == Some(0)
+    }
+
+    /// Whether executing at `price` strays more than max_slippage_bps from the
+    /// last trade. A price update alone can trip it, so a large repricing
+    /// blocks trading until the admin widens or clears the bound.
+    pub fn exceeds_slippage(&self, price: u128) -> bool {
+        if self.max_slippage_bps == 0 || self.last_trade_price == 0 {
+            return false;
+        }
+        let deviation = price.abs_diff(self.last_trade_price);
+        deviation.saturating_mul(BPS_DENOMINATOR) > self.last_trade_price.saturating_mul(self.max_slippage_bps as u128)
+    }
+
+    /// Whether a price update to `new_price` at `now` respects the change
+    /// limit against price_per_token and the cooldown since the last update
+    pub fn check_price_update(&self, new_price: u128, now: i64) -> Result<()> {
+        let cooldown = self.price_update_cooldown_secs.min(i64::MAX as u64) as i64;
+        if cooldown > 0 && self.last_price_update_ts > 0 && now < self.last_price_update_ts.saturating_add(cooldown) {
+            return err!(MarketError::PriceUpdateCooldown);
+        }
+        if self.max_price_change_bps > 0 {
+            let change = new_price.abs_diff(self.price_per_token);
+            if change.saturating_mul(BPS_DENOMINATOR) > self.price_per_token.saturating_mul(self.max_price_change_bps as u128) {
+                return err!(MarketError::PriceChangeTooLarge);
+            }
+        }
+        Ok(())
+    }
+
+    /// Whether check_price_update bounds how far repeated updates can walk
+    /// the price: both a change limit and a cooldown are set
+    pub fn has_price_guard(&self) -> bool {
+        self.max_price_change_bps > 0 && self.price_update_cooldown_secs > 0
+    }
+
+    /// Whether trades count side flips, which needs both settings
+    pub fn has_wash_check(&self) -> bool {
+        self.wash_window_secs > 0 && self.wash_flip_threshold > 0
+    }
+
+    /// Whether buy and sell need the trader's InvestorPosition
+    pub fn requires_position(&self) -> bool {
+        self.trade_cooldown_secs > 0 || self.has_wash_check()
+    }
+
+    /// Whether a sell of `amount` may proceed at `now`; shared by sell and sell_batch
+    pub fn check_sell(&self, amount: u64, now: i64) -> Result<()> {
+        if self.is_halted(now) {
+            return err!(MarketError::MarketPaused);
+        }
+        if !self.phase.allows_sell() {
+            return err!(MarketError::InvalidPhase);
+        }
+        if !self.sell_enabled {
+            return err!(MarketError::SellDisabled);
+        }
+        if self.exceeds_max_trade(amount) {
+            return err!(MarketError::TradeTooLarge);
+        }
+        Ok(())
+    }
+
+    /// Whether sells are open: the vault has been funded once, or holds
+    /// min_initial_funding now. Later drawdowns don't close it again.
+    pub fn is_funded(&self, vault_usdc: u64) -> bool {
+        self.funded || vault_usdc >= self.min_initial_funding
+    }
+
+    /// USDC owed for `amount` bonds, rounded up in the market's favour
+    pub fn buy_cost(&self, amount: u64) -> Result<u64> {
+        self.buy_cost_at(amount, self.price_per_token)
+    }
+
+    pub fn buy_cost_at(&self, amount: u64, price: u128) -> Result<u64> {
+        quote_amount(amount, price, self.price_denominator()?, Rounding::Up)
+    }
+
+    /// USDC paid for `amount` bonds, rounded down in the market's favour
+    pub fn sell_proceeds(&self, amount: u64) -> Result<u64> {
+        self.sell_proceeds_at(amount, self.price_per_token)
+    }
+
+    pub fn sell_proceeds_at(&self, amount: u64, price: u128) -> Result<u64> {
+        quote_amount(amount, price, self.price_denominator()?, Rounding::Down)
+    }
+
+    /// Bond base units price_per_token is quoted for
+    pub fn lot(&self) -> u64 {
+        match self.price_basis {
+            PriceBasis::PerBaseUnit => 1,
+            PriceBasis::PerLot => self.lot_size,
+        }
+    }
+
+    /// 10^price_scale times the lot price_per_token is quoted for
+    pub fn price_denominator(&self) -> Result<u128> {
+        pow10(self.price_scale)?
+            .checked_mul(self.lot() as u128)
+            .ok_or(error!(MarketError::MathOverflow))
+    }
+
+    /// Value of `amount` bonds at the current price without the u64 bound, rounded down
+    pub fn inventory_value(&self, amount: u64) -> Result<u128> {
+        mul_div(amount as u128, self.price_per_token, self.price_denominator()?, Rounding::Down)
+    }
+
+    /// USDC needed to buy back every outstanding bond at the current price;
+    /// issue-only markets owe nothing. Terminated markets still do: their
+    /// holders exit through redemption, which this backing pays for.
+    pub fn backing_required(&self) -> Result<u128> {
+        if !self.sell_enabled {
+            return Ok(0);
+        }
+        let outstanding = self.net_bonds_out.max(0).unsigned_abs();
+        mul_div(outstanding, self.price_per_token, self.price_denominator()?, Rounding::Down)
+    }
+
+    /// Price emergency_exit pays for `amount` bonds: vault_usdc spread
+    /// evenly over the outstanding bonds, capped at price_per_token. The
+    /// rate is unchanged by each exit, so order doesn't matter. Bonds that
+    /// never passed through the market count toward outstanding here.
+    pub fn emergency_exit_rate(&self, amount: u64, vault_usdc: u64) -> Result<u128> {
+        let outstanding = self.net_bonds_out.max(amount as i128).unsigned_abs();
+        if outstanding == 0 {
+            return Ok(self.price_per_token);
+        }
+        let rate = mul_div(vault_usdc as u128, self.price_denominator()?, outstanding, Rounding::Down)?;
+        Ok(rate.min(self.price_per_token))
+    }
+
+    pub fn is_auction_running(&self, now: i64) -> bool {
+        self.auction_enabled && now < self.auction.end_ts
+    }
+
+    /// Move price_per_token to the auction schedule; no-op without an auction
+    pub fn sync_auction_price(&mut self, now: i64) -> Result<()> {
+        if !self.auction_enabled {
+            return Ok(());
+        }
+        self.price_per_token = self.auction.price_at(now)?;
+        self.refresh_display_price()
+    }
+
+    /// Recompute display_price; call after any change to price_per_token
+    pub fn refresh_display_price(&mut self) -> Result<()> {
+        self.display_price = display_price(
+            self.price_per_token,
+            self.price_scale,
+            self.bond_decimals,
+            self.usdc_decimals,
+            DISPLAY_PRICE_DECIMALS,
+        )?
+        .checked_div(self.lot())
+        .ok_or(MarketError::MathOverflow)?;
+        Ok(())
+    }
+
+    pub fn record_buy(&mut self, amount: u64, total_price: u64) -> Result<()> {
+        self.apply_trade(amount as i128, total_price as i128)
+    }
+
+    pub fn record_sell(&mut self, amount: u64, total_price: u64) -> Result<()> {
+        self.apply_trade(-(amount as i128), -(total_price as i128))
+    }
+
+    // Outstanding inventory is revalued at the current price on every trade, so a
+    // buy-then-sell round trip at the same price nets to zero and any price move
+    // between the legs shows up as profit or loss.
+    fn apply_trade(&mut self, bonds_delta: i128, quote_delta: i128) -> Result<()> {
+        self.net_bonds_out = self.net_bonds_out.checked_add(bonds_delta).ok_or(MarketError::MathOverflow)?;
+        self.net_quote_flow = self.net_quote_flow.checked_add(quote_delta).ok_or(MarketError::MathOverflow)?;
+
+        let magnitude = mul_div(
+            self.net_bonds_out.unsigned_abs(),
+            self.price_per_token,
+            self.price_denominator()?,
+            Rounding::Down,
+        )?;
+        let inventory_value = if self.net_bonds_out < 0 {
+            -u128_to_i128(magnitude)?
+        } else {
+            u128_to_i128(magnitude)?
+        };
+        self.realized_pnl = self.net_quote_flow.checked_sub(inventory_value).ok_or(MarketError::MathOverflow)?;
+        Ok(())
+    }
+}
+
+/// Singleton index of every market created by the program
+#[account]
+pub struct MarketRegistry {
+    pub market_count: u64,
+    pub bump: u8,
+}
+
+impl MarketRegistry {
+    // 8 discriminator + market_count u64 = 8, bump u8 = 1
+    pub const LEN: usize = 8 + 8 + 1;
+}
+
+/// One fixed-size page of market keys, seeded by its page index
+#[account]
+pub struct RegistryPage {
+    pub page: u64,
+    pub markets: Vec<Pubkey>,
+    pub bump: u8,
+}
+
+impl RegistryPage {
+    pub const MAX_MARKETS: usize = 32;
+    // 8 discriminator + page u64 = 8, vec prefix = 4, 32 pubkeys, bump u8 = 1
+    pub const LEN: usize = 8 + 8 + 4 + (32 * Self::MAX_MARKETS) + 1;
+}
+
+/// Program-wide settings, managed by the program's upgrade authority
+#[account]
+pub struct ProgramConfig {
+    pub authority: Pubkey,
+    /// Quote mints markets may be created against; empty allows any mint
+    pub approved_quote_mints: Vec<Pubkey>,
+    pub bump: u8,
+    /// Markets one admin key may create; 0 is unlimited
+    pub max_markets_per_admin: u64,
+}
+
+impl ProgramConfig {
+    pub const MAX_QUOTE_MINTS: usize = 10;
+    // 8 discriminator + authority pubkey = 32, vec prefix = 4, 10 pubkeys, bump u8 = 1
+    // max_markets_per_admin u64 = 8
+    pub const LEN: usize = 8 + 32 + 4 + (32 * Self::MAX_QUOTE_MINTS) + 1 + 8;
+
+    pub fn is_quote_mint_approved(&self, mint: &Pubkey) -> bool {
+        self.approved_quote_mints.is_empty() || self.approved_quote_mints.contains(mint)
+    }
+}
+
+/// Markets created by one admin key, checked against
+/// ProgramConfig::max_markets_per_admin
+#[account]
+pub struct AdminMarketCount {
+    pub admin: Pubkey,
+    pub count: u64,
+    pub bump: u8,
+}
+
+impl AdminMarketCount {
+    // 8 discriminator + admin pubkey = 32, count u64 = 8, bump u8 = 1
+    pub const LEN: usize = 8 + 32 + 8 + 1;
+}
+
+/// Bonds one wallet drew from a market's devnet faucet on `day`
+#[cfg(feature = "devnet-faucet")]
+#[account]
+pub struct FaucetClaim {
+    pub market: Pubkey,
+    pub wallet: Pubkey,
+    /// Unix day (ts / 86_400) `claimed` counts toward
+    pub day: i64,
+    pub claimed: u64,
+    pub bump: u8,
+}
+
+#[cfg(feature = "devnet-faucet")]
+impl FaucetClaim {
+    // discriminator = 8, market/wallet pubkey = 32 * 2, day i64 = 8, claimed u64 = 8, bump u8 = 1
+    pub const LEN: usize = 8 + (32 * 2) + 8 + 8 + 1;
+
+    /// Adds `amount` to the wallet's draw for the day containing `now`,
+    /// starting over on a new day
+    pub fn claim(&mut self, amount: u64, now: i64) -> Result<()> {
+        let day = now.div_euclid(DailyStats::SECONDS_PER_DAY);
+        if day != self.day {
+            self.day = day;
+            self.claimed = 0;
+        }
+        let claimed = self.claimed.checked_add(amount).ok_or(MarketError::MathOverflow)?;
+        if claimed > crate::constants::FAUCET_BONDS_PER_DAY {
+            return err!(MarketError::FaucetLimitReached);
+        }
+        self.claimed = claimed;
+        Ok(())
+    }
+}
+
+/// Cross-market totals for dashboards. Markets and trades only count while
+/// the caller passes this account, so the totals are a lower bound.
+#[account]
+pub struct ProtocolStats {
+    pub markets_created: u64,
+    pub trade_count: u64,
+    /// Quote base units paid or received across every market; only
+    /// comparable while all quote mints share the same decimals
+    pub total_volume: u128,
+    pub bump: u8,
+}
+
+impl ProtocolStats {
+    // 8 discriminator + markets_created u64 = 8, trade_count u64 = 8, total_volume u128 = 16, bump u8 = 1
+    pub const LEN: usize = 8 + 8 + 8 + 16 + 1;
+
+    pub fn record_trade(&mut self, quote_amount: u64) -> Result<()> {
+        self.trade_count = self.trade_count.checked_add(1).ok_or(MarketError::MathOverflow)?;
+        self.total_volume = self
+            .total_volume
+            .checked_add(quote_amount as u128)
+            .ok_or(MarketError::MathOverflow)?;
+        Ok(())
+    }
+}
+
+/// Open/high/low/close of one market's trades for the current UTC day.
+/// The first trade of a later day emits the finished day as an OhlcEvent
+/// and starts over; days without trades produce no candle.
+#[account]
+pub struct DailyStats {
+    pub market: Pubkey,
+    /// Unix day (ts / 86_400) the accumulators cover
+    pub day: i64,
+    pub open: u128,
+    pub high: u128,
+    pub low: u128,
+    pub close: u128,
+    /// Quote base units traded during the day
+    pub volume: u64,
+    pub trade_count: u64,
+    pub bump: u8,
+}
+
+impl DailyStats {
+    // discriminator = 8, market pubkey = 32, day i64 = 8
+    // open/high/low/close u128 = 16 * 4, volume u64 = 8, trade_count u64 = 8, bump u8 = 1
+    pub const LEN: usize = 8 + 32 + 8 + (16 * 4) + 8 + 8 + 1;
+
+    pub const SECONDS_PER_DAY: i64 = 86_400;
+
+    /// Folds a trade at `price` into the day containing `now`, returning the
+    /// previous day's candle when this trade is the first of a new day
+    pub fn record(&mut self, price: u128, quote_amount: u64, now: i64) -> Result<Option<OhlcEvent>> {
+        let day = now.div_euclid(Self::SECONDS_PER_DAY);
+        let mut closed = None;
+        if self.trade_count > 0 && day != self.day {
+            closed = Some(OhlcEvent {
+                market: self.market,
+                day: self.day,
+                open: self.open,
+                high: self.high,
+                low: self.low,
+                close: self.close,
+                volume: self.volume,
+                trade_count: self.trade_count,
+            });
+            self.trade_count = 0;
+            self.volume = 0;
+        }
+        if self.trade_count == 0 {
+            self.day = day;
+            self.open = price;
+            self.high = price;
+            self.low = price;
+        }
+        self.high = self.high.max(price);
+        self.low = self.low.min(price);
+        self.close = price;
+        self.volume = self.volume.checked_add(quote_amount).ok_or(MarketError::MathOverflow)?;
+        self.trade_count = self.trade_count.checked_add(1).ok_or(MarketError::MathOverflow)?;
+        Ok(closed)
+    }
+}
+
+/// Redemption of a matured market: holders register bonds until end_ts,
+/// then settle_redemptions pays every claim the same fraction of what it is owed
+#[account]
+pub struct RedemptionWindow {
+    pub market: Pubkey,
+    /// Quote paid per bond on full redemption, fixed point like price_per_token
+    pub redemption_price: u128,
+    pub end_ts: i64,
+    pub total_bonds: u64,
+    pub total_owed: u64,
+    pub claim_count: u64,
+    pub paid_count: u64,
+    /// vault_usdc shared among claims, capped at total_owed; fixed by the first settle
+    pub available: u64,
+    pub settled: bool,
+    pub bump: u8,
+}
+
+impl RedemptionWindow {
+    // 8 discriminator + market pubkey = 32, redemption_price u128 = 16, end_ts i64 = 8
+    // total_bonds, total_owed, claim_count, paid_count, available u64 = 8*5, settled u8 = 1, bump u8 = 1
+    pub const LEN: usize = 8 + 32 + 16 + 8 + (8 * 5) + 1 + 1;
+
+    /// `owed * available / total_owed`, rounded down; the full amount when covered
+    pub fn payout(&self, owed: u64) -> Result<u64> {
+        if self.total_owed == 0 {
+            return Ok(0);
+        }
+        u128_to_u64(mul_div(owed as u128, self.available as u128, self.total_owed as u128, Rounding::Down)?)
+    }
+}
+
+/// One holder's registered bonds in a market's redemption window
+#[account]
+pub struct RedemptionClaim {
+    pub market: Pubkey,
+    pub holder: Pubkey,
+    pub bonds: u64,
+    /// Quote owed at the redemption price before any pro-rata haircut
+    pub owed: u64,
+    pub paid: bool,
+    pub bump: u8,
+}
+
+impl RedemptionClaim {
+    // 8 discriminator + market, holder pubkeys = 32*2, bonds, owed u64 = 8*2, paid u8 = 1, bump u8 = 1
+    pub const LEN: usize = 8 + (32 * 2) + (8 * 2) + 1 + 1;
+}
+
+/// Last CAPACITY prices set on a market, for on-chain consumers that can't read events
+#[account]
+pub struct PriceHistory {
+    pub market: Pubkey,
+    /// Slot the next point is written to once the buffer is full
+    pub head: u32,
+    /// Ring storage; chronological order starts at `head` once full
+    pub points: Vec<PricePoint>,
+    pub bump: u8,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, Debug)]
+pub struct PricePoint {
+    pub price: u128,
+    pub ts: i64,
+}
+
+impl PricePoint {
+    // price u128 = 16, ts i64 = 8
+    pub const LEN: usize = 16 + 8;
+}
+
+impl PriceHistory {
+    pub const CAPACITY: usize = 32;
+    // 8 discriminator + market pubkey = 32, head u32 = 4, vec prefix = 4, 32 points, bump u8 = 1
+    pub const LEN: usize = 8 + 32 + 4 + 4 + (PricePoint::LEN * Self::CAPACITY) + 1;
+
+    pub fn record(&mut self, price: u128, ts: i64) {
+        let point = PricePoint { price, ts };
+        if self.points.len() < Self::CAPACITY {
+            self.points.push(point);
+        } else {
+            self.points[self.head as usize] = point;
+        }
+        self.head = ((self.head as usize + 1) % Self::CAPACITY) as u32;
+    }
+
+    /// Stored points, oldest first
+    pub fn chronological(&self) -> Vec<PricePoint> {
+        if self.points.len() < Self::CAPACITY {
+            return self.points.clone();
+        }
+        let (newer, older) = self.points.split_at(self.head as usize);
+        older.iter().chain(newer).copied().collect()
+    }
+
+    /// Time-weighted average over [now - window_secs, now], rounded down.
+    /// Each price holds until the next point, the newest until `now`. A window
+    /// reaching past the oldest point is clipped to it; with no elapsed time
+    /// the newest price is returned as is.
+    pub fn twap(&self, now: i64, window_secs: u64) -> Result<u128> {
+        let points = self.chronological();
+        let latest = points.last().ok_or(MarketError::EmptyPriceHistory)?;
+        let window_start = now.saturating_sub(window_secs.min(i64::MAX as u64) as i64);
+        let start = window_start.max(points[0].ts);
+        if now <= start {
+            return Ok(latest.price);
+        }
+
+        let mut weighted: u128 = 0;
+        for (i, point) in points.iter().enumerate() {
+            let seg_end = points.get(i + 1).map_or(now, |next| next.ts).min(now);
+            let seg_start = point.ts.max(start);
+            if seg_end > seg_start {
+                let term = point
+                    .price
+                    .checked_mul((seg_end - seg_start) as u128)
+                    .ok_or(MarketError::MathOverflow)?;
+                weighted = weighted.checked_add(term).ok_or(MarketError::MathOverflow)?;
+            }
+        }
+        Ok(weighted / (now - start) as u128)
+    }
+}
+
+/// Authorizes `delegate` to sign buy/sell for `owner` on one market. The
+/// owner's token accounts must also approve the delegate through SPL
+/// `approve`, which caps how much it can spend.
+#[account]
+pub struct TradingDelegate {
+    pub market: Pubkey,
+    pub owner: Pubkey,
+    pub delegate: Pubkey,
+    pub bump: u8,
+}
+
+impl TradingDelegate {
+    // discriminator = 8, market/owner/delegate pubkey = 32 * 3, bump u8 = 1
+    pub const LEN: usize = 8 + (32 * 3) + 1;
+}
+
+/// Per-trader record on one market, opened with open_investor_position or
+/// created by a buy given system_program. buy and sell stamp it whenever it
+/// is passed, and require it while the market has a trade cooldown or wash
+/// check. A fresh key gets a fresh position, so both deter churn from one
+/// account rather than sybils: an owner alternating sides across several
+/// wallets, or buying from one and selling from another, is never counted.
+#[account]
+pub struct InvestorPosition {
+    pub market: Pubkey,
+    pub owner: Pubkey,
+    /// Unix timestamp of the owner's last buy or sell; 0 before the first
+    pub last_trade_ts: i64,
+    pub bump: u8,
+    /// Side of the last trade; None before the first
+    pub last_side: Option<TradeSide>,
+    /// Side flips since flip_window_start_ts, while the market has a wash check
+    pub flip_count: u16,
+    /// When the first flip of the current wash window happened
+    pub flip_window_start_ts: i64,
+}
+
+impl InvestorPosition {
+    // discriminator = 8, market/owner pubkey = 32 * 2, last_trade_ts i64 = 8, bump u8 = 1
+    // last_side option<u8> = 2, flip_count u16 = 2, flip_window_start_ts i64 = 8
+    pub const LEN: usize = 8 + (32 * 2) + 8 + 1 + 2 + 2 + 8;
+
+    /// Whether `now` falls inside `cooldown_secs` of the last trade
+    pub fn in_cooldown(&self, cooldown_secs: u64, now: i64) -> bool {
+        let cooldown = cooldown_secs.min(i64::MAX as u64) as i64;
+        cooldown > 0 && self.last_trade_ts > 0 && now < self.last_trade_ts.saturating_add(cooldown)
+    }
+
+    /// Flip count and window start should the owner trade `side` at `now`.
+    /// A trade on the other side from the last one is a flip; the window
+    /// opens at the first flip and restarts once `window_secs` have passed.
+    pub fn flips_after(&self, side: &TradeSide, window_secs: u64, now: i64) -> (u16, i64) {
+        if self.last_side.is_none() || self.last_side.as_ref() == Some(side) {
+            return (self.flip_count, self.flip_window_start_ts);
+        }
+        let window = window_secs.min(i64::MAX as u64) as i64;
+        if self.flip_count == 0 || now >= self.flip_window_start_ts.saturating_add(window) {
+            return (1, now);
+        }
+        (self.flip_count.saturating_add(1), self.flip_window_start_ts)
+    }
+
+    /// Whether trading `side` at `now` takes the owner past the market's flip threshold
+    pub fn is_wash_suspected(&self, market: &Market, side: &TradeSide, now: i64) -> bool {
+        market.has_wash_check() && self.flips_after(side, market.wash_window_secs, now).0 > market.wash_flip_threshold
+    }
+
+    /// Stamps a `side` trade at `now`, failing inside the market's trade
+    /// cooldown of the last one or past its wash flip threshold
+    pub fn record_trade(&mut self, market: &Market, side: TradeSide, now: i64) -> Result<()> {
+        if self.in_cooldown(market.trade_cooldown_secs, now) {
+            return err!(MarketError::TradeCooldown);
+        }
+        if self.is_wash_suspected(market, &side, now) {
+            return err!(MarketError::WashTradingSuspected);
+        }
+        if market.has_wash_check() {
+            (self.flip_count, self.flip_window_start_ts) = self.flips_after(&side, market.wash_window_secs, now);
+        }
+        self.last_trade_ts = now;
+        self.last_side = Some(side);
+        Ok(())
+    }
+}
+
+/// Whose accounts a trade signed by `signer` settles against: the owner of
+/// whichever delegate or relayer record is passed, otherwise the signer.
+/// buy and sell reject passing both.
+pub fn trader_for(
+    delegate: &Option<Account<TradingDelegate>>,
+    relayer: &Option<Account<RelayerAuthorization>>,
+    signer: Pubkey,
+) -> Pubkey {
+    match (delegate, relayer) {
+        (Some(d), _) => d.owner,
+        (None, Some(r)) => r.owner,
+        (None, None) => signer,
+    }
+}
+
+/// An owner's standing permission for `relayer` to trade on one market for
+/// them, up to `max_notional` of quote in total and until `expiry`.
+/// Like a trading delegate, the relayer also needs an SPL `approve` on the
+/// owner's token accounts.
+#[account]
+pub struct RelayerAuthorization {
+    pub market: Pubkey,
+    pub owner: Pubkey,
+    pub relayer: Pubkey,
+    /// Total quote the owner granted, as configured in authorize_relayer
+    pub max_notional: u64,
+    /// What is left of max_notional; each buy or sell debits its quote amount
+    pub remaining_notional: u64,
+    /// Unix timestamp from which relayed trades are rejected
+    pub expiry: i64,
+    pub bump: u8,
+}
+
+impl RelayerAuthorization {
+    // discriminator = 8, market/owner/relayer pubkey = 32 * 3, max_notional/remaining_notional u64 = 8 * 2,
+    // expiry i64 = 8, bump u8 = 1
+    pub const LEN: usize = 8 + (32 * 3) + (8 * 2) + 8 + 1;
+
+    /// Debits `quote` from the allowance for a trade at `now`
+    pub fn spend(&mut self, quote: u64, now: i64) -> Result<()> {
+        if now >= self.expiry {
+            return err!(MarketError::RelayExpired);
+        }
+        self.remaining_notional = self.remaining_notional.checked_sub(quote).ok_or(MarketError::RelayLimitExceeded)?;
+        Ok(())
+    }
+}
+
+/// Market as first deployed, before status, version and every later field.
+/// Only migrate_market reads it.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug)]
+pub struct MarketV0 {
+    pub bond_mint: Pubkey,
+    pub usdc_mint: Pubkey,
+    pub price_per_token: u128,
+    pub vault_bond: Pubkey,
+    pub vault_usdc: Pubkey,
+    pub admin: Pubkey,
+    pub paused: bool,
+    pub bump: u8,
+}
+
+impl MarketV0 {
+    // 8 discriminator + 32*5 pubkeys = 160, price u128 = 16, paused u8 = 1, bump u8 = 1
     pub const LEN: usize = 8 + (32 * 5) + 16 + 1 + 1;
+
+    /// The same market in the current layout, with the settings a v0 market
+    /// implicitly had: price per base unit with no scale, sells on, Active.
+    /// Decimals stay unset for set_decimals. `outstanding` seeds
+    /// net_bonds_out, since v0 kept no trade counters.
+    pub fn upgrade(&self, key: Pubkey, outstanding: u64) -> Result<Market> {
+        let mut market = Market::try_from_slice(&[0u8; Market::LEN - 8]).map_err(|_| MarketError::InvalidMarketAccount)?;
+        market.version = Market::VERSION;
+        market.bond_mint = self.bond_mint;
+        market.usdc_mint = self.usdc_mint;
+        market.price_per_token = self.price_per_token;
+        market.vault_bond = self.vault_bond;
+        market.vault_usdc = self.vault_usdc;
+        market.admin = self.admin;
+        market.paused = self.paused;
+        market.bump = self.bump;
+        market.net_bonds_out = outstanding as i128;
+        market.price_updater = self.admin;
+        market.vault_authority = key;
+        market.sell_enabled = true;
+        market.phase = MarketPhase::Active;
+        market.event_verbosity = EventVerbosity::Full;
+        market.refresh_status();
+        Ok(market)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn signer_seeds_derive_the_market_pda() {
+        // an all-zero account body doubles as a check that LEN matches the layout
+        let mut market = Market::try_from_slice(&[0u8; Market::LEN - 8]).unwrap();
+        market.bond_mint = Pubkey::new_unique();
+        let (pda, bump) = Pubkey::find_program_address(&[b"market", market.bond_mint.as_ref()], &crate::ID);
+        market.bump = bump;
+        assert_eq!(Pubkey::create_program_address(&market.signer_seeds(), &crate::ID).unwrap(), pda);
+    }
+
+    fn history() -> PriceHistory {
+        PriceHistory { market: Pubkey::default(), head: 0, points: Vec::new(), bump: 0 }
+    }
+
+    #[test]
+    fn price_history_wraps_after_capacity() {
+        let mut h = history();
+        for i in 0..3 {
+            h.record(i, i as i64);
+        }
+        assert_eq!(h.chronological().iter().map(|p| p.price).collect::<Vec<_>>(), vec![0, 1, 2]);
+
+        let total = PriceHistory::CAPACITY as u128 + 5;
+        for i in 3..total {
+            h.record(i, i as i64);
+        }
+        let prices: Vec<u128> = h.chronological().iter().map(|p| p.price).collect();
+        assert_eq!(h.points.len(), PriceHistory::CAPACITY);
+        assert_eq!(prices, (5..total).collect::<Vec<_>>());
+        assert_eq!(h.head, 5);
+    }
+
+    #[test]
+    fn twap_weights_prices_by_duration() {
+        let mut h = history();
+        h.record(100, 0);
+        h.record(200, 10);
+        h.record(400, 30);
+
+        // 100 for 10s, 200 for 20s, 400 for 10s
+        assert_eq!(h.twap(40, 40).unwrap(), (100 * 10 + 200 * 20 + 400 * 10) / 40);
+        // window covering only part of the middle segment
+        assert_eq!(h.twap(40, 15).unwrap(), (200 * 5 + 400 * 10) / 15);
+        // window older than the history is clipped to the first point
+        assert_eq!(h.twap(40, 1_000).unwrap(), h.twap(40, 40).unwrap());
+        assert_eq!(h.twap(40, u64::MAX).unwrap(), h.twap(40, 40).unwrap());
+        // window entirely inside the newest segment
+        assert_eq!(h.twap(40, 5).unwrap(), 400);
+        // no elapsed time
+        assert_eq!(h.twap(30, 0).unwrap(), 400);
+    }
+
+    #[test]
+    fn twap_single_point_and_empty() {
+        let mut h = history();
+        assert!(h.twap(0, 10).is_err());
+        h.record(750, 100);
+        assert_eq!(h.twap(100, 60).unwrap(), 750);
+        assert_eq!(h.twap(500, 60).unwrap(), 750);
+    }
+
+    #[test]
+    fn redemption_payout_is_pro_rata() {
+        let mut window = RedemptionWindow {
+            market: Pubkey::default(),
+            redemption_price: 0,
+            end_ts: 0,
+            total_bonds: 4,
+            total_owed: 8_000_000,
+            claim_count: 2,
+            paid_count: 0,
+            available: 8_000_000,
+            settled: true,
+            bump: 0,
+        };
+        assert_eq!(window.payout(6_000_000).unwrap(), 6_000_000);
+
+        // half covered: every claim takes the same haircut, rounded down
+        window.available = 4_000_000;
+        assert_eq!(window.payout(6_000_000).unwrap(), 3_000_000);
+        assert_eq!(window.payout(3).unwrap(), 1);
+
+        window.total_owed = 0;
+        assert_eq!(window.payout(0).unwrap(), 0);
+    }
+
+    #[test]
+    fn slippage_guard_compares_against_last_trade() {
+        let mut market = Market::try_from_slice(&[0u8; Market::LEN - 8]).unwrap();
+        market.max_slippage_bps = 500;
+        assert!(!market.exceeds_slippage(u128::MAX), "no trade yet");
+
+        market.last_trade_price = 1_000_000;
+        assert!(!market.exceeds_slippage(1_050_000));
+        assert!(!market.exceeds_slippage(950_000));
+        assert!(market.exceeds_slippage(1_050_001));
+        assert!(market.exceeds_slippage(949_999));
+        assert!(market.exceeds_slippage(u128::MAX));
+
+        market.max_slippage_bps = 0;
+        assert!(!market.exceeds_slippage(2_000_000));
+    }
+
+    #[test]
+    fn price_updates_respect_the_change_limit_and_cooldown() {
+        let mut market = Market::try_from_slice(&[0u8; Market::LEN - 8]).unwrap();
+        market.price_per_token = 1_000_000;
+        market.check_price_update(u128::MAX, 10).unwrap();
+
+        market.max_price_change_bps = 1_000;
+        market.check_price_update(1_100_000, 10).unwrap();
+        market.check_price_update(900_000, 10).unwrap();
+        assert!(is_error(market.check_price_update(1_100_001, 10).unwrap_err(), "PriceChangeTooLarge"));
+        assert!(is_error(market.check_price_update(0, 10).unwrap_err(), "PriceChangeTooLarge"));
+        assert!(!market.has_price_guard());
+
+        market.price_update_cooldown_secs = 60;
+        assert!(market.has_price_guard());
+        market.check_price_update(1_000_000, 10).unwrap();
+        market.last_price_update_ts = 10;
+        assert!(is_error(market.check_price_update(1_000_000, 69).unwrap_err(), "PriceUpdateCooldown"));
+        market.check_price_update(1_000_000, 70).unwrap();
+    }
+
+    #[test]
+    fn per_lot_price_matches_per_base_unit() {
+        // 6-decimal bond at 1000 USDC each: 1_000 quote units per base unit, or
+        // 1_000_000_000 per lot of one whole bond
+        let mut unit = Market::try_from_slice(&[0u8; Market::LEN - 8]).unwrap();
+        unit.price_per_token = 1_000;
+        unit.bond_decimals = 6;
+        unit.usdc_decimals = 6;
+        let mut lot = unit.clone();
+        lot.price_basis = PriceBasis::PerLot;
+        lot.lot_size = 1_000_000;
+        lot.price_per_token = 1_000_000_000;
+
+        for amount in [1, 999, 1_000_000, 2_500_001] {
+            assert_eq!(unit.buy_cost(amount).unwrap(), lot.buy_cost(amount).unwrap());
+            assert_eq!(unit.sell_proceeds(amount).unwrap(), lot.sell_proceeds(amount).unwrap());
+        }
+        unit.refresh_display_price().unwrap();
+        lot.refresh_display_price().unwrap();
+        assert_eq!(unit.display_price, lot.display_price);
+
+        // a lot price finer than one quote unit per base unit still rounds per side
+        lot.price_per_token = 1_500_000;
+        assert_eq!(lot.buy_cost(1).unwrap(), 2);
+        assert_eq!(lot.sell_proceeds(1).unwrap(), 1);
+    }
+
+    #[test]
+    fn daily_stats_roll_over_at_day_boundaries() {
+        let day = DailyStats::SECONDS_PER_DAY;
+        let mut stats = DailyStats::try_from_slice(&[0u8; DailyStats::LEN - 8]).unwrap();
+        assert!(stats.record(100, 10, 5 * day + 1).unwrap().is_none(), "first trade ever closes nothing");
+        assert!(stats.record(130, 10, 5 * day + 50).unwrap().is_none());
+        assert!(stats.record(90, 10, 5 * day + 100).unwrap().is_none());
+        assert!(stats.record(110, 10, 6 * day - 1).unwrap().is_none());
+
+        let candle = stats.record(120, 7, 6 * day).unwrap().unwrap();
+        assert_eq!(candle.day, 5);
+        assert_eq!((candle.open, candle.high, candle.low, candle.close), (100, 130, 90, 110));
+        assert_eq!((candle.volume, candle.trade_count), (40, 4));
+        assert_eq!((stats.day, stats.open, stats.high, stats.low, stats.close), (6, 120, 120, 120, 120));
+        assert_eq!((stats.volume, stats.trade_count), (7, 1));
+
+        // days 7 and 8 see no trades, so the next candle is day 6 and nothing is emitted for them
+        let candle = stats.record(80, 1, 9 * day + 5).unwrap().unwrap();
+        assert_eq!((candle.day, candle.open, candle.close, candle.trade_count), (6, 120, 120, 1));
+        assert_eq!(stats.day, 9);
+    }
+
+    #[test]
+    fn emergency_exit_rate_is_pro_rata_up_to_price() {
+        let mut market = Market::try_from_slice(&[0u8; Market::LEN - 8]).unwrap();
+        market.price_per_token = 1_000_000;
+        market.net_bonds_out = 10;
+
+        assert_eq!(market.emergency_exit_rate(4, 10_000_000).unwrap(), 1_000_000);
+        assert_eq!(market.emergency_exit_rate(4, 25_000_000).unwrap(), 1_000_000);
+        // 4 USDC behind 10 bonds: each exit gets 0.4 USDC a bond
+        assert_eq!(market.emergency_exit_rate(4, 4_000_000).unwrap(), 400_000);
+        assert_eq!(market.sell_proceeds_at(4, 400_000).unwrap(), 1_600_000);
+        // bonds from outside the market widen the pool instead of overdrawing it
+        assert_eq!(market.emergency_exit_rate(20, 4_000_000).unwrap(), 200_000);
+        assert_eq!(market.emergency_exit_rate(0, 0).unwrap(), 0);
+    }
+
+    #[test]
+    fn terminated_markets_keep_their_backing() {
+        let mut market = Market::try_from_slice(&[0u8; Market::LEN - 8]).unwrap();
+        market.price_per_token = 1_000_000;
+        market.net_bonds_out = 3;
+        market.sell_enabled = true;
+        assert_eq!(market.backing_required().unwrap(), 3_000_000);
+        market.terminated = true;
+        assert_eq!(market.backing_required().unwrap(), 3_000_000);
+        market.sell_enabled = false;
+        assert_eq!(market.backing_required().unwrap(), 0);
+    }
+
+    #[test]
+    fn status_sits_at_a_fixed_offset() {
+        let mut market = Market::try_from_slice(&[0u8; Market::LEN - 8]).unwrap();
+        market.phase = MarketPhase::Matured;
+        market.paused = true;
+        market.refresh_status();
+        assert_eq!(market.status, Market::STATUS_PAUSED | (2 << Market::STATUS_PHASE_SHIFT));
+
+        let mut data = Vec::new();
+        market.try_serialize(&mut data).unwrap();
+        assert_eq!(data.len(), Market::LEN);
+        assert_eq!(Market::STATUS_OFFSET, Market::DISCRIMINATOR.len());
+        assert_eq!(data[Market::STATUS_OFFSET], market.status);
+
+        market.terminated = true;
+        market.paused = false;
+        market.refresh_status();
+        let mut data = Vec::new();
+        market.try_serialize(&mut data).unwrap();
+        assert_eq!(data[Market::STATUS_OFFSET], Market::STATUS_TERMINATED | (2 << Market::STATUS_PHASE_SHIFT));
+    }
+
+    fn is_error(err: Error, name: &str) -> bool {
+        matches!(err, Error::AnchorError(e) if e.error_name == name)
+    }
+
+    #[test]
+    fn v0_markets_upgrade_in_place() {
+        let legacy = MarketV0 {
+            bond_mint: Pubkey::new_unique(),
+            usdc_mint: Pubkey::new_unique(),
+            price_per_token: 1_000_000,
+            vault_bond: Pubkey::new_unique(),
+            vault_usdc: Pubkey::new_unique(),
+            admin: Pubkey::new_unique(),
+            paused: true,
+            bump: 254,
+        };
+        assert_eq!(legacy.try_to_vec().unwrap().len(), MarketV0::LEN - 8);
+
+        let key = Pubkey::new_unique();
+        let market = legacy.upgrade(key, 7).unwrap();
+        assert_eq!((market.bond_mint, market.vault_usdc, market.admin), (legacy.bond_mint, legacy.vault_usdc, legacy.admin));
+        assert_eq!((market.price_per_token, market.price_scale, market.bump), (1_000_000, 0, 254));
+        assert_eq!(market.net_bonds_out, 7);
+        assert_eq!((market.price_updater, market.vault_authority), (legacy.admin, key));
+        assert!(market.sell_enabled && !market.decimals_set);
+
+        let mut data = Vec::new();
+        market.try_serialize(&mut data).unwrap();
+        assert_eq!(data.len(), Market::LEN);
+        assert_eq!(data[Market::STATUS_OFFSET], Market::STATUS_PAUSED | (1 << Market::STATUS_PHASE_SHIFT));
+        assert_eq!(data[Market::VERSION_OFFSET], Market::VERSION);
+    }
+
+    #[test]
+    fn only_older_versions_are_zero_extended() {
+        let mut market = Market::try_from_slice(&[0u8; Market::LEN - 8]).unwrap();
+        market.version = Market::VERSION;
+        let body = market.try_to_vec().unwrap();
+        assert!(is_error(Market::from_older_version(&body).err().unwrap(), "AlreadyMigrated"));
+
+        // version 1 ended before trade_cooldown_secs, version 2 after it,
+        // version 3 after last_price_update_ts
+        market.version = 1;
+        market.max_cumulative_withdraw = 5;
+        let body = market.try_to_vec().unwrap();
+        let upgraded = Market::from_older_version(&body[..body.len() - 36]).unwrap();
+        assert_eq!((upgraded.version, upgraded.max_cumulative_withdraw, upgraded.trade_cooldown_secs), (Market::VERSION, 5, 0));
+
+        market.version = 2;
+        market.trade_cooldown_secs = 30;
+        let body = market.try_to_vec().unwrap();
+        let upgraded = Market::from_older_version(&body[..body.len() - 28]).unwrap();
+        assert_eq!((upgraded.version, upgraded.trade_cooldown_secs, upgraded.max_price_change_bps), (Market::VERSION, 30, 0));
+
+        market.version = 3;
+        market.price_update_cooldown_secs = 45;
+        let body = market.try_to_vec().unwrap();
+        let upgraded = Market::from_older_version(&body[..body.len() - 10]).unwrap();
+        assert_eq!((upgraded.version, upgraded.price_update_cooldown_secs, upgraded.wash_window_secs), (Market::VERSION, 45, 0));
+
+        // versions start at 1, so a short body with version 0 is no known layout
+        market.version = 0;
+        let body = market.try_to_vec().unwrap();
+        assert!(is_error(Market::from_older_version(&body[..100]).err().unwrap(), "InvalidMarketAccount"));
+    }
+
+    #[test]
+    fn trade_cooldown_runs_from_the_last_trade() {
+        let mut market = Market::try_from_slice(&[0u8; Market::LEN - 8]).unwrap();
+        let mut position = InvestorPosition::deserialize(&mut &[0u8; InvestorPosition::LEN - 8][..]).unwrap();
+        market.trade_cooldown_secs = 60;
+        position.record_trade(&market, TradeSide::Buy, 1_000).unwrap();
+        assert!(is_error(position.record_trade(&market, TradeSide::Buy, 1_059).unwrap_err(), "TradeCooldown"));
+        position.record_trade(&market, TradeSide::Sell, 1_060).unwrap();
+        assert_eq!(position.last_trade_ts, 1_060);
+
+        // no cooldown: every trade is stamped
+        market.trade_cooldown_secs = 0;
+        position.record_trade(&market, TradeSide::Buy, 1_061).unwrap();
+        assert_eq!(position.last_trade_ts, 1_061);
+        market.trade_cooldown_secs = u64::MAX;
+        position.record_trade(&market, TradeSide::Buy, 1_061).unwrap_err();
+    }
+
+    #[test]
+    fn wash_check_counts_side_flips_per_window() {
+        let mut market = Market::try_from_slice(&[0u8; Market::LEN - 8]).unwrap();
+        let mut position = InvestorPosition::deserialize(&mut &[0u8; InvestorPosition::LEN - 8][..]).unwrap();
+        market.wash_window_secs = 100;
+        market.wash_flip_threshold = 1;
+        assert!(market.requires_position());
+
+        // buys in a row never flip; buy, sell is one flip, buy again a second
+        position.record_trade(&market, TradeSide::Buy, 1_000).unwrap();
+        position.record_trade(&market, TradeSide::Buy, 1_001).unwrap();
+        position.record_trade(&market, TradeSide::Sell, 1_010).unwrap();
+        assert_eq!((position.flip_count, position.flip_window_start_ts), (1, 1_010));
+        position.record_trade(&market, TradeSide::Sell, 1_020).unwrap();
+        assert!(position.is_wash_suspected(&market, &TradeSide::Buy, 1_109));
+        assert!(is_error(position.record_trade(&market, TradeSide::Buy, 1_109).unwrap_err(), "WashTradingSuspected"));
+
+        // the window restarts once it has passed
+        position.record_trade(&market, TradeSide::Buy, 1_110).unwrap();
+        assert_eq!((position.flip_count, position.flip_window_start_ts), (1, 1_110));
+
+        // either setting at 0 turns the check off
+        market.wash_flip_threshold = 0;
+        assert!(!market.requires_position());
+        position.record_trade(&market, TradeSide::Sell, 1_111).unwrap();
+        position.record_trade(&market, TradeSide::Buy, 1_112).unwrap();
+    }
+
+    #[test]
+    fn relayer_allowance_is_debited_until_expiry() {
+        let mut auth = RelayerAuthorization::try_from_slice(&[0u8; RelayerAuthorization::LEN - 8]).unwrap();
+        auth.remaining_notional = 10;
+        auth.expiry = 100;
+        auth.spend(4, 50).unwrap();
+        auth.spend(6, 99).unwrap();
+        assert_eq!(auth.remaining_notional, 0);
+        assert!(auth.spend(1, 99).is_err());
+        auth.remaining_notional = 5;
+        assert!(auth.spend(1, 100).is_err());
+        assert_eq!(auth.remaining_notional, 5);
+    }
+
+    #[test]
+    fn approved_payee_waits_out_the_timelock() {
+        let mut market = Market::try_from_slice(&[0u8; Market::LEN - 8]).unwrap();
+        market.admin = Pubkey::new_unique();
+        let payee = Pubkey::new_unique();
+        assert!(market.is_approved_payee(&market.admin, 0));
+        assert!(!market.is_approved_payee(&payee, 0));
+        assert!(!market.is_approved_payee(&Pubkey::default(), 0));
+
+        market.approved_payee = payee;
+        market.payee_effective_ts = 100;
+        assert!(!market.is_approved_payee(&payee, 99));
+        assert!(market.is_approved_payee(&payee, 100));
+        assert!(!market.is_approved_payee(&Pubkey::new_unique(), 100));
+    }
+
+    #[cfg(feature = "devnet-faucet")]
+    #[test]
+    fn faucet_limit_resets_each_day() {
+        use crate::constants::FAUCET_BONDS_PER_DAY;
+        let day = DailyStats::SECONDS_PER_DAY;
+        let mut claim = FaucetClaim::try_from_slice(&[0u8; FaucetClaim::LEN - 8]).unwrap();
+        claim.claim(FAUCET_BONDS_PER_DAY - 1, 3 * day).unwrap();
+        claim.claim(1, 4 * day - 1).unwrap();
+        assert!(claim.claim(1, 4 * day - 1).is_err());
+        claim.claim(FAUCET_BONDS_PER_DAY, 4 * day).unwrap();
+        assert_eq!((claim.day, claim.claimed), (4, FAUCET_BONDS_PER_DAY));
+    }
 }
@@ -0,0 +1,39 @@
+// Anchor 0.31 IDL codegen still calls `AccountInfo::realloc`, deprecated in solana 2.2.
+#![allow(deprecated)]
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::instruction::{AccountMeta, Instruction};
+use anchor_lang::solana_program::program::invoke;
+
+declare_id!("8VNfJwpuh1wtrerAZf2PywD8KQvQ6KqLEh8PznSrowvK");
+
+/// Test-only stand-in for an aggregator: relays one instruction to
+/// `target` so sebi's tests can trade through a CPI.
+#[program]
+pub mod trade_router {
+    use super::*;
+
+    /// Invokes `target` with `data` over remaining_accounts, keeping each
+    /// account's signer and writable flags
+    pub fn forward<'info>(ctx: Context<'_, '_, 'info, 'info, Forward<'info>>, data: Vec<u8>) -> Result<()> {
+        let accounts = ctx.remaining_accounts;
+        let ix = Instruction {
+            program_id: ctx.accounts.target.key(),
+            accounts: accounts
+                .iter()
+                .map(|a| AccountMeta { pubkey: *a.key, is_signer: a.is_signer, is_writable: a.is_writable })
+                .collect(),
+            data,
+        };
+        let mut infos = accounts.to_vec();
+        infos.push(ctx.accounts.target.to_account_info());
+        invoke(&ix, &infos)?;
+        Ok(())
+    }
+}
+
+#[derive(Accounts)]
+pub struct Forward<'info> {
+    /// CHECK: any program; the router only relays to it
+    #[account(executable)]
+    pub target: UncheckedAccount<'info>,
+}
@@ -53,6 +53,15 @@ async function main() {
       buyerBond: buyerBondAta.address,
       vaultUsdc: new anchor.web3.PublicKey(process.env.VAULT_USDC!),
       vaultBond: new anchor.web3.PublicKey(process.env.VAULT_BOND!),
+      instructions: null,
+      protocolStats: null,
+      complianceProgram: null,
+      tradingDelegate: null,
+      relayerAuthorization: null,
+      investorPosition: null,
+      dailyStats: null,
+      notifyProgram: null,
+      bondMint: null,
       tokenProgram: anchor.utils.token.TOKEN_PROGRAM_ID,
     })
     .signers([buyer])
@@ -39,18 +39,69 @@ async function main() {
     true
   );
 
+  // Ring buffer of recent prices, created alongside the market
+  const [priceHistory] = anchor.web3.PublicKey.findProgramAddressSync(
+    [Buffer.from("price_history"), marketPda.toBuffer()],
+    program.programId
+  );
+
+  // Program config (quote mint allowlist); created once by the upgrade authority
+  const [config] = anchor.web3.PublicKey.findProgramAddressSync(
+    [Buffer.from("config")],
+    program.programId
+  );
+
+  // Registry PDAs: the market is appended to page market_count / 32
+  const [registry] = anchor.web3.PublicKey.findProgramAddressSync(
+    [Buffer.from("registry")],
+    program.programId
+  );
+  const registryInfo = await program.account.marketRegistry.fetchNullable(registry);
+  const marketCount = registryInfo ? (registryInfo.marketCount as anchor.BN).toNumber() : 0;
+  const page = new anchor.BN(Math.floor(marketCount / 32));
+  const [registryPage] = anchor.web3.PublicKey.findProgramAddressSync(
+    [Buffer.from("registry_page"), page.toArrayLike(Buffer, "le", 8)],
+    program.programId
+  );
+
   // Default price = 1 USDC per bond (scaled 1e6)
   const price = new anchor.BN(process.env.PRICE || "1000000");
 
+  // Fixed-point exponent: cost = amount * price / 10^PRICE_SCALE
+  const priceScale = parseInt(process.env.PRICE_SCALE || "0");
+
+  // Issue-only markets set SELL_ENABLED=false to never buy bonds back
+  const sellEnabled = process.env.SELL_ENABLED !== "false";
+
+  // Optional Dutch auction: AUCTION_END_PRICE over [AUCTION_START_TS, AUCTION_END_TS]
+  // decaying from PRICE; price is then set by the schedule, not update_price
+  const auction = process.env.AUCTION_END_TS
+    ? {
+        startPrice: price,
+        endPrice: new anchor.BN(process.env.AUCTION_END_PRICE!),
+        startTs: new anchor.BN(process.env.AUCTION_START_TS!),
+        endTs: new anchor.BN(process.env.AUCTION_END_TS),
+      }
+    : null;
+
+  // Optional LOT_SIZE: PRICE is then per LOT_SIZE bond base units, not per base unit
+  const lotSize = parseInt(process.env.LOT_SIZE || "0");
+  const priceBasis = lotSize > 0 ? { perLot: {} } : { perBaseUnit: {} };
+
   // Call initialize_market
   await program.methods
-    .initializeMarket(price)
+    .initializeMarket(price, priceScale, sellEnabled, auction, priceBasis, new anchor.BN(lotSize))
     .accounts({
       market: marketPda,
       bondMint,
       usdcMint,
       vaultBond: vaultBond.address,
       vaultUsdc: vaultUsdc.address,
+      priceHistory,
+      config,
+      registry,
+      registryPage,
+      protocolStats: null,
       admin: admin.publicKey,
       systemProgram: anchor.web3.SystemProgram.programId,
       tokenProgram: anchor.utils.token.TOKEN_PROGRAM_ID,
@@ -44,6 +44,15 @@ async function main() {
       sellerUsdc: sellerUsdcAta.address,
       vaultBond: new anchor.web3.PublicKey(process.env.VAULT_BOND!),
       vaultUsdc: new anchor.web3.PublicKey(process.env.VAULT_USDC!),
+      insuranceVault: null,
+      instructions: null,
+      protocolStats: null,
+      complianceProgram: null,
+      tradingDelegate: null,
+      relayerAuthorization: null,
+      investorPosition: null,
+      dailyStats: null,
+      notifyProgram: null,
       tokenProgram: anchor.utils.token.TOKEN_PROGRAM_ID,
     })
     .signers([seller])
@@ -11,12 +11,18 @@ async function main() {
     program.programId
   );
 
+  const [priceHistory] = anchor.web3.PublicKey.findProgramAddressSync(
+    [Buffer.from("price_history"), marketPda.toBuffer()],
+    program.programId
+  );
+
   const newPrice = new anchor.BN(process.env.NEW_PRICE || "2000000"); // 2 USDC if decimals=6
 
   await program.methods
     .updatePrice(newPrice)
     .accounts({
       market: marketPda,
+      priceHistory,
       admin: provider.wallet.publicKey,
     })
     .rpc();
@@ -0,0 +1,46 @@
+import * as anchor from "@coral-xyz/anchor";
+import NodeWallet from "@coral-xyz/anchor/dist/cjs/nodewallet";
+import { Program } from "@coral-xyz/anchor";
+import { Sebi } from "../target/types/sebi";
+import { Keypair, LAMPORTS_PER_SOL, PublicKey } from "@solana/web3.js";
+import { describe, it } from "node:test";
+import assert from "assert";
+import { setupMarket, ensureConfig, expectError } from "./utils";
+
+describe("admin market limit", () => {
+  anchor.setProvider(anchor.AnchorProvider.env());
+  const provider = anchor.getProvider() as anchor.AnchorProvider;
+  const authority = (provider.wallet as NodeWallet).payer;
+  const program = anchor.workspace.Sebi as Program<Sebi>;
+
+  const setMax = async (max: number) => {
+    const config = await ensureConfig(program, authority);
+    await program.methods
+      .setMaxMarketsPerAdmin(new anchor.BN(max))
+      .accountsPartial({ config, authority: authority.publicKey })
+      .rpc();
+  };
+
+  it("lets one admin create markets up to the cap and no more", async () => {
+    const admin = Keypair.generate();
+    const sig = await provider.connection.requestAirdrop(admin.publicKey, 5 * LAMPORTS_PER_SOL);
+    await provider.connection.confirmTransaction(sig);
+
+    // the cap is program-wide, so restore it for the other suites
+    await setMax(2);
+    try {
+      await setupMarket(program, admin, new anchor.BN(1_000_000));
+      await setupMarket(program, admin, new anchor.BN(1_000_000));
+      await expectError(setupMarket(program, admin, new anchor.BN(1_000_000)), "MarketLimitReached");
+    } finally {
+      await setMax(0);
+    }
+
+    const [count] = PublicKey.findProgramAddressSync(
+      [Buffer.from("admin_markets"), admin.publicKey.toBuffer()],
+      program.programId
+    );
+    assert.equal((await program.account.adminMarketCount.fetch(count)).count.toNumber(), 2);
+    await setupMarket(program, admin, new anchor.BN(1_000_000));
+  });
+});
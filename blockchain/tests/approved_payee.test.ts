@@ -0,0 +1,52 @@
+import * as anchor from "@coral-xyz/anchor";
+import NodeWallet from "@coral-xyz/anchor/dist/cjs/nodewallet";
+import { Program } from "@coral-xyz/anchor";
+import { Sebi } from "../target/types/sebi";
+import { getOrCreateAssociatedTokenAccount } from "@solana/spl-token";
+import { Keypair, PublicKey } from "@solana/web3.js";
+import { describe, it } from "node:test";
+import assert from "assert";
+import { setupMarket, tokenBalance, expectError, withdraw, TestMarket } from "./utils";
+
+describe("approved payee", () => {
+  anchor.setProvider(anchor.AnchorProvider.env());
+  const provider = anchor.getProvider() as anchor.AnchorProvider;
+  const admin = (provider.wallet as NodeWallet).payer;
+  const program = anchor.workspace.Sebi as Program<Sebi>;
+
+  const setPayee = (m: TestMarket, payee: PublicKey) =>
+    program.methods
+      .setApprovedPayee(payee)
+      .accountsPartial({ market: m.market, admin: admin.publicKey })
+      .rpc();
+
+  it("rejects destinations owned by anyone but admin", async () => {
+    const m = await setupMarket(program, admin, new anchor.BN(1_000_000));
+    const stranger = await getOrCreateAssociatedTokenAccount(
+      provider.connection, admin, m.bondMint, Keypair.generate().publicKey
+    );
+    await expectError(withdraw(program, admin, m, stranger.address, 1, false), "UnapprovedPayee");
+
+    const treasury = await getOrCreateAssociatedTokenAccount(provider.connection, admin, m.bondMint, admin.publicKey);
+    await withdraw(program, admin, m, treasury.address, 1, false);
+    assert.equal(await tokenBalance(program, treasury.address), 1);
+  });
+
+  it("holds a new payee back until the timelock passes", async () => {
+    const m = await setupMarket(program, admin, new anchor.BN(1_000_000));
+    const payee = Keypair.generate().publicKey;
+    const payeeBond = await getOrCreateAssociatedTokenAccount(provider.connection, admin, m.bondMint, payee);
+
+    await setPayee(m, payee);
+    const market = await program.account.market.fetch(m.market);
+    assert.ok(market.approvedPayee.equals(payee));
+    assert.ok(market.payeeEffectiveTs.toNumber() > Date.now() / 1000);
+    await expectError(withdraw(program, admin, m, payeeBond.address, 1, false), "UnapprovedPayee");
+
+    // revoking takes effect at once
+    await setPayee(m, PublicKey.default);
+    const cleared = await program.account.market.fetch(m.market);
+    assert.equal(cleared.payeeEffectiveTs.toNumber(), 0);
+    assert.equal(await tokenBalance(program, payeeBond.address), 0);
+  });
+});
@@ -0,0 +1,61 @@
+import * as anchor from "@coral-xyz/anchor";
+import NodeWallet from "@coral-xyz/anchor/dist/cjs/nodewallet";
+import { Program } from "@coral-xyz/anchor";
+import { Sebi } from "../target/types/sebi";
+import { describe, it } from "node:test";
+import assert from "assert";
+import { setupMarket, createTrader, buy, tokenBalance, expectError } from "./utils";
+
+describe("dutch auction", () => {
+  anchor.setProvider(anchor.AnchorProvider.env());
+  const provider = anchor.getProvider() as anchor.AnchorProvider;
+  const admin = (provider.wallet as NodeWallet).payer;
+  const program = anchor.workspace.Sebi as Program<Sebi>;
+
+  // intermediate timestamps are sampled by the interpolate_price unit test;
+  // the validator clock can't be steered, so these pin the clamped ends
+  const auction = (startOffset: number, endOffset: number) => {
+    const now = Math.floor(Date.now() / 1000);
+    return {
+      startPrice: new anchor.BN(1_000_000),
+      endPrice: new anchor.BN(400_000),
+      startTs: new anchor.BN(now + startOffset),
+      endTs: new anchor.BN(now + endOffset),
+    };
+  };
+
+  const updatePrice = (market: anchor.web3.PublicKey, price: number) =>
+    program.methods
+      .updatePrice(new anchor.BN(price))
+      .accountsPartial({ market, admin: admin.publicKey })
+      .rpc();
+
+  it("charges start_price before the auction starts", async () => {
+    const m = await setupMarket(program, admin, new anchor.BN(1), { auction: auction(3_600, 7_200) });
+    const trader = await createTrader(program, admin, m, 10_000_000);
+
+    await buy(program, m, trader, 2);
+    assert.equal(await tokenBalance(program, m.vaultUsdc), 2_000_000);
+    await expectError(updatePrice(m.market, 900_000), "AuctionInProgress");
+  });
+
+  it("holds end_price after the auction ends and hands pricing back", async () => {
+    const m = await setupMarket(program, admin, new anchor.BN(1), { auction: auction(-7_200, -3_600) });
+    const trader = await createTrader(program, admin, m, 10_000_000);
+
+    await buy(program, m, trader, 2);
+    assert.equal(await tokenBalance(program, m.vaultUsdc), 800_000);
+
+    await updatePrice(m.market, 500_000);
+    const market = await program.account.market.fetch(m.market);
+    assert.equal(market.auctionEnabled, false);
+    assert.equal(market.pricePerToken.toNumber(), 500_000);
+  });
+
+  it("rejects a schedule that does not move forward in time", async () => {
+    await expectError(
+      setupMarket(program, admin, new anchor.BN(1), { auction: auction(100, 100) }),
+      "InvalidAuction"
+    );
+  });
+});
@@ -0,0 +1,44 @@
+import * as anchor from "@coral-xyz/anchor";
+import NodeWallet from "@coral-xyz/anchor/dist/cjs/nodewallet";
+import { Program } from "@coral-xyz/anchor";
+import { Sebi } from "../target/types/sebi";
+import { getMint } from "@solana/spl-token";
+import { describe, it } from "node:test";
+import assert from "assert";
+import { setupMarket, createTrader, buy, tokenBalance, expectError, programEvents, TestMarket } from "./utils";
+
+describe("burn unsold", () => {
+  anchor.setProvider(anchor.AnchorProvider.env());
+  const provider = anchor.getProvider() as anchor.AnchorProvider;
+  const admin = (provider.wallet as NodeWallet).payer;
+  const program = anchor.workspace.Sebi as Program<Sebi>;
+
+  const burnUnsold = (m: TestMarket) =>
+    program.methods
+      .burnUnsold()
+      .accountsPartial({ market: m.market, bondMint: m.bondMint, vaultBond: m.vaultBond })
+      .rpc();
+
+  it("burns the remaining inventory once matured", async () => {
+    const m = await setupMarket(program, admin, new anchor.BN(1_000_000), { bondSupply: 100 });
+    const trader = await createTrader(program, admin, m, 10_000_000);
+    await buy(program, m, trader, 30);
+
+    await expectError(burnUnsold(m), "BurnDisabled");
+    await program.methods
+      .setBurnUnsoldAtMaturity(true)
+      .accountsPartial({ market: m.market, admin: admin.publicKey })
+      .rpc();
+    await expectError(burnUnsold(m), "InvalidPhase");
+
+    await program.methods.setPhase({ matured: {} }).accountsPartial({ market: m.market, admin: admin.publicKey }).rpc();
+    const sig = await burnUnsold(m);
+
+    assert.equal(await tokenBalance(program, m.vaultBond), 0);
+    assert.equal(Number((await getMint(provider.connection, m.bondMint)).supply), 30);
+    assert.equal(await tokenBalance(program, trader.bond), 30);
+    const burns = (await programEvents(program, sig)).filter((e) => e.name === "burnEvent");
+    assert.equal(burns.length, 1);
+    assert.equal((burns[0].data as { amount: anchor.BN }).amount.toNumber(), 70);
+  });
+});
@@ -0,0 +1,84 @@
+import * as anchor from "@coral-xyz/anchor";
+import NodeWallet from "@coral-xyz/anchor/dist/cjs/nodewallet";
+import { Program } from "@coral-xyz/anchor";
+import { Sebi } from "../target/types/sebi";
+import {
+  ASSOCIATED_TOKEN_PROGRAM_ID,
+  getAccount,
+  getAssociatedTokenAddressSync,
+  getOrCreateAssociatedTokenAccount,
+  mintTo,
+  TOKEN_PROGRAM_ID,
+} from "@solana/spl-token";
+import { Keypair, LAMPORTS_PER_SOL, PublicKey, SystemProgram } from "@solana/web3.js";
+import { describe, it } from "node:test";
+import assert from "assert";
+import { setupMarket, tokenBalance, expectError, TestMarket } from "./utils";
+
+describe("buy creates the bond account", () => {
+  anchor.setProvider(anchor.AnchorProvider.env());
+  const provider = anchor.getProvider() as anchor.AnchorProvider;
+  const admin = (provider.wallet as NodeWallet).payer;
+  const program = anchor.workspace.Sebi as Program<Sebi>;
+
+  // a funded wallet holding USDC but no bond account
+  async function newBuyer(m: TestMarket) {
+    const keypair = Keypair.generate();
+    const sig = await provider.connection.requestAirdrop(keypair.publicKey, LAMPORTS_PER_SOL);
+    await provider.connection.confirmTransaction(sig);
+    const usdc = await getOrCreateAssociatedTokenAccount(provider.connection, admin, m.usdcMint, keypair.publicKey);
+    await mintTo(provider.connection, admin, m.usdcMint, usdc.address, admin, 10_000_000);
+    return { keypair, usdc: usdc.address, bond: getAssociatedTokenAddressSync(m.bondMint, keypair.publicKey) };
+  }
+
+  const buyCreating = (m: TestMarket, buyer: { keypair: Keypair; usdc: PublicKey; bond: PublicKey }, create: boolean) =>
+    program.methods
+      .buy(new anchor.BN(2))
+      .accountsPartial({
+        market: m.market,
+        buyer: buyer.keypair.publicKey,
+        buyerUsdc: buyer.usdc,
+        buyerBond: buyer.bond,
+        vaultUsdc: m.vaultUsdc,
+        vaultBond: m.vaultBond,
+        instructions: null,
+        protocolStats: null,
+        complianceProgram: null,
+        tradingDelegate: null,
+        relayerAuthorization: null,
+        investorPosition: null,
+        dailyStats: null,
+        notifyProgram: null,
+        bondMint: create ? m.bondMint : null,
+        associatedTokenProgram: create ? ASSOCIATED_TOKEN_PROGRAM_ID : null,
+        systemProgram: create ? SystemProgram.programId : null,
+        tokenProgram: TOKEN_PROGRAM_ID,
+      })
+      .signers([buyer.keypair])
+      .rpc();
+
+  it("creates a first-time buyer's ATA at the buyer's expense", async () => {
+    const m = await setupMarket(program, admin, new anchor.BN(1_000_000));
+    const buyer = await newBuyer(m);
+    const lamportsBefore = await provider.connection.getBalance(buyer.keypair.publicKey);
+
+    await buyCreating(m, buyer, true);
+    const account = await getAccount(provider.connection, buyer.bond);
+    assert.ok(account.owner.equals(buyer.keypair.publicKey));
+    assert.equal(await tokenBalance(program, buyer.bond), 2);
+    assert.ok((await provider.connection.getBalance(buyer.keypair.publicKey)) < lamportsBefore);
+
+    // the account now exists, so later buys work with or without the extra accounts
+    await buyCreating(m, buyer, true);
+    await buyCreating(m, buyer, false);
+    assert.equal(await tokenBalance(program, buyer.bond), 6);
+  });
+
+  it("fails as before when the accounts to create it are left out", async () => {
+    const m = await setupMarket(program, admin, new anchor.BN(1_000_000));
+    const buyer = await newBuyer(m);
+
+    await expectError(buyCreating(m, buyer, false), "AccountNotInitialized");
+    assert.equal(await tokenBalance(program, buyer.usdc), 10_000_000);
+  });
+});
@@ -0,0 +1,56 @@
+import * as anchor from "@coral-xyz/anchor";
+import NodeWallet from "@coral-xyz/anchor/dist/cjs/nodewallet";
+import { Program } from "@coral-xyz/anchor";
+import { Sebi } from "../target/types/sebi";
+import { TOKEN_PROGRAM_ID } from "@solana/spl-token";
+import { describe, it } from "node:test";
+import assert from "assert";
+import { setupMarket, createTrader, tokenBalance, expectError, TestMarket, Trader } from "./utils";
+
+describe("buy if below", () => {
+  anchor.setProvider(anchor.AnchorProvider.env());
+  const provider = anchor.getProvider() as anchor.AnchorProvider;
+  const admin = (provider.wallet as NodeWallet).payer;
+  const program = anchor.workspace.Sebi as Program<Sebi>;
+
+  const buyIfBelow = (m: TestMarket, t: Trader, amount: number, maxPrice: number, maxCost: number) =>
+    program.methods
+      .buyIfBelow(new anchor.BN(amount), new anchor.BN(maxPrice), new anchor.BN(maxCost))
+      .accountsPartial({
+        market: m.market,
+        buyer: t.keypair.publicKey,
+        buyerUsdc: t.usdc,
+        buyerBond: t.bond,
+        vaultUsdc: m.vaultUsdc,
+        vaultBond: m.vaultBond,
+        instructions: null,
+        protocolStats: null,
+        complianceProgram: null,
+        tradingDelegate: null,
+        relayerAuthorization: null,
+        investorPosition: null,
+        dailyStats: null,
+        notifyProgram: null,
+        bondMint: null,
+        tokenProgram: TOKEN_PROGRAM_ID,
+      })
+      .signers([t.keypair])
+      .rpc();
+
+  it("fills at the target price and rejects one unit above it", async () => {
+    const m = await setupMarket(program, admin, new anchor.BN(1_000_000));
+    const trader = await createTrader(program, admin, m, 10_000_000);
+
+    await expectError(buyIfBelow(m, trader, 1, 999_999, 10_000_000), "PriceAboveTarget");
+    await buyIfBelow(m, trader, 2, 1_000_000, 2_000_000);
+    assert.equal(await tokenBalance(program, trader.bond), 2);
+  });
+
+  it("enforces the cost cap", async () => {
+    const m = await setupMarket(program, admin, new anchor.BN(1_000_000));
+    const trader = await createTrader(program, admin, m, 10_000_000);
+
+    await expectError(buyIfBelow(m, trader, 2, 1_000_000, 1_999_999), "SlippageExceeded");
+    assert.equal(await tokenBalance(program, trader.bond), 0);
+  });
+});
@@ -0,0 +1,149 @@
+import * as anchor from "@coral-xyz/anchor";
+import NodeWallet from "@coral-xyz/anchor/dist/cjs/nodewallet";
+import { Program } from "@coral-xyz/anchor";
+import { Sebi } from "../target/types/sebi";
+import { describe, it } from "node:test";
+import assert from "assert";
+import { getOrCreateAssociatedTokenAccount, mintTo, TOKEN_PROGRAM_ID } from "@solana/spl-token";
+import { Keypair, PublicKey } from "@solana/web3.js";
+import { setupMarket, createTrader, buy, TestMarket, Trader } from "./utils";
+
+// TradeBlock discriminants from instructions/can_trade.rs
+const NONE = 0;
+const PAUSED = 2;
+const SELL_DISABLED = 4;
+const INSUFFICIENT_INVENTORY = 7;
+const INSUFFICIENT_BALANCE = 9;
+const TRADE_COOLDOWN = 13;
+const POSITION_REQUIRED = 14;
+
+describe("can trade", () => {
+  anchor.setProvider(anchor.AnchorProvider.env());
+  const provider = anchor.getProvider() as anchor.AnchorProvider;
+  const admin = (provider.wallet as NodeWallet).payer;
+  const program = anchor.workspace.Sebi as Program<Sebi>;
+
+  const positionPda = (m: TestMarket, t: Trader) =>
+    PublicKey.findProgramAddressSync(
+      [Buffer.from("position"), m.market.toBuffer(), t.keypair.publicKey.toBuffer()],
+      program.programId
+    )[0];
+
+  const canTrade = async (
+    m: TestMarket,
+    t: Trader,
+    side: "buy" | "sell",
+    amount: number,
+    investorPosition: PublicKey | null = null
+  ) => {
+    const check = await program.methods
+      .canTrade(side === "buy" ? { buy: {} } : { sell: {} }, new anchor.BN(amount))
+      .accountsPartial({
+        market: m.market,
+        trader: t.keypair.publicKey,
+        traderUsdc: t.usdc,
+        traderBond: t.bond,
+        vaultBond: m.vaultBond,
+        vaultUsdc: m.vaultUsdc,
+        investorPosition,
+      })
+      .view();
+    assert.equal(check.allowed, check.reasonCode === NONE);
+    return check.reasonCode;
+  };
+
+  it("reports the first gate a trade would fail", async () => {
+    const m = await setupMarket(program, admin, new anchor.BN(1_000_000), { bondSupply: 5 });
+    const trader = await createTrader(program, admin, m, 3_000_000);
+
+    assert.equal(await canTrade(m, trader, "buy", 3), NONE);
+    assert.equal(await canTrade(m, trader, "buy", 4), INSUFFICIENT_BALANCE);
+    assert.equal(await canTrade(m, trader, "buy", 6), INSUFFICIENT_INVENTORY);
+    assert.equal(await canTrade(m, trader, "sell", 1), INSUFFICIENT_BALANCE);
+
+    // the real trade agrees with the pre-flight
+    await buy(program, m, trader, 3);
+    assert.equal(await canTrade(m, trader, "sell", 3), NONE);
+
+    await program.methods.pause().accountsPartial({ market: m.market, admin: admin.publicKey }).rpc();
+    assert.equal(await canTrade(m, trader, "sell", 1), PAUSED);
+  });
+
+  it("flags sells on issue-only markets", async () => {
+    const m = await setupMarket(program, admin, new anchor.BN(1_000_000), { sellEnabled: false });
+    const trader = await createTrader(program, admin, m, 1_000_000);
+    await buy(program, m, trader, 1);
+    assert.equal(await canTrade(m, trader, "sell", 1), SELL_DISABLED);
+  });
+
+  it("checks the trade cooldown against the position", async () => {
+    const m = await setupMarket(program, admin, new anchor.BN(1_000_000));
+    const trader = await createTrader(program, admin, m, 3_000_000);
+    const position = positionPda(m, trader);
+    await program.methods
+      .openInvestorPosition()
+      .accountsPartial({ market: m.market, investorPosition: position, owner: trader.keypair.publicKey })
+      .signers([trader.keypair])
+      .rpc();
+    await program.methods
+      .setTradeCooldown(new anchor.BN(60))
+      .accountsPartial({ market: m.market, admin: admin.publicKey })
+      .rpc();
+
+    assert.equal(await canTrade(m, trader, "buy", 1), POSITION_REQUIRED);
+    assert.equal(await canTrade(m, trader, "buy", 1, position), NONE);
+    // buy creates a missing position, sell needs an existing one
+    const fresh = await createTrader(program, admin, m, 3_000_000);
+    assert.equal(await canTrade(m, fresh, "buy", 1, positionPda(m, fresh)), NONE);
+    assert.equal(await canTrade(m, fresh, "sell", 1, positionPda(m, fresh)), POSITION_REQUIRED);
+
+    await program.methods
+      .buy(new anchor.BN(1))
+      .accountsPartial({
+        market: m.market,
+        buyer: trader.keypair.publicKey,
+        buyerUsdc: trader.usdc,
+        buyerBond: trader.bond,
+        vaultUsdc: m.vaultUsdc,
+        vaultBond: m.vaultBond,
+        bondMint: null,
+        investorPosition: position,
+        instructions: null,
+        protocolStats: null,
+        complianceProgram: null,
+        tradingDelegate: null,
+        relayerAuthorization: null,
+        dailyStats: null,
+        notifyProgram: null,
+        tokenProgram: TOKEN_PROGRAM_ID,
+      })
+      .signers([trader.keypair])
+      .rpc();
+    assert.equal(await canTrade(m, trader, "buy", 1, position), TRADE_COOLDOWN);
+    assert.equal(await canTrade(m, trader, "sell", 1, position), TRADE_COOLDOWN);
+  });
+
+  it("checks a first-time buyer without a bond account", async () => {
+    const m = await setupMarket(program, admin, new anchor.BN(1_000_000));
+    const keypair = Keypair.generate();
+    const usdc = await getOrCreateAssociatedTokenAccount(provider.connection, admin, m.usdcMint, keypair.publicKey);
+    await mintTo(provider.connection, admin, m.usdcMint, usdc.address, admin, 2_000_000);
+
+    const check = (side: "buy" | "sell", amount: number) =>
+      program.methods
+        .canTrade(side === "buy" ? { buy: {} } : { sell: {} }, new anchor.BN(amount))
+        .accountsPartial({
+          market: m.market,
+          trader: keypair.publicKey,
+          traderUsdc: usdc.address,
+          traderBond: null,
+          vaultBond: m.vaultBond,
+          vaultUsdc: m.vaultUsdc,
+          investorPosition: null,
+        })
+        .view();
+    assert.equal((await check("buy", 2)).reasonCode, NONE);
+    assert.equal((await check("buy", 3)).reasonCode, INSUFFICIENT_BALANCE);
+    assert.equal((await check("sell", 1)).reasonCode, INSUFFICIENT_BALANCE);
+  });
+});
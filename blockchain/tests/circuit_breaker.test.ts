@@ -0,0 +1,80 @@
+import * as anchor from "@coral-xyz/anchor";
+import NodeWallet from "@coral-xyz/anchor/dist/cjs/nodewallet";
+import { Program } from "@coral-xyz/anchor";
+import { Sebi } from "../target/types/sebi";
+import { Keypair, LAMPORTS_PER_SOL, PublicKey } from "@solana/web3.js";
+import { describe, it } from "node:test";
+import assert from "assert";
+import { setupMarket, expectError, priceHistoryPda } from "./utils";
+
+describe("circuit breaker authority", () => {
+  anchor.setProvider(anchor.AnchorProvider.env());
+  const provider = anchor.getProvider() as anchor.AnchorProvider;
+  const admin = (provider.wallet as NodeWallet).payer;
+  const program = anchor.workspace.Sebi as Program<Sebi>;
+
+  const pause = (market: PublicKey, authority: Keypair) =>
+    program.methods.pause().accountsPartial({ market, admin: authority.publicKey }).signers([authority]).rpc();
+
+  async function marketWithBreaker() {
+    const m = await setupMarket(program, admin, new anchor.BN(1_000_000));
+    const breaker = Keypair.generate();
+    const sig = await provider.connection.requestAirdrop(breaker.publicKey, LAMPORTS_PER_SOL);
+    await provider.connection.confirmTransaction(sig);
+    await program.methods
+      .setCircuitBreakerAuthority(breaker.publicKey)
+      .accountsPartial({ market: m.market, admin: admin.publicKey })
+      .rpc();
+    return { m, breaker };
+  }
+
+  it("lets the breaker and the admin toggle pause", async () => {
+    const { m, breaker } = await marketWithBreaker();
+
+    await pause(m.market, breaker);
+    assert.equal((await program.account.market.fetch(m.market)).paused, true);
+    await pause(m.market, admin);
+    assert.equal((await program.account.market.fetch(m.market)).paused, false);
+    await program.methods
+      .pauseWithGrace(new anchor.BN(60))
+      .accountsPartial({ market: m.market, admin: breaker.publicKey })
+      .signers([breaker])
+      .rpc();
+    assert.equal((await program.account.market.fetch(m.market)).paused, true);
+  });
+
+  it("gives the breaker nothing beyond pause", async () => {
+    const { m, breaker } = await marketWithBreaker();
+
+    await expectError(
+      program.methods
+        .updatePrice(new anchor.BN(2_000_000))
+        .accountsPartial({ market: m.market, priceHistory: priceHistoryPda(program, m.market), admin: breaker.publicKey })
+        .signers([breaker])
+        .rpc(),
+      "ConstraintHasOne"
+    );
+    await expectError(
+      program.methods
+        .setCircuitBreakerAuthority(breaker.publicKey)
+        .accountsPartial({ market: m.market, admin: breaker.publicKey })
+        .signers([breaker])
+        .rpc(),
+      "ConstraintHasOne"
+    );
+  });
+
+  it("rejects other signers and a cleared breaker", async () => {
+    const { m, breaker } = await marketWithBreaker();
+    const stranger = Keypair.generate();
+    const sig = await provider.connection.requestAirdrop(stranger.publicKey, LAMPORTS_PER_SOL);
+    await provider.connection.confirmTransaction(sig);
+    await expectError(pause(m.market, stranger), "Unauthorized");
+
+    await program.methods
+      .setCircuitBreakerAuthority(PublicKey.default)
+      .accountsPartial({ market: m.market, admin: admin.publicKey })
+      .rpc();
+    await expectError(pause(m.market, breaker), "Unauthorized");
+  });
+});
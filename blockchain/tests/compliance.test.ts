@@ -0,0 +1,78 @@
+import * as anchor from "@coral-xyz/anchor";
+import NodeWallet from "@coral-xyz/anchor/dist/cjs/nodewallet";
+import { Program } from "@coral-xyz/anchor";
+import { Sebi } from "../target/types/sebi";
+import { TOKEN_PROGRAM_ID } from "@solana/spl-token";
+import { Keypair, PublicKey } from "@solana/web3.js";
+import { describe, it } from "node:test";
+import assert from "assert";
+import { setupMarket, createTrader, buy, sell, tokenBalance, expectError, TestMarket, Trader } from "./utils";
+
+describe("compliance hook", () => {
+  anchor.setProvider(anchor.AnchorProvider.env());
+  const provider = anchor.getProvider() as anchor.AnchorProvider;
+  const admin = (provider.wallet as NodeWallet).payer;
+  const program = anchor.workspace.Sebi as Program<Sebi>;
+
+  const setCompliance = (m: TestMarket, complianceProgram: PublicKey) =>
+    program.methods
+      .setComplianceProgram(complianceProgram)
+      .accountsPartial({ market: m.market, admin: admin.publicKey })
+      .rpc();
+
+  const buyWith = (m: TestMarket, t: Trader, complianceProgram: PublicKey) =>
+    program.methods
+      .buy(new anchor.BN(1))
+      .accountsPartial({
+        market: m.market,
+        buyer: t.keypair.publicKey,
+        buyerUsdc: t.usdc,
+        buyerBond: t.bond,
+        vaultUsdc: m.vaultUsdc,
+        vaultBond: m.vaultBond,
+        instructions: null,
+        protocolStats: null,
+        complianceProgram,
+        tradingDelegate: null,
+        relayerAuthorization: null,
+        investorPosition: null,
+        dailyStats: null,
+        notifyProgram: null,
+        bondMint: null,
+        tokenProgram: TOKEN_PROGRAM_ID,
+      })
+      .signers([t.keypair])
+      .rpc();
+
+  // approving a trade needs a deployed check_trade callback; these cover the
+  // paths where the hook rejects before or without one
+  it("rejects trades that omit or swap the registered program", async () => {
+    const m = await setupMarket(program, admin, new anchor.BN(1_000_000));
+    const trader = await createTrader(program, admin, m, 10_000_000);
+    await buy(program, m, trader, 2);
+
+    const screen = Keypair.generate().publicKey;
+    await setCompliance(m, screen);
+    await expectError(buy(program, m, trader, 1), "ComplianceRejected");
+    await expectError(sell(program, m, trader, 1), "ComplianceRejected");
+    await expectError(buyWith(m, trader, TOKEN_PROGRAM_ID), "ComplianceRejected");
+
+    // clearing the hook restores plain trading
+    await setCompliance(m, PublicKey.default);
+    await sell(program, m, trader, 1);
+    assert.equal(await tokenBalance(program, trader.bond), 1);
+  });
+
+  it("only the admin sets the program", async () => {
+    const m = await setupMarket(program, admin, new anchor.BN(1_000_000));
+    const outsider = Keypair.generate();
+    await expectError(
+      program.methods
+        .setComplianceProgram(outsider.publicKey)
+        .accountsPartial({ market: m.market, admin: outsider.publicKey })
+        .signers([outsider])
+        .rpc(),
+      "ConstraintHasOne"
+    );
+  });
+});
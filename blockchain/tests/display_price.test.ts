@@ -0,0 +1,39 @@
+import * as anchor from "@coral-xyz/anchor";
+import NodeWallet from "@coral-xyz/anchor/dist/cjs/nodewallet";
+import { Program } from "@coral-xyz/anchor";
+import { Sebi } from "../target/types/sebi";
+import { describe, it } from "node:test";
+import assert from "assert";
+import { setupMarket } from "./utils";
+
+describe("display price", () => {
+  anchor.setProvider(anchor.AnchorProvider.env());
+  const provider = anchor.getProvider() as anchor.AnchorProvider;
+  const admin = (provider.wallet as NodeWallet).payer;
+  const program = anchor.workspace.Sebi as Program<Sebi>;
+
+  // test markets use a 0-decimal bond and a 6-decimal quote mint
+  it("caches whole USDC per whole bond with 6 decimals", async () => {
+    const m = await setupMarket(program, admin, new anchor.BN(1_500_000));
+    let market = await program.account.market.fetch(m.market);
+    assert.equal(market.bondDecimals, 0);
+    assert.equal(market.usdcDecimals, 6);
+    assert.equal(market.displayPrice.toNumber(), 1_500_000);
+
+    await program.methods
+      .updatePrice(new anchor.BN(2_250_000))
+      .accountsPartial({ market: m.market, admin: admin.publicKey })
+      .rpc();
+    market = await program.account.market.fetch(m.market);
+    assert.equal(market.displayPrice.toNumber(), 2_250_000);
+  });
+
+  it("applies price_scale and truncates below the display precision", async () => {
+    // 1.5 base units per bond at scale 2 is 0.0000015 USDC
+    const m = await setupMarket(program, admin, new anchor.BN(150), { priceScale: 2 });
+    await program.methods.refreshDisplayPrice().accountsPartial({ market: m.market }).rpc();
+
+    const market = await program.account.market.fetch(m.market);
+    assert.equal(market.displayPrice.toNumber(), 1);
+  });
+});
@@ -0,0 +1,60 @@
+import * as anchor from "@coral-xyz/anchor";
+import NodeWallet from "@coral-xyz/anchor/dist/cjs/nodewallet";
+import { Program } from "@coral-xyz/anchor";
+import { Sebi } from "../target/types/sebi";
+import { getOrCreateAssociatedTokenAccount, TOKEN_PROGRAM_ID } from "@solana/spl-token";
+import { describe, it } from "node:test";
+import assert from "assert";
+import { setupMarket, createTrader, buy, withdraw, tokenBalance, expectError, programEvents, TestMarket, Trader } from "./utils";
+
+describe("emergency exit", () => {
+  anchor.setProvider(anchor.AnchorProvider.env());
+  const provider = anchor.getProvider() as anchor.AnchorProvider;
+  const admin = (provider.wallet as NodeWallet).payer;
+  const program = anchor.workspace.Sebi as Program<Sebi>;
+
+  const exit = (m: TestMarket, t: Trader, amount: number) =>
+    program.methods
+      .emergencyExit(new anchor.BN(amount))
+      .accountsPartial({
+        market: m.market,
+        holder: t.keypair.publicKey,
+        holderBond: t.bond,
+        holderUsdc: t.usdc,
+        vaultBond: m.vaultBond,
+        vaultUsdc: m.vaultUsdc,
+        tokenProgram: TOKEN_PROGRAM_ID,
+      })
+      .signers([t.keypair])
+      .rpc();
+
+  it("pays a pro-rata share of a short vault while paused", async () => {
+    // with selling off nothing backs the bonds, so the issuer can drain the vault
+    const m = await setupMarket(program, admin, new anchor.BN(1_000_000), { sellEnabled: false });
+    const trader = await createTrader(program, admin, m, 10_000_000);
+    await buy(program, m, trader, 10);
+    const treasury = await getOrCreateAssociatedTokenAccount(provider.connection, admin, m.usdcMint, admin.publicKey);
+    await withdraw(program, admin, m, treasury.address, 6_000_000, true);
+    await program.methods.pause().accountsPartial({ market: m.market, admin: admin.publicKey }).rpc();
+
+    await expectError(exit(m, trader, 4), "EmergencyExitDisabled");
+    await program.methods
+      .setEmergencyExitEnabled(true)
+      .accountsPartial({ market: m.market, admin: admin.publicKey })
+      .rpc();
+
+    // 4 USDC behind 10 bonds: 0.4 USDC a bond, for the first exit and the last
+    const sig = await exit(m, trader, 4);
+    assert.equal(await tokenBalance(program, trader.usdc), 1_600_000);
+    await exit(m, trader, 6);
+    assert.equal(await tokenBalance(program, trader.usdc), 4_000_000);
+    assert.equal(await tokenBalance(program, m.vaultUsdc), 0);
+    assert.equal(await tokenBalance(program, trader.bond), 0);
+
+    const [event] = (await programEvents(program, sig)).filter((e) => e.name === "emergencyExitEvent");
+    const data = event.data as { amount: anchor.BN; paid: anchor.BN; rate: anchor.BN };
+    assert.equal(data.amount.toNumber(), 4);
+    assert.equal(data.paid.toNumber(), 1_600_000);
+    assert.equal(data.rate.toNumber(), 400_000);
+  });
+});
@@ -0,0 +1,48 @@
+import * as anchor from "@coral-xyz/anchor";
+import NodeWallet from "@coral-xyz/anchor/dist/cjs/nodewallet";
+import { Program } from "@coral-xyz/anchor";
+import { Sebi } from "../target/types/sebi";
+import { describe, it } from "node:test";
+import assert from "assert";
+import { setupMarket, createTrader, buy, programEvents, expectError } from "./utils";
+
+describe("event verbosity", () => {
+  anchor.setProvider(anchor.AnchorProvider.env());
+  const provider = anchor.getProvider() as anchor.AnchorProvider;
+  const admin = (provider.wallet as NodeWallet).payer;
+  const program = anchor.workspace.Sebi as Program<Sebi>;
+
+  it("emits full, summary or no trade events per level", async () => {
+    const m = await setupMarket(program, admin, new anchor.BN(1_000_000));
+    const trader = await createTrader(program, admin, m, 10_000_000);
+    assert.deepEqual((await program.account.market.fetch(m.market)).eventVerbosity, { full: {} });
+
+    const setVerbosity = (eventVerbosity: Parameters<typeof program.methods.setEventVerbosity>[0]) =>
+      program.methods.setEventVerbosity(eventVerbosity).accountsPartial({ market: m.market, admin: admin.publicKey }).rpc();
+    const namesAfterBuy = async (amount: number) =>
+      (await programEvents(program, await buy(program, m, trader, amount))).map((e) => e.name);
+
+    assert.deepEqual(await namesAfterBuy(1), ["tradeEvent"]);
+
+    await setVerbosity({ minimal: {} });
+    const summary = await programEvents(program, await buy(program, m, trader, 2));
+    assert.deepEqual(summary.map((e) => e.name), ["tradeSummaryEvent"]);
+    assert.equal((summary[0].data as { amount: anchor.BN }).amount.toNumber(), 2);
+
+    await setVerbosity({ off: {} });
+    assert.deepEqual(await namesAfterBuy(3), []);
+  });
+
+  it("only the admin changes the level", async () => {
+    const m = await setupMarket(program, admin, new anchor.BN(1_000_000));
+    const trader = await createTrader(program, admin, m, 0);
+    await expectError(
+      program.methods
+        .setEventVerbosity({ off: {} })
+        .accountsPartial({ market: m.market, admin: trader.keypair.publicKey })
+        .signers([trader.keypair])
+        .rpc(),
+      "ConstraintHasOne"
+    );
+  });
+});
@@ -0,0 +1,36 @@
+import * as anchor from "@coral-xyz/anchor";
+import NodeWallet from "@coral-xyz/anchor/dist/cjs/nodewallet";
+import { Program } from "@coral-xyz/anchor";
+import { Sebi } from "../target/types/sebi";
+import { mintTo } from "@solana/spl-token";
+import { describe, it } from "node:test";
+import assert from "assert";
+import { setupMarket, createTrader, buy, expectError } from "./utils";
+
+describe("low inventory auto-pause", () => {
+  anchor.setProvider(anchor.AnchorProvider.env());
+  const provider = anchor.getProvider() as anchor.AnchorProvider;
+  const admin = (provider.wallet as NodeWallet).payer;
+  const program = anchor.workspace.Sebi as Program<Sebi>;
+
+  it("halts buying below the threshold and resumes after a restock", async () => {
+    const m = await setupMarket(program, admin, new anchor.BN(1_000), { bondSupply: 10 });
+    const trader = await createTrader(program, admin, m, 1_000_000);
+    await program.methods
+      .setLowInventoryThreshold(new anchor.BN(5))
+      .accountsPartial({ market: m.market, admin: admin.publicKey })
+      .rpc();
+
+    // 10 -> 4 crosses the threshold; the crossing buy itself still fills
+    await buy(program, m, trader, 6);
+    let market = await program.account.market.fetch(m.market);
+    assert.equal(market.lowInventoryPaused, true);
+
+    await expectError(buy(program, m, trader, 1), "LowInventory");
+
+    await mintTo(provider.connection, admin, m.bondMint, m.vaultBond, admin, 5);
+    await buy(program, m, trader, 1);
+    market = await program.account.market.fetch(m.market);
+    assert.equal(market.lowInventoryPaused, false);
+  });
+});
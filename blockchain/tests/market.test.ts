@@ -4,7 +4,8 @@ import { Program } from "@coral-xyz/anchor";
 import { Sebi } from "../target/types/sebi";
 import { createMint, getOrCreateAssociatedTokenAccount, mintTo, TOKEN_PROGRAM_ID } from "@solana/spl-token";
 import { Keypair, PublicKey, LAMPORTS_PER_SOL } from "@solana/web3.js";
-import { describe, it } from "node:test";
+import { before, describe, it } from "node:test";
+import { ensureConfig } from "./utils";
 
 describe("sebi market", () => {
   anchor.setProvider(anchor.AnchorProvider.env());
@@ -12,6 +13,12 @@ describe("sebi market", () => {
   const wallet = provider.wallet as NodeWallet;
   const admin = wallet.payer; // ✅ Keypair
   const program = anchor.workspace.Sebi as Program<Sebi>;
+  let config: PublicKey;
+
+  before(async () => {
+    // initializeMarket requires the program config; don't rely on another file creating it
+    config = await ensureConfig(program, admin);
+  });
 
   it("init market and trade", async () => {
     const connection = provider.connection;
@@ -36,13 +43,15 @@ describe("sebi market", () => {
     // initialize market
     const price_per_token = new anchor.BN(1_000_000);
     await program.methods
-      .initializeMarket(price_per_token)
+      .initializeMarket(price_per_token, 0, true, null, { perBaseUnit: {} }, new anchor.BN(0))
       .accounts({
         market: marketPda,
         bondMint,
         usdcMint,
         vaultBond: vaultBond.address,
         vaultUsdc: vaultUsdc.address,
+        config,
+        protocolStats: null,
         admin: admin.publicKey,
         systemProgram: anchor.web3.SystemProgram.programId,
         tokenProgram: TOKEN_PROGRAM_ID,
@@ -69,6 +78,15 @@ describe("sebi market", () => {
         buyerBond: buyerBond.address,
         vaultUsdc: vaultUsdc.address,
         vaultBond: vaultBond.address,
+        instructions: null,
+        protocolStats: null,
+        complianceProgram: null,
+        tradingDelegate: null,
+        relayerAuthorization: null,
+        investorPosition: null,
+        dailyStats: null,
+        notifyProgram: null,
+        bondMint: null,
         tokenProgram: TOKEN_PROGRAM_ID,
       })
       .signers([buyer])
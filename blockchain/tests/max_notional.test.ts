@@ -0,0 +1,49 @@
+import * as anchor from "@coral-xyz/anchor";
+import NodeWallet from "@coral-xyz/anchor/dist/cjs/nodewallet";
+import { Program } from "@coral-xyz/anchor";
+import { Sebi } from "../target/types/sebi";
+import { describe, it } from "node:test";
+import assert from "assert";
+import { setupMarket, createTrader, buy, sell, tokenBalance, expectError, TestMarket } from "./utils";
+
+describe("max notional", () => {
+  anchor.setProvider(anchor.AnchorProvider.env());
+  const provider = anchor.getProvider() as anchor.AnchorProvider;
+  const admin = (provider.wallet as NodeWallet).payer;
+  const program = anchor.workspace.Sebi as Program<Sebi>;
+
+  const setMaxNotional = (m: TestMarket, max: number) =>
+    program.methods
+      .setMaxNotional(new anchor.BN(max))
+      .accountsPartial({ market: m.market, admin: admin.publicKey })
+      .rpc();
+
+  it("caps trade value rather than bond count", async () => {
+    // 500 bonds at 0.01 USDC is worth less than 1 bond at 10 USDC
+    const cheap = await setupMarket(program, admin, new anchor.BN(10_000));
+    const pricey = await setupMarket(program, admin, new anchor.BN(10_000_000));
+    const cheapTrader = await createTrader(program, admin, cheap, 10_000_000);
+    const priceyTrader = await createTrader(program, admin, pricey, 20_000_000);
+    await setMaxNotional(cheap, 5_000_000);
+    await setMaxNotional(pricey, 5_000_000);
+
+    await buy(program, cheap, cheapTrader, 500);
+    await sell(program, cheap, cheapTrader, 500);
+    await expectError(buy(program, pricey, priceyTrader, 1), "NotionalTooLarge");
+    assert.equal(await tokenBalance(program, priceyTrader.bond), 0);
+  });
+
+  it("applies to sells and treats 0 as disabled", async () => {
+    const m = await setupMarket(program, admin, new anchor.BN(1_000_000));
+    const trader = await createTrader(program, admin, m, 10_000_000);
+    await buy(program, m, trader, 5);
+
+    await setMaxNotional(m, 2_000_000);
+    await expectError(sell(program, m, trader, 3), "NotionalTooLarge");
+    await sell(program, m, trader, 2);
+
+    await setMaxNotional(m, 0);
+    await sell(program, m, trader, 3);
+    assert.equal(await tokenBalance(program, trader.bond), 0);
+  });
+});
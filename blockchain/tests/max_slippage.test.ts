@@ -0,0 +1,57 @@
+import * as anchor from "@coral-xyz/anchor";
+import NodeWallet from "@coral-xyz/anchor/dist/cjs/nodewallet";
+import { Program } from "@coral-xyz/anchor";
+import { Sebi } from "../target/types/sebi";
+import { describe, it } from "node:test";
+import assert from "assert";
+import { setupMarket, createTrader, buy, sell, tokenBalance, expectError, priceHistoryPda, TestMarket } from "./utils";
+
+describe("max slippage", () => {
+  anchor.setProvider(anchor.AnchorProvider.env());
+  const provider = anchor.getProvider() as anchor.AnchorProvider;
+  const admin = (provider.wallet as NodeWallet).payer;
+  const program = anchor.workspace.Sebi as Program<Sebi>;
+
+  const setMaxSlippage = (m: TestMarket, bps: number) =>
+    program.methods
+      .setMaxSlippageBps(bps)
+      .accountsPartial({ market: m.market, admin: admin.publicKey })
+      .rpc();
+
+  const updatePrice = (m: TestMarket, price: number) =>
+    program.methods
+      .updatePrice(new anchor.BN(price))
+      .accountsPartial({ market: m.market, priceHistory: priceHistoryPda(program, m.market), admin: admin.publicKey })
+      .rpc();
+
+  it("rejects trades a large price move carries past the bound", async () => {
+    const m = await setupMarket(program, admin, new anchor.BN(1_000_000));
+    const trader = await createTrader(program, admin, m, 10_000_000);
+    await setMaxSlippage(m, 500);
+    await buy(program, m, trader, 2);
+
+    // +5% is inside the bound and becomes the new reference
+    await updatePrice(m, 1_050_000);
+    await buy(program, m, trader, 1);
+    const market = await program.account.market.fetch(m.market);
+    assert.equal(market.lastTradePrice.toNumber(), 1_050_000);
+
+    // the plain buy/sell helpers pass no bound of their own
+    await updatePrice(m, 800_000);
+    await expectError(buy(program, m, trader, 1), "SlippageExceeded");
+    await expectError(sell(program, m, trader, 1), "SlippageExceeded");
+    assert.equal(await tokenBalance(program, trader.bond), 3);
+  });
+
+  it("treats 0 as disabled", async () => {
+    const m = await setupMarket(program, admin, new anchor.BN(1_000_000));
+    const trader = await createTrader(program, admin, m, 10_000_000);
+    await setMaxSlippage(m, 100);
+    await buy(program, m, trader, 1);
+    await setMaxSlippage(m, 0);
+
+    await updatePrice(m, 2_000_000);
+    await buy(program, m, trader, 1);
+    assert.equal(await tokenBalance(program, trader.bond), 2);
+  });
+});
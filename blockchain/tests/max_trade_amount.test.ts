@@ -0,0 +1,44 @@
+import * as anchor from "@coral-xyz/anchor";
+import NodeWallet from "@coral-xyz/anchor/dist/cjs/nodewallet";
+import { Program } from "@coral-xyz/anchor";
+import { Sebi } from "../target/types/sebi";
+import { describe, it } from "node:test";
+import assert from "assert";
+import { setupMarket, createTrader, buy, sell, tokenBalance, expectError } from "./utils";
+
+describe("max trade amount", () => {
+  anchor.setProvider(anchor.AnchorProvider.env());
+  const provider = anchor.getProvider() as anchor.AnchorProvider;
+  const admin = (provider.wallet as NodeWallet).payer;
+  const program = anchor.workspace.Sebi as Program<Sebi>;
+
+  const setMax = (market: anchor.web3.PublicKey, max: number) =>
+    program.methods
+      .setMaxTradeAmount(new anchor.BN(max))
+      .accountsPartial({ market, admin: admin.publicKey })
+      .rpc();
+
+  it("allows trades at the ceiling and rejects one unit above", async () => {
+    const m = await setupMarket(program, admin, new anchor.BN(1_000));
+    const trader = await createTrader(program, admin, m, 1_000_000);
+    await setMax(m.market, 5);
+
+    await buy(program, m, trader, 5);
+    await expectError(buy(program, m, trader, 6), "TradeTooLarge");
+
+    await buy(program, m, trader, 5);
+    await expectError(sell(program, m, trader, 6), "TradeTooLarge");
+    await sell(program, m, trader, 5);
+    assert.equal(await tokenBalance(program, trader.bond), 5);
+  });
+
+  it("treats 0 as unlimited", async () => {
+    const m = await setupMarket(program, admin, new anchor.BN(1_000));
+    const trader = await createTrader(program, admin, m, 1_000_000);
+    await setMax(m.market, 1);
+    await setMax(m.market, 0);
+
+    await buy(program, m, trader, 500);
+    assert.equal(await tokenBalance(program, trader.bond), 500);
+  });
+});
@@ -0,0 +1,40 @@
+import * as anchor from "@coral-xyz/anchor";
+import NodeWallet from "@coral-xyz/anchor/dist/cjs/nodewallet";
+import { Program } from "@coral-xyz/anchor";
+import { Sebi } from "../target/types/sebi";
+import { describe, it } from "node:test";
+import assert from "assert";
+import { setupMarket, expectError, priceHistoryPda } from "./utils";
+
+// converting a v0 account is covered by the unit tests in state.rs; the
+// program can no longer create one on a local validator
+describe("migrate market", () => {
+  anchor.setProvider(anchor.AnchorProvider.env());
+  const provider = anchor.getProvider() as anchor.AnchorProvider;
+  const admin = (provider.wallet as NodeWallet).payer;
+  const program = anchor.workspace.Sebi as Program<Sebi>;
+
+  it("stamps new markets with the current layout version", async () => {
+    const m = await setupMarket(program, admin, new anchor.BN(1_000_000));
+    const info = await provider.connection.getAccountInfo(m.market);
+    assert.equal(info!.data[9], (await program.account.market.fetch(m.market)).version);
+    assert.equal(info!.data.length, program.account.market.size);
+  });
+
+  it("leaves current markets alone", async () => {
+    const m = await setupMarket(program, admin, new anchor.BN(1_000_000));
+    await expectError(
+      program.methods
+        .migrateMarket()
+        .accountsPartial({
+          market: m.market,
+          priceHistory: priceHistoryPda(program, m.market),
+          bondMint: m.bondMint,
+          vaultBond: m.vaultBond,
+          admin: admin.publicKey,
+        })
+        .rpc(),
+      "AlreadyMigrated"
+    );
+  });
+});
@@ -0,0 +1,62 @@
+import * as anchor from "@coral-xyz/anchor";
+import NodeWallet from "@coral-xyz/anchor/dist/cjs/nodewallet";
+import { Program } from "@coral-xyz/anchor";
+import { Sebi } from "../target/types/sebi";
+import { createMint, getOrCreateAssociatedTokenAccount } from "@solana/spl-token";
+import { Keypair, PublicKey } from "@solana/web3.js";
+import { describe, it } from "node:test";
+import assert from "assert";
+import { setupMarket, createTrader, buy, withdraw, tokenBalance, expectError, configPda, TestMarket } from "./utils";
+
+describe("migrate quote mint", () => {
+  anchor.setProvider(anchor.AnchorProvider.env());
+  const provider = anchor.getProvider() as anchor.AnchorProvider;
+  const admin = (provider.wallet as NodeWallet).payer;
+  const program = anchor.workspace.Sebi as Program<Sebi>;
+
+  const migrate = (m: TestMarket, newUsdcMint: PublicKey, newVault: Keypair) =>
+    program.methods
+      .migrateQuoteMint()
+      .accountsPartial({
+        market: m.market,
+        admin: admin.publicKey,
+        vaultUsdc: m.vaultUsdc,
+        newUsdcMint,
+        newVaultUsdc: newVault.publicKey,
+        config: configPda(program),
+      })
+      .signers([newVault])
+      .rpc();
+
+  it("moves an emptied market onto the new mint", async () => {
+    // without sells nothing backs the bonds, so the whole vault can be withdrawn
+    const m = await setupMarket(program, admin, new anchor.BN(1_000_000), { sellEnabled: false });
+    const trader = await createTrader(program, admin, m, 10_000_000);
+    await buy(program, m, trader, 3);
+    const newMint = await createMint(provider.connection, admin, admin.publicKey, null, 6);
+
+    await expectError(migrate(m, newMint, Keypair.generate()), "QuoteVaultNotEmpty");
+    const treasury = await getOrCreateAssociatedTokenAccount(provider.connection, admin, m.usdcMint, admin.publicKey);
+    await withdraw(program, admin, m, treasury.address, 3_000_000, true);
+
+    const newVault = Keypair.generate();
+    await migrate(m, newMint, newVault);
+    const market = await program.account.market.fetch(m.market);
+    assert.ok(market.usdcMint.equals(newMint));
+    assert.ok(market.vaultUsdc.equals(newVault.publicKey));
+    assert.equal(market.netBondsOut.toNumber(), 3);
+
+    // trading now settles in the new mint
+    const migrated = { ...m, usdcMint: newMint, vaultUsdc: newVault.publicKey };
+    const buyer = await createTrader(program, admin, migrated, 5_000_000);
+    await buy(program, migrated, buyer, 2);
+    assert.equal(await tokenBalance(program, newVault.publicKey), 2_000_000);
+    assert.equal(await tokenBalance(program, buyer.usdc), 3_000_000);
+  });
+
+  it("rejects a mint with different decimals", async () => {
+    const m = await setupMarket(program, admin, new anchor.BN(1_000_000));
+    const newMint = await createMint(provider.connection, admin, admin.publicKey, null, 9);
+    await expectError(migrate(m, newMint, Keypair.generate()), "QuoteDecimalsMismatch");
+  });
+});
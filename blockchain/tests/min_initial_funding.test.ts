@@ -0,0 +1,37 @@
+import * as anchor from "@coral-xyz/anchor";
+import NodeWallet from "@coral-xyz/anchor/dist/cjs/nodewallet";
+import { Program } from "@coral-xyz/anchor";
+import { Sebi } from "../target/types/sebi";
+import { mintTo } from "@solana/spl-token";
+import { describe, it } from "node:test";
+import assert from "assert";
+import { setupMarket, createTrader, buy, sell, tokenBalance, expectError } from "./utils";
+
+describe("min initial funding", () => {
+  anchor.setProvider(anchor.AnchorProvider.env());
+  const provider = anchor.getProvider() as anchor.AnchorProvider;
+  const admin = (provider.wallet as NodeWallet).payer;
+  const program = anchor.workspace.Sebi as Program<Sebi>;
+
+  it("holds sells until the vault is funded, then stays open", async () => {
+    const m = await setupMarket(program, admin, new anchor.BN(1_000_000));
+    const trader = await createTrader(program, admin, m, 10_000_000);
+    await program.methods
+      .setMinInitialFunding(new anchor.BN(5_000_000))
+      .accountsPartial({ market: m.market, admin: admin.publicKey })
+      .rpc();
+
+    // buys are unaffected; their 3 USDC alone doesn't reach the minimum
+    await buy(program, m, trader, 3);
+    await expectError(sell(program, m, trader, 1), "MarketNotFunded");
+
+    await mintTo(provider.connection, admin, m.usdcMint, m.vaultUsdc, admin, 2_000_000);
+    await sell(program, m, trader, 1);
+    assert.ok((await program.account.market.fetch(m.market)).funded);
+
+    // the vault is back under 5 USDC, but the market has been funded once
+    assert.equal(await tokenBalance(program, m.vaultUsdc), 4_000_000);
+    await sell(program, m, trader, 1);
+    assert.equal(await tokenBalance(program, trader.bond), 1);
+  });
+});
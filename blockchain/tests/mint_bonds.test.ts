@@ -0,0 +1,49 @@
+import * as anchor from "@coral-xyz/anchor";
+import NodeWallet from "@coral-xyz/anchor/dist/cjs/nodewallet";
+import { Program } from "@coral-xyz/anchor";
+import { Sebi } from "../target/types/sebi";
+import { createMint, TOKEN_PROGRAM_ID } from "@solana/spl-token";
+import { Keypair, PublicKey } from "@solana/web3.js";
+import { describe, it } from "node:test";
+import assert from "assert";
+import { setupMarket, tokenBalance, expectError, TestMarket } from "./utils";
+
+describe("mint bonds to vault", () => {
+  anchor.setProvider(anchor.AnchorProvider.env());
+  const provider = anchor.getProvider() as anchor.AnchorProvider;
+  const admin = (provider.wallet as NodeWallet).payer;
+  const program = anchor.workspace.Sebi as Program<Sebi>;
+
+  const mintBonds = (m: TestMarket, amount: number) =>
+    program.methods
+      .mintBondsToVault(new anchor.BN(amount))
+      .accountsPartial({
+        market: m.market,
+        admin: admin.publicKey,
+        bondMint: m.bondMint,
+        vaultBond: m.vaultBond,
+        tokenProgram: TOKEN_PROGRAM_ID,
+      })
+      .rpc();
+
+  it("mints into the vault when the market PDA is the mint authority", async () => {
+    // the market PDA is seeded by the mint, so fix the mint key first
+    const mintKeypair = Keypair.generate();
+    const [market] = PublicKey.findProgramAddressSync(
+      [Buffer.from("market"), mintKeypair.publicKey.toBuffer()],
+      program.programId
+    );
+    const bondMint = await createMint(provider.connection, admin, market, null, 0, mintKeypair);
+    const m = await setupMarket(program, admin, new anchor.BN(1_000_000), { bondMint, bondSupply: 0 });
+
+    await mintBonds(m, 250);
+    await mintBonds(m, 50);
+    assert.equal(await tokenBalance(program, m.vaultBond), 300);
+    assert.equal((await program.account.market.fetch(m.market)).totalIssued.toNumber(), 300);
+  });
+
+  it("rejects markets that do not hold the mint authority", async () => {
+    const m = await setupMarket(program, admin, new anchor.BN(1_000_000));
+    await expectError(mintBonds(m, 1), "NotMintAuthority");
+  });
+});
@@ -0,0 +1,52 @@
+import * as anchor from "@coral-xyz/anchor";
+import NodeWallet from "@coral-xyz/anchor/dist/cjs/nodewallet";
+import { Program } from "@coral-xyz/anchor";
+import { Sebi } from "../target/types/sebi";
+import { Keypair, PublicKey } from "@solana/web3.js";
+import { describe, it } from "node:test";
+import assert from "assert";
+import { setupMarket, createTrader, buy, sell, tokenBalance, expectError, TestMarket } from "./utils";
+
+describe("trade notify", () => {
+  anchor.setProvider(anchor.AnchorProvider.env());
+  const provider = anchor.getProvider() as anchor.AnchorProvider;
+  const admin = (provider.wallet as NodeWallet).payer;
+  const program = anchor.workspace.Sebi as Program<Sebi>;
+
+  const setNotify = (m: TestMarket, notifyProgram: PublicKey, required: boolean) =>
+    program.methods
+      .setNotifyProgram(notifyProgram, required)
+      .accountsPartial({ market: m.market, admin: admin.publicKey })
+      .rpc();
+
+  // delivering a notification needs a deployed on_trade callback; these
+  // cover whether trades go ahead when the callback is left out
+  it("skips an optional notification the caller omits", async () => {
+    const m = await setupMarket(program, admin, new anchor.BN(1_000_000));
+    const trader = await createTrader(program, admin, m, 10_000_000);
+    await setNotify(m, Keypair.generate().publicKey, false);
+
+    await buy(program, m, trader, 2);
+    await sell(program, m, trader, 1);
+    assert.equal(await tokenBalance(program, trader.bond), 1);
+  });
+
+  it("rejects trades that omit a required notification", async () => {
+    const m = await setupMarket(program, admin, new anchor.BN(1_000_000));
+    const trader = await createTrader(program, admin, m, 10_000_000);
+    await buy(program, m, trader, 2);
+    await setNotify(m, Keypair.generate().publicKey, true);
+
+    await expectError(buy(program, m, trader, 1), "NotifyFailed");
+    await expectError(sell(program, m, trader, 1), "NotifyFailed");
+
+    await setNotify(m, PublicKey.default, true);
+    await buy(program, m, trader, 1);
+    assert.equal(await tokenBalance(program, trader.bond), 3);
+  });
+
+  it("refuses this program as its own notify target", async () => {
+    const m = await setupMarket(program, admin, new anchor.BN(1_000_000));
+    await expectError(setNotify(m, program.programId, false), "InvalidNotifyProgram");
+  });
+});
@@ -0,0 +1,122 @@
+import * as anchor from "@coral-xyz/anchor";
+import NodeWallet from "@coral-xyz/anchor/dist/cjs/nodewallet";
+import { Program } from "@coral-xyz/anchor";
+import { Sebi } from "../target/types/sebi";
+import { TOKEN_PROGRAM_ID } from "@solana/spl-token";
+import { Ed25519Program, Keypair, PublicKey, SYSVAR_INSTRUCTIONS_PUBKEY } from "@solana/web3.js";
+import { describe, it } from "node:test";
+import assert from "assert";
+import { setupMarket, createTrader, tokenBalance, tradeEvents, expectError, TestMarket, Trader } from "./utils";
+
+// borsh PriceAttestation { market: Pubkey, price: u128, expiry: i64 }
+function attestation(market: PublicKey, price: number, expiry: number): Buffer {
+  return Buffer.concat([
+    market.toBuffer(),
+    new anchor.BN(price).toArrayLike(Buffer, "le", 16),
+    new anchor.BN(expiry).toArrayLike(Buffer, "le", 8),
+  ]);
+}
+
+describe("price attestation", () => {
+  anchor.setProvider(anchor.AnchorProvider.env());
+  const provider = anchor.getProvider() as anchor.AnchorProvider;
+  const admin = (provider.wallet as NodeWallet).payer;
+  const program = anchor.workspace.Sebi as Program<Sebi>;
+
+  async function marketWithDesk() {
+    const m = await setupMarket(program, admin, new anchor.BN(1_000_000));
+    const desk = Keypair.generate();
+    await program.methods
+      .setPriceUpdater(desk.publicKey)
+      .accountsPartial({ market: m.market, admin: admin.publicKey })
+      .rpc();
+    const trader = await createTrader(program, admin, m, 10_000_000);
+    return { m, desk, trader };
+  }
+
+  const attestedBuy = (m: TestMarket, t: Trader, signer: Keypair, message: Buffer, amount: number) =>
+    program.methods
+      .buy(new anchor.BN(amount))
+      .accountsPartial({
+        market: m.market,
+        buyer: t.keypair.publicKey,
+        buyerUsdc: t.usdc,
+        buyerBond: t.bond,
+        vaultUsdc: m.vaultUsdc,
+        vaultBond: m.vaultBond,
+        instructions: SYSVAR_INSTRUCTIONS_PUBKEY,
+        protocolStats: null,
+        complianceProgram: null,
+        tradingDelegate: null,
+        relayerAuthorization: null,
+        investorPosition: null,
+        dailyStats: null,
+        notifyProgram: null,
+        bondMint: null,
+        tokenProgram: TOKEN_PROGRAM_ID,
+      })
+      .preInstructions([Ed25519Program.createInstructionWithPrivateKey({ privateKey: signer.secretKey, message })])
+      .signers([t.keypair])
+      .rpc();
+
+  const future = () => Math.floor(Date.now() / 1000) + 600;
+
+  it("trades at a signed, unexpired price", async () => {
+    const { m, desk, trader } = await marketWithDesk();
+    const sig = await attestedBuy(m, trader, desk, attestation(m.market, 1_250_000, future()), 2);
+    assert.equal(await tokenBalance(program, m.vaultUsdc), 2_500_000);
+
+    // the event reports the executed price alongside the stored one
+    const [event] = await tradeEvents(program, sig);
+    assert.equal(event.price.toNumber(), 1_250_000);
+    assert.equal(event.referencePrice.toNumber(), 1_000_000);
+
+    // the stored price is untouched
+    const market = await program.account.market.fetch(m.market);
+    assert.equal(market.pricePerToken.toNumber(), 1_000_000);
+  });
+
+  it("sells at a signed price", async () => {
+    const { m, desk, trader } = await marketWithDesk();
+    await attestedBuy(m, trader, desk, attestation(m.market, 1_000_000, future()), 2);
+    await program.methods
+      .sell(new anchor.BN(2))
+      .accountsPartial({
+        market: m.market,
+        seller: trader.keypair.publicKey,
+        sellerBond: trader.bond,
+        sellerUsdc: trader.usdc,
+        vaultBond: m.vaultBond,
+        vaultUsdc: m.vaultUsdc,
+        insuranceVault: null,
+        instructions: SYSVAR_INSTRUCTIONS_PUBKEY,
+        protocolStats: null,
+        complianceProgram: null,
+        tradingDelegate: null,
+        relayerAuthorization: null,
+        investorPosition: null,
+        dailyStats: null,
+        notifyProgram: null,
+        tokenProgram: TOKEN_PROGRAM_ID,
+      })
+      .preInstructions([
+        Ed25519Program.createInstructionWithPrivateKey({
+          privateKey: desk.secretKey,
+          message: attestation(m.market, 900_000, future()),
+        }),
+      ])
+      .signers([trader.keypair])
+      .rpc();
+    assert.equal(await tokenBalance(program, trader.usdc), 10_000_000 - 2_000_000 + 1_800_000);
+  });
+
+  it("rejects expired, foreign and mis-signed attestations", async () => {
+    const { m, desk, trader } = await marketWithDesk();
+    const past = Math.floor(Date.now() / 1000) - 600;
+
+    await expectError(attestedBuy(m, trader, desk, attestation(m.market, 1, past), 1), "AttestationExpired");
+    await expectError(attestedBuy(m, trader, Keypair.generate(), attestation(m.market, 1, future()), 1), "InvalidAttestation");
+    await expectError(attestedBuy(m, trader, desk, attestation(Keypair.generate().publicKey, 1, future()), 1), "InvalidAttestation");
+    assert.equal(await tokenBalance(program, trader.bond), 0);
+  });
+});
@@ -0,0 +1,39 @@
+import * as anchor from "@coral-xyz/anchor";
+import NodeWallet from "@coral-xyz/anchor/dist/cjs/nodewallet";
+import { Program } from "@coral-xyz/anchor";
+import { Sebi } from "../target/types/sebi";
+import { createMint } from "@solana/spl-token";
+import { describe, it } from "node:test";
+import assert from "assert";
+import { setupMarket, createTrader, buy, sell, tokenBalance } from "./utils";
+
+describe("price basis", () => {
+  anchor.setProvider(anchor.AnchorProvider.env());
+  const provider = anchor.getProvider() as anchor.AnchorProvider;
+  const admin = (provider.wallet as NodeWallet).payer;
+  const program = anchor.workspace.Sebi as Program<Sebi>;
+
+  // 6-decimal bond at 1000 USDC per whole bond, quoted both ways
+  async function spent(price: number, lotSize?: number) {
+    const bondMint = await createMint(provider.connection, admin, admin.publicKey, null, 6);
+    const m = await setupMarket(program, admin, new anchor.BN(price), {
+      bondMint,
+      bondSupply: 10_000_000,
+      lotSize,
+    });
+    const t = await createTrader(program, admin, m, 10_000_000_000);
+    await buy(program, m, t, 2_500_000);
+    const afterBuy = await tokenBalance(program, t.usdc);
+    await sell(program, m, t, 1_000_000);
+    const afterSell = await tokenBalance(program, t.usdc);
+    return { cost: 10_000_000_000 - afterBuy, proceeds: afterSell - afterBuy };
+  }
+
+  it("charges the same for a per-lot price as the equivalent per-unit price", async () => {
+    const perUnit = await spent(1_000);
+    const perLot = await spent(1_000_000_000, 1_000_000);
+    assert.deepStrictEqual(perLot, perUnit);
+    assert.strictEqual(perLot.cost, 2_500_000_000);
+    assert.strictEqual(perLot.proceeds, 1_000_000_000);
+  });
+});
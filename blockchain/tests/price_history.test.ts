@@ -0,0 +1,55 @@
+import * as anchor from "@coral-xyz/anchor";
+import NodeWallet from "@coral-xyz/anchor/dist/cjs/nodewallet";
+import { Program } from "@coral-xyz/anchor";
+import { Sebi } from "../target/types/sebi";
+import { describe, it } from "node:test";
+import assert from "assert";
+import { setupMarket, priceHistoryPda } from "./utils";
+
+const CAPACITY = 32;
+
+describe("price history", () => {
+  anchor.setProvider(anchor.AnchorProvider.env());
+  const provider = anchor.getProvider() as anchor.AnchorProvider;
+  const admin = (provider.wallet as NodeWallet).payer;
+  const program = anchor.workspace.Sebi as Program<Sebi>;
+
+  const updatePrice = (market: anchor.web3.PublicKey, price: number) =>
+    program.methods
+      .updatePrice(new anchor.BN(price))
+      .accountsPartial({ market, priceHistory: priceHistoryPda(program, market), admin: admin.publicKey })
+      .rpc();
+
+  const history = (market: anchor.web3.PublicKey) =>
+    program.methods
+      .getPriceHistory()
+      .accountsPartial({ market, priceHistory: priceHistoryPda(program, market) })
+      .view();
+
+  it("records the initial price and each update in order", async () => {
+    const m = await setupMarket(program, admin, new anchor.BN(100));
+    await updatePrice(m.market, 101);
+    await updatePrice(m.market, 102);
+
+    const points = await history(m.market);
+    assert.deepEqual(points.map((p: any) => p.price.toNumber()), [100, 101, 102]);
+    assert.ok(points[0].ts.toNumber() <= points[2].ts.toNumber());
+  });
+
+  it("keeps only the newest CAPACITY points after wrapping", async () => {
+    const m = await setupMarket(program, admin, new anchor.BN(100));
+    const updates = CAPACITY + 4;
+    for (let i = 1; i <= updates; i++) {
+      await updatePrice(m.market, 100 + i);
+    }
+
+    const points = await history(m.market);
+    assert.equal(points.length, CAPACITY);
+    // initial price plus the first 4 updates were overwritten
+    assert.equal(points[0].price.toNumber(), 100 + 5);
+    assert.equal(points[CAPACITY - 1].price.toNumber(), 100 + updates);
+
+    const account = await program.account.priceHistory.fetch(priceHistoryPda(program, m.market));
+    assert.equal(account.head, 5);
+  });
+});
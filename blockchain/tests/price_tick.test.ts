@@ -0,0 +1,46 @@
+import * as anchor from "@coral-xyz/anchor";
+import NodeWallet from "@coral-xyz/anchor/dist/cjs/nodewallet";
+import { Program } from "@coral-xyz/anchor";
+import { Sebi } from "../target/types/sebi";
+import { describe, it } from "node:test";
+import assert from "assert";
+import { setupMarket, expectError } from "./utils";
+
+describe("price tick", () => {
+  anchor.setProvider(anchor.AnchorProvider.env());
+  const provider = anchor.getProvider() as anchor.AnchorProvider;
+  const admin = (provider.wallet as NodeWallet).payer;
+  const program = anchor.workspace.Sebi as Program<Sebi>;
+
+  const updatePrice = (market: anchor.web3.PublicKey, price: number) =>
+    program.methods
+      .updatePrice(new anchor.BN(price))
+      .accountsPartial({ market, admin: admin.publicKey })
+      .rpc();
+
+  it("accepts prices on the tick grid and rejects prices off it", async () => {
+    const m = await setupMarket(program, admin, new anchor.BN(1_000_000));
+    await program.methods
+      .setPriceTick(new anchor.BN(10_000))
+      .accountsPartial({ market: m.market, admin: admin.publicKey })
+      .rpc();
+
+    await updatePrice(m.market, 1_010_000);
+    await expectError(updatePrice(m.market, 1_010_001), "InvalidTick");
+    await expectError(updatePrice(m.market, 1_009_999), "InvalidTick");
+
+    const market = await program.account.market.fetch(m.market);
+    assert.equal(market.pricePerToken.toString(), "1010000");
+  });
+
+  it("treats a tick of 0 or 1 as unconstrained", async () => {
+    const m = await setupMarket(program, admin, new anchor.BN(1_000_000));
+    await updatePrice(m.market, 1_000_001);
+
+    await program.methods
+      .setPriceTick(new anchor.BN(1))
+      .accountsPartial({ market: m.market, admin: admin.publicKey })
+      .rpc();
+    await updatePrice(m.market, 999_999);
+  });
+});
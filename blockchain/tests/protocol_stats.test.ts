@@ -0,0 +1,87 @@
+import * as anchor from "@coral-xyz/anchor";
+import NodeWallet from "@coral-xyz/anchor/dist/cjs/nodewallet";
+import { Program } from "@coral-xyz/anchor";
+import { Sebi } from "../target/types/sebi";
+import { TOKEN_PROGRAM_ID } from "@solana/spl-token";
+import { describe, it } from "node:test";
+import assert from "assert";
+import { setupMarket, createTrader, buy, ensureConfig, protocolStatsPda, tokenBalance } from "./utils";
+
+describe("protocol stats", () => {
+  anchor.setProvider(anchor.AnchorProvider.env());
+  const provider = anchor.getProvider() as anchor.AnchorProvider;
+  const admin = (provider.wallet as NodeWallet).payer;
+  const program = anchor.workspace.Sebi as Program<Sebi>;
+  const protocolStats = protocolStatsPda(program);
+
+  async function ensureStats() {
+    if (!(await program.account.protocolStats.fetchNullable(protocolStats))) {
+      const config = await ensureConfig(program, admin);
+      await program.methods
+        .initProtocolStats()
+        .accountsPartial({ protocolStats, config, authority: admin.publicKey })
+        .rpc();
+    }
+    return program.methods.getProtocolStats().accountsPartial({ protocolStats }).view();
+  }
+
+  it("counts markets and trades only when the stats account is passed", async () => {
+    const before = await ensureStats();
+
+    const m = await setupMarket(program, admin, new anchor.BN(1_000_000), { protocolStats });
+    const trader = await createTrader(program, admin, m, 10_000_000);
+    await program.methods
+      .buy(new anchor.BN(3))
+      .accountsPartial({
+        market: m.market,
+        buyer: trader.keypair.publicKey,
+        buyerUsdc: trader.usdc,
+        buyerBond: trader.bond,
+        vaultUsdc: m.vaultUsdc,
+        vaultBond: m.vaultBond,
+        instructions: null,
+        protocolStats,
+        complianceProgram: null,
+        tradingDelegate: null,
+        relayerAuthorization: null,
+        investorPosition: null,
+        dailyStats: null,
+        notifyProgram: null,
+        bondMint: null,
+        tokenProgram: TOKEN_PROGRAM_ID,
+      })
+      .signers([trader.keypair])
+      .rpc();
+    await program.methods
+      .sell(new anchor.BN(1))
+      .accountsPartial({
+        market: m.market,
+        seller: trader.keypair.publicKey,
+        sellerBond: trader.bond,
+        sellerUsdc: trader.usdc,
+        vaultBond: m.vaultBond,
+        vaultUsdc: m.vaultUsdc,
+        insuranceVault: null,
+        instructions: null,
+        protocolStats,
+        complianceProgram: null,
+        tradingDelegate: null,
+        relayerAuthorization: null,
+        investorPosition: null,
+        dailyStats: null,
+        notifyProgram: null,
+        tokenProgram: TOKEN_PROGRAM_ID,
+      })
+      .signers([trader.keypair])
+      .rpc();
+
+    // trading without the account still works and leaves the totals alone
+    await buy(program, m, trader, 1);
+    assert.equal(await tokenBalance(program, trader.bond), 3);
+
+    const after = await program.methods.getProtocolStats().accountsPartial({ protocolStats }).view();
+    assert.equal(after.marketsCreated.sub(before.marketsCreated).toNumber(), 1);
+    assert.equal(after.tradeCount.sub(before.tradeCount).toNumber(), 2);
+    assert.equal(after.totalVolume.sub(before.totalVolume).toNumber(), 4_000_000);
+  });
+});
@@ -0,0 +1,42 @@
+import * as anchor from "@coral-xyz/anchor";
+import NodeWallet from "@coral-xyz/anchor/dist/cjs/nodewallet";
+import { Program } from "@coral-xyz/anchor";
+import { Sebi } from "../target/types/sebi";
+import { createMint } from "@solana/spl-token";
+import { PublicKey } from "@solana/web3.js";
+import { describe, it } from "node:test";
+import { setupMarket, ensureConfig, expectError } from "./utils";
+
+describe("quote mint allowlist", () => {
+  anchor.setProvider(anchor.AnchorProvider.env());
+  const provider = anchor.getProvider() as anchor.AnchorProvider;
+  const admin = (provider.wallet as NodeWallet).payer;
+  const program = anchor.workspace.Sebi as Program<Sebi>;
+
+  const setApproval = async (mint: PublicKey, approved: boolean) =>
+    program.methods
+      .setQuoteMintApproval(mint, approved)
+      .accountsPartial({ config: await ensureConfig(program, admin), authority: admin.publicKey })
+      .rpc();
+
+  it("allows any quote mint while the list is empty", async () => {
+    await setupMarket(program, admin, new anchor.BN(1_000_000));
+  });
+
+  it("accepts approved quote mints and rejects others", async () => {
+    const usdc = await createMint(provider.connection, admin, admin.publicKey, null, 6);
+    const counterfeit = await createMint(provider.connection, admin, admin.publicKey, null, 6);
+
+    await setApproval(usdc, true);
+    try {
+      await setupMarket(program, admin, new anchor.BN(1_000_000), { usdcMint: usdc });
+      await expectError(
+        setupMarket(program, admin, new anchor.BN(1_000_000), { usdcMint: counterfeit }),
+        "UnapprovedQuoteMint"
+      );
+    } finally {
+      // other suites create markets against fresh mints
+      await setApproval(usdc, false);
+    }
+  });
+});
@@ -0,0 +1,63 @@
+import * as anchor from "@coral-xyz/anchor";
+import NodeWallet from "@coral-xyz/anchor/dist/cjs/nodewallet";
+import { Program } from "@coral-xyz/anchor";
+import { Sebi } from "../target/types/sebi";
+import { describe, it } from "node:test";
+import assert from "assert";
+import { setupMarket, createTrader, buy, sell } from "./utils";
+
+describe("realized pnl", () => {
+  anchor.setProvider(anchor.AnchorProvider.env());
+  const provider = anchor.getProvider() as anchor.AnchorProvider;
+  const admin = (provider.wallet as NodeWallet).payer;
+  const program = anchor.workspace.Sebi as Program<Sebi>;
+
+  async function setPrice(market: anchor.web3.PublicKey, price: number) {
+    await program.methods
+      .updatePrice(new anchor.BN(price))
+      .accountsPartial({ market, admin: admin.publicKey })
+      .rpc();
+  }
+
+  it("is flat after a round trip at an unchanged price", async () => {
+    const m = await setupMarket(program, admin, new anchor.BN(1_000_000));
+    const trader = await createTrader(program, admin, m, 10_000_000);
+
+    await buy(program, m, trader, 2);
+    let market = await program.account.market.fetch(m.market);
+    assert.equal(market.realizedPnl.toString(), "0");
+
+    await sell(program, m, trader, 2);
+    market = await program.account.market.fetch(m.market);
+    assert.equal(market.netBondsOut.toString(), "0");
+    assert.equal(market.netQuoteFlow.toString(), "0");
+    assert.equal(market.realizedPnl.toString(), "0");
+  });
+
+  it("captures the spread when buying back below the sale price", async () => {
+    const m = await setupMarket(program, admin, new anchor.BN(1_000_000));
+    const trader = await createTrader(program, admin, m, 10_000_000);
+
+    await buy(program, m, trader, 2);
+    await setPrice(m.market, 900_000);
+    await sell(program, m, trader, 2);
+
+    const market = await program.account.market.fetch(m.market);
+    assert.equal(market.netQuoteFlow.toString(), "200000");
+    assert.equal(market.realizedPnl.toString(), "200000");
+  });
+
+  it("goes negative when buying back above the sale price", async () => {
+    const m = await setupMarket(program, admin, new anchor.BN(1_000_000));
+    const trader = await createTrader(program, admin, m, 10_000_000);
+
+    await buy(program, m, trader, 3);
+    await setPrice(m.market, 1_100_000);
+    await sell(program, m, trader, 1);
+
+    // 3_000_000 in, 1_100_000 out, 2 bonds still out valued at 1_100_000
+    const market = await program.account.market.fetch(m.market);
+    assert.equal(market.netBondsOut.toString(), "2");
+    assert.equal(market.realizedPnl.toString(), "-300000");
+  });
+});
@@ -0,0 +1,119 @@
+import * as anchor from "@coral-xyz/anchor";
+import NodeWallet from "@coral-xyz/anchor/dist/cjs/nodewallet";
+import { Program } from "@coral-xyz/anchor";
+import { Sebi } from "../target/types/sebi";
+import { getOrCreateAssociatedTokenAccount, mintTo, TOKEN_PROGRAM_ID } from "@solana/spl-token";
+import { PublicKey } from "@solana/web3.js";
+import { describe, it } from "node:test";
+import assert from "assert";
+import { setupMarket, createTrader, buy, tokenBalance, expectError, withdraw, TestMarket, Trader } from "./utils";
+
+describe("pro-rata redemption", () => {
+  anchor.setProvider(anchor.AnchorProvider.env());
+  const provider = anchor.getProvider() as anchor.AnchorProvider;
+  const admin = (provider.wallet as NodeWallet).payer;
+  const program = anchor.workspace.Sebi as Program<Sebi>;
+
+  const windowPda = (m: TestMarket) =>
+    PublicKey.findProgramAddressSync([Buffer.from("redemption"), m.market.toBuffer()], program.programId)[0];
+  const claimPda = (m: TestMarket, t: Trader) =>
+    PublicKey.findProgramAddressSync(
+      [Buffer.from("redemption_claim"), m.market.toBuffer(), t.keypair.publicKey.toBuffer()],
+      program.programId
+    )[0];
+
+  const register = (m: TestMarket, t: Trader, amount: number) =>
+    program.methods
+      .registerRedemption(new anchor.BN(amount))
+      .accountsPartial({
+        market: m.market,
+        redemptionWindow: windowPda(m),
+        claim: claimPda(m, t),
+        holder: t.keypair.publicKey,
+        holderBond: t.bond,
+        vaultBond: m.vaultBond,
+        tokenProgram: TOKEN_PROGRAM_ID,
+      })
+      .signers([t.keypair])
+      .rpc();
+
+  const settle = (m: TestMarket, holders: Trader[]) =>
+    program.methods
+      .settleRedemptions()
+      .accountsPartial({ market: m.market, redemptionWindow: windowPda(m), vaultUsdc: m.vaultUsdc, tokenProgram: TOKEN_PROGRAM_ID })
+      .remainingAccounts(
+        holders.flatMap((t) => [
+          { pubkey: claimPda(m, t), isSigner: false, isWritable: true },
+          { pubkey: t.usdc, isSigner: false, isWritable: true },
+        ])
+      )
+      .rpc();
+
+  it("shares an undercollateralized vault pro rata at window close", async () => {
+    const m = await setupMarket(program, admin, new anchor.BN(1_000_000));
+    const a = await createTrader(program, admin, m, 3_000_000);
+    const b = await createTrader(program, admin, m, 1_000_000);
+    await buy(program, m, a, 3);
+    await buy(program, m, b, 1);
+
+    await program.methods.setPhase({ matured: {} }).accountsPartial({ market: m.market, admin: admin.publicKey }).rpc();
+    // face value of 2 USDC against 4 USDC in the vault: 8 USDC owed, half covered
+    await program.methods
+      .openRedemption(new anchor.BN(2_000_000), new anchor.BN(3))
+      .accountsPartial({ market: m.market, redemptionWindow: windowPda(m), admin: admin.publicKey })
+      .rpc();
+
+    await register(m, a, 2);
+    await register(m, a, 1);
+    await register(m, b, 1);
+    await expectError(settle(m, [a, b]), "RedemptionWindowOpen");
+    // the size cap is checked first; a repeated claim keeps the transaction small
+    await expectError(settle(m, new Array(17).fill(a)), "BatchTooLarge");
+    const treasury = await getOrCreateAssociatedTokenAccount(provider.connection, admin, m.usdcMint, admin.publicKey);
+    await expectError(withdraw(program, admin, m, treasury.address, 1, true), "RedemptionClaimsOutstanding");
+
+    await new Promise((r) => setTimeout(r, 4_000));
+    await expectError(register(m, b, 0), "RedemptionWindowClosed");
+
+    await settle(m, [a]);
+    await expectError(settle(m, [a]), "RedemptionAlreadyPaid");
+    assert.equal((await program.account.market.fetch(m.market)).redemptionPending, true);
+    await settle(m, [b]);
+
+    assert.equal(await tokenBalance(program, a.usdc), 3_000_000);
+    assert.equal(await tokenBalance(program, b.usdc), 1_000_000);
+    assert.equal(await tokenBalance(program, m.vaultUsdc), 0);
+    assert.equal(await tokenBalance(program, m.vaultBond), 1000);
+
+    const market = await program.account.market.fetch(m.market);
+    assert.equal(market.redemptionPending, false);
+    assert.equal(market.netBondsOut.toNumber(), 0);
+  });
+
+  it("lets the admin withdraw only the surplus over pending claims", async () => {
+    // sells off, so outstanding bonds need no buyback backing
+    const m = await setupMarket(program, admin, new anchor.BN(1_000_000), { sellEnabled: false });
+    const a = await createTrader(program, admin, m, 3_000_000);
+    await buy(program, m, a, 3);
+    await mintTo(provider.connection, admin, m.usdcMint, m.vaultUsdc, admin, 5_000_000);
+
+    await program.methods.setPhase({ matured: {} }).accountsPartial({ market: m.market, admin: admin.publicKey }).rpc();
+    await program.methods
+      .openRedemption(new anchor.BN(1_000_000), new anchor.BN(3))
+      .accountsPartial({ market: m.market, redemptionWindow: windowPda(m), admin: admin.publicKey })
+      .rpc();
+    await register(m, a, 3);
+    assert.equal((await program.account.market.fetch(m.market)).totalPendingRedemption.toNumber(), 3_000_000);
+
+    const treasury = await getOrCreateAssociatedTokenAccount(provider.connection, admin, m.usdcMint, admin.publicKey);
+    const before = await tokenBalance(program, treasury.address);
+    await expectError(withdraw(program, admin, m, treasury.address, 6_000_000, true), "RedemptionClaimsOutstanding");
+    await withdraw(program, admin, m, treasury.address, 5_000_000, true);
+    assert.equal(await tokenBalance(program, treasury.address) - before, 5_000_000);
+
+    await new Promise((r) => setTimeout(r, 4_000));
+    await settle(m, [a]);
+    assert.equal(await tokenBalance(program, a.usdc), 3_000_000);
+    assert.equal((await program.account.market.fetch(m.market)).totalPendingRedemption.toNumber(), 0);
+  });
+});
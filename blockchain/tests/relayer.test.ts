@@ -0,0 +1,126 @@
+import * as anchor from "@coral-xyz/anchor";
+import NodeWallet from "@coral-xyz/anchor/dist/cjs/nodewallet";
+import { Program } from "@coral-xyz/anchor";
+import { Sebi } from "../target/types/sebi";
+import { approve, TOKEN_PROGRAM_ID } from "@solana/spl-token";
+import { Keypair, PublicKey } from "@solana/web3.js";
+import { describe, it } from "node:test";
+import assert from "assert";
+import { setupMarket, createTrader, tokenBalance, expectError, TestMarket, Trader } from "./utils";
+
+describe("relayer authorization", () => {
+  anchor.setProvider(anchor.AnchorProvider.env());
+  const provider = anchor.getProvider() as anchor.AnchorProvider;
+  const admin = (provider.wallet as NodeWallet).payer;
+  const program = anchor.workspace.Sebi as Program<Sebi>;
+
+  const relayerPda = (m: TestMarket, owner: PublicKey) =>
+    PublicKey.findProgramAddressSync(
+      [Buffer.from("relayer"), m.market.toBuffer(), owner.toBuffer()],
+      program.programId
+    )[0];
+
+  const authorize = (m: TestMarket, owner: Trader, relayer: PublicKey, maxNotional: number, expiry: number) =>
+    program.methods
+      .authorizeRelayer(relayer, new anchor.BN(maxNotional), new anchor.BN(expiry))
+      .accountsPartial({ market: m.market, relayerAuthorization: relayerPda(m, owner.keypair.publicKey), owner: owner.keypair.publicKey })
+      .signers([owner.keypair])
+      .rpc();
+
+  const relayBuy = (m: TestMarket, owner: Trader, relayer: Keypair, amount: number) =>
+    program.methods
+      .buy(new anchor.BN(amount))
+      .accountsPartial({
+        market: m.market,
+        buyer: relayer.publicKey,
+        buyerUsdc: owner.usdc,
+        buyerBond: owner.bond,
+        vaultUsdc: m.vaultUsdc,
+        vaultBond: m.vaultBond,
+        instructions: null,
+        protocolStats: null,
+        complianceProgram: null,
+        tradingDelegate: null,
+        relayerAuthorization: relayerPda(m, owner.keypair.publicKey),
+        investorPosition: null,
+        dailyStats: null,
+        notifyProgram: null,
+        bondMint: null,
+        tokenProgram: TOKEN_PROGRAM_ID,
+      })
+      .signers([relayer])
+      .rpc();
+
+  const relaySell = (m: TestMarket, owner: Trader, relayer: Keypair, amount: number) =>
+    program.methods
+      .sell(new anchor.BN(amount))
+      .accountsPartial({
+        market: m.market,
+        seller: relayer.publicKey,
+        sellerBond: owner.bond,
+        sellerUsdc: owner.usdc,
+        vaultBond: m.vaultBond,
+        vaultUsdc: m.vaultUsdc,
+        insuranceVault: null,
+        instructions: null,
+        protocolStats: null,
+        complianceProgram: null,
+        tradingDelegate: null,
+        relayerAuthorization: relayerPda(m, owner.keypair.publicKey),
+        investorPosition: null,
+        dailyStats: null,
+        notifyProgram: null,
+        tokenProgram: TOKEN_PROGRAM_ID,
+      })
+      .signers([relayer])
+      .rpc();
+
+  const inAnHour = () => Math.floor(Date.now() / 1000) + 3600;
+
+  async function relayed(maxNotional: number, expiry: number) {
+    const m = await setupMarket(program, admin, new anchor.BN(1_000_000));
+    const owner = await createTrader(program, admin, m, 10_000_000);
+    const relayer = Keypair.generate();
+    await authorize(m, owner, relayer.publicKey, maxNotional, expiry);
+    await approve(provider.connection, admin, owner.usdc, relayer.publicKey, owner.keypair, 10_000_000);
+    await approve(provider.connection, admin, owner.bond, relayer.publicKey, owner.keypair, 10);
+    return { m, owner, relayer };
+  }
+
+  it("debits buys and sells from the allowance", async () => {
+    const { m, owner, relayer } = await relayed(5_000_000, inAnHour());
+    await relayBuy(m, owner, relayer, 3);
+    await relaySell(m, owner, relayer, 1);
+    assert.equal(await tokenBalance(program, owner.bond), 2);
+    assert.equal(await tokenBalance(program, owner.usdc), 8_000_000);
+
+    const auth = await program.account.relayerAuthorization.fetch(relayerPda(m, owner.keypair.publicKey));
+    assert.equal(auth.maxNotional.toNumber(), 5_000_000);
+    assert.equal(auth.remainingNotional.toNumber(), 1_000_000);
+  });
+
+  it("rejects relays past the allowance", async () => {
+    const { m, owner, relayer } = await relayed(2_000_000, inAnHour());
+    await expectError(relayBuy(m, owner, relayer, 3), "RelayLimitExceeded");
+    await relayBuy(m, owner, relayer, 2);
+    await expectError(relayBuy(m, owner, relayer, 1), "RelayLimitExceeded");
+    assert.equal(await tokenBalance(program, owner.bond), 2);
+
+    // only the named relayer can spend it
+    await expectError(relayBuy(m, owner, Keypair.generate(), 1), "NotDelegated");
+  });
+
+  it("rejects relays once the authorization expires", async () => {
+    const { m, owner, relayer } = await relayed(5_000_000, Math.floor(Date.now() / 1000) + 2);
+    await new Promise((r) => setTimeout(r, 4_000));
+    await expectError(relayBuy(m, owner, relayer, 1), "RelayExpired");
+
+    await program.methods
+      .revokeRelayer()
+      .accountsPartial({ market: m.market, relayerAuthorization: relayerPda(m, owner.keypair.publicKey), owner: owner.keypair.publicKey })
+      .signers([owner.keypair])
+      .rpc();
+    await expectError(relayBuy(m, owner, relayer, 1), "AccountNotInitialized");
+    assert.equal(await tokenBalance(program, owner.usdc), 10_000_000);
+  });
+});
@@ -0,0 +1,64 @@
+import * as anchor from "@coral-xyz/anchor";
+import NodeWallet from "@coral-xyz/anchor/dist/cjs/nodewallet";
+import { Program } from "@coral-xyz/anchor";
+import { Sebi } from "../target/types/sebi";
+import { mintTo } from "@solana/spl-token";
+import { describe, it } from "node:test";
+import assert from "assert";
+import { setupMarket, createTrader, buy, sell, priceHistoryPda, TestMarket } from "./utils";
+
+describe("required backing", () => {
+  anchor.setProvider(anchor.AnchorProvider.env());
+  const provider = anchor.getProvider() as anchor.AnchorProvider;
+  const admin = (provider.wallet as NodeWallet).payer;
+  const program = anchor.workspace.Sebi as Program<Sebi>;
+
+  const requiredBacking = async (m: TestMarket) =>
+    (await program.methods
+      .requiredBacking()
+      .accountsPartial({ market: m.market, vaultUsdc: m.vaultUsdc })
+      .view()).toNumber();
+
+  const updatePrice = (m: TestMarket, price: number) =>
+    program.methods
+      .updatePrice(new anchor.BN(price))
+      .accountsPartial({ market: m.market, priceHistory: priceHistoryPda(program, m.market), admin: admin.publicKey })
+      .rpc();
+
+  it("is zero with nothing outstanding", async () => {
+    const m = await setupMarket(program, admin, new anchor.BN(1_000_000));
+    assert.equal(await requiredBacking(m), 0);
+  });
+
+  it("tracks outstanding supply, the price and the vault balance", async () => {
+    const m = await setupMarket(program, admin, new anchor.BN(1_000_000));
+    const trader = await createTrader(program, admin, m, 10_000_000);
+    await buy(program, m, trader, 4);
+    // buys fund their own buyback at an unchanged price
+    assert.equal(await requiredBacking(m), 0);
+
+    // 4 bonds at 1.5 USDC need 6 USDC against the 4 the buys paid in
+    await updatePrice(m, 1_500_000);
+    assert.equal(await requiredBacking(m), 2_000_000);
+
+    await mintTo(provider.connection, admin, m.usdcMint, m.vaultUsdc, admin, 500_000);
+    assert.equal(await requiredBacking(m), 1_500_000);
+
+    // selling 2 back pays out 3 USDC and retires 3 USDC of obligation
+    await mintTo(provider.connection, admin, m.usdcMint, m.vaultUsdc, admin, 1_500_000);
+    await sell(program, m, trader, 2);
+    assert.equal(await requiredBacking(m), 0);
+
+    // a surplus never shows as a negative requirement
+    await updatePrice(m, 500_000);
+    assert.equal(await requiredBacking(m), 0);
+  });
+
+  it("needs nothing for markets that never buy back", async () => {
+    const m = await setupMarket(program, admin, new anchor.BN(1_000_000), { sellEnabled: false });
+    const trader = await createTrader(program, admin, m, 10_000_000);
+    await buy(program, m, trader, 4);
+    await updatePrice(m, 5_000_000);
+    assert.equal(await requiredBacking(m), 0);
+  });
+});
@@ -0,0 +1,144 @@
+import * as anchor from "@coral-xyz/anchor";
+import NodeWallet from "@coral-xyz/anchor/dist/cjs/nodewallet";
+import { Program } from "@coral-xyz/anchor";
+import { Sebi } from "../target/types/sebi";
+import { TOKEN_PROGRAM_ID } from "@solana/spl-token";
+import { describe, it } from "node:test";
+import assert from "assert";
+import { setupMarket, createTrader, buy, tokenBalance, expectError, priceHistoryPda, TestMarket, Trader } from "./utils";
+
+describe("requote and trade", () => {
+  anchor.setProvider(anchor.AnchorProvider.env());
+  const provider = anchor.getProvider() as anchor.AnchorProvider;
+  const admin = (provider.wallet as NodeWallet).payer;
+  const program = anchor.workspace.Sebi as Program<Sebi>;
+
+  const optional = {
+    instructions: null,
+    protocolStats: null,
+    complianceProgram: null,
+    tradingDelegate: null,
+    relayerAuthorization: null,
+    investorPosition: null,
+    dailyStats: null,
+    notifyProgram: null,
+    tokenProgram: TOKEN_PROGRAM_ID,
+  };
+
+  const requoteAndBuy = (m: TestMarket, t: Trader, price: number, amount: number) =>
+    program.methods
+      .requoteAndBuy(new anchor.BN(price), new anchor.BN(amount))
+      .accountsPartial({
+        trade: {
+          market: m.market,
+          buyer: t.keypair.publicKey,
+          buyerUsdc: t.usdc,
+          buyerBond: t.bond,
+          vaultUsdc: m.vaultUsdc,
+          vaultBond: m.vaultBond,
+          bondMint: null,
+          ...optional,
+        },
+        priceHistory: priceHistoryPda(program, m.market),
+      } as any)
+      .signers([t.keypair])
+      .rpc();
+
+  const requoteAndSell = (m: TestMarket, t: Trader, price: number, amount: number) =>
+    program.methods
+      .requoteAndSell(new anchor.BN(price), new anchor.BN(amount))
+      .accountsPartial({
+        trade: {
+          market: m.market,
+          seller: t.keypair.publicKey,
+          sellerBond: t.bond,
+          sellerUsdc: t.usdc,
+          vaultBond: m.vaultBond,
+          vaultUsdc: m.vaultUsdc,
+          insuranceVault: null,
+          ...optional,
+        },
+        priceHistory: priceHistoryPda(program, m.market),
+      } as any)
+      .signers([t.keypair])
+      .rpc();
+
+  const price = async (m: TestMarket) => (await program.account.market.fetch(m.market)).pricePerToken.toNumber();
+
+  const setPriceGuard = (m: TestMarket, bps: number, cooldownSecs: number) =>
+    program.methods
+      .setPriceGuard(bps, new anchor.BN(cooldownSecs))
+      .accountsPartial({ market: m.market, admin: admin.publicKey })
+      .rpc();
+
+  const pastCooldown = () => new Promise((r) => setTimeout(r, 1_500));
+
+  // requotes may move the price up to 200% once a second
+  async function withMarketMaker() {
+    const m = await setupMarket(program, admin, new anchor.BN(1_000_000));
+    const mm = await createTrader(program, admin, m, 10_000_000);
+    await program.methods
+      .setPriceUpdater(mm.keypair.publicKey)
+      .accountsPartial({ market: m.market, admin: admin.publicKey })
+      .rpc();
+    await setPriceGuard(m, 20_000, 1);
+    return { m, mm };
+  }
+
+  it("trades at the new price in the same instruction", async () => {
+    const { m, mm } = await withMarketMaker();
+    await requoteAndBuy(m, mm, 2_000_000, 2);
+    assert.equal(await price(m), 2_000_000);
+    assert.equal(await tokenBalance(program, mm.usdc), 6_000_000);
+
+    await pastCooldown();
+    await requoteAndSell(m, mm, 1_500_000, 2);
+    assert.equal(await price(m), 1_500_000);
+    assert.equal(await tokenBalance(program, mm.usdc), 9_000_000);
+
+    const points = await program.methods
+      .getPriceHistory()
+      .accountsPartial({ market: m.market, priceHistory: priceHistoryPda(program, m.market) })
+      .view();
+    assert.deepEqual(points.map((p: any) => p.price.toNumber()), [1_000_000, 2_000_000, 1_500_000]);
+  });
+
+  it("rejects anyone but the price updater", async () => {
+    const { m } = await withMarketMaker();
+    const other = await createTrader(program, admin, m, 10_000_000);
+    await expectError(requoteAndBuy(m, other, 500_000, 1), "Unauthorized");
+    await buy(program, m, other, 1);
+    await expectError(requoteAndSell(m, other, 2_000_000, 1), "Unauthorized");
+    assert.equal(await price(m), 1_000_000);
+  });
+
+  it("keeps the old price when the trade fails", async () => {
+    const { m, mm } = await withMarketMaker();
+    await requoteAndBuy(m, mm, 1_000_000, 1);
+    await pastCooldown();
+    // 1 USDC in the vault can't pay the sale at the new price
+    await expectError(requoteAndSell(m, mm, 3_000_000, 1), "InsufficientVaultFunds");
+    assert.equal(await price(m), 1_000_000);
+    assert.equal(await tokenBalance(program, mm.bond), 1);
+  });
+
+  it("requires and enforces the price change limit and cooldown", async () => {
+    const m = await setupMarket(program, admin, new anchor.BN(1_000_000));
+    const mm = await createTrader(program, admin, m, 10_000_000);
+    await program.methods
+      .setPriceUpdater(mm.keypair.publicKey)
+      .accountsPartial({ market: m.market, admin: admin.publicKey })
+      .rpc();
+
+    // a change limit alone still lets repeated requotes walk the price
+    await expectError(requoteAndBuy(m, mm, 1_040_000, 1), "PriceGuardRequired");
+    await setPriceGuard(m, 500, 0);
+    await expectError(requoteAndBuy(m, mm, 1_040_000, 1), "PriceGuardRequired");
+
+    await setPriceGuard(m, 500, 60);
+    await expectError(requoteAndBuy(m, mm, 50_000_000, 1), "PriceChangeTooLarge");
+    await requoteAndBuy(m, mm, 1_040_000, 1);
+    await expectError(requoteAndSell(m, mm, 1_090_000, 1), "PriceUpdateCooldown");
+    assert.equal(await price(m), 1_040_000);
+  });
+});
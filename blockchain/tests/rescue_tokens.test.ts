@@ -0,0 +1,93 @@
+import * as anchor from "@coral-xyz/anchor";
+import NodeWallet from "@coral-xyz/anchor/dist/cjs/nodewallet";
+import { Program } from "@coral-xyz/anchor";
+import { Sebi } from "../target/types/sebi";
+import { PublicKey } from "@solana/web3.js";
+import { createMint, getOrCreateAssociatedTokenAccount, mintTo } from "@solana/spl-token";
+import { describe, it } from "node:test";
+import assert from "assert";
+import { setupMarket, tokenBalance, expectError } from "./utils";
+
+describe("rescue tokens", () => {
+  anchor.setProvider(anchor.AnchorProvider.env());
+  const provider = anchor.getProvider() as anchor.AnchorProvider;
+  const admin = (provider.wallet as NodeWallet).payer;
+  const program = anchor.workspace.Sebi as Program<Sebi>;
+
+  it("returns a stray token balance held by the market PDA", async () => {
+    const m = await setupMarket(program, admin, new anchor.BN(1_000_000));
+    const connection = provider.connection;
+
+    const strayMint = await createMint(connection, admin, admin.publicKey, null, 0);
+    const stray = await getOrCreateAssociatedTokenAccount(connection, admin, strayMint, m.market, true);
+    const destination = await getOrCreateAssociatedTokenAccount(connection, admin, strayMint, admin.publicKey);
+    await mintTo(connection, admin, strayMint, stray.address, admin, 42);
+
+    await program.methods
+      .rescueTokens()
+      .accountsPartial({
+        market: m.market,
+        admin: admin.publicKey,
+        source: stray.address,
+        destination: destination.address,
+      })
+      .rpc();
+
+    assert.equal(await tokenBalance(program, stray.address), 0);
+    assert.equal(await tokenBalance(program, destination.address), 42);
+  });
+
+  it("refuses to drain the market vaults", async () => {
+    const m = await setupMarket(program, admin, new anchor.BN(1_000_000));
+    const destination = await getOrCreateAssociatedTokenAccount(
+      provider.connection, admin, m.bondMint, admin.publicKey
+    );
+
+    await expectError(
+      program.methods
+        .rescueTokens()
+        .accountsPartial({
+          market: m.market,
+          admin: admin.publicKey,
+          source: m.vaultBond,
+          destination: destination.address,
+        })
+        .rpc(),
+      "CannotRescueVault"
+    );
+    assert.equal(await tokenBalance(program, m.vaultBond), 1000);
+  });
+
+  it("refuses to drain the insurance vault", async () => {
+    const m = await setupMarket(program, admin, new anchor.BN(1_000_000));
+    const [insuranceVault] = PublicKey.findProgramAddressSync(
+      [Buffer.from("insurance"), m.market.toBuffer()],
+      program.programId
+    );
+    await program.methods
+      .initInsuranceVault()
+      .accountsPartial({
+        market: m.market,
+        usdcMint: m.usdcMint,
+        insuranceVault,
+        admin: admin.publicKey,
+      })
+      .rpc();
+    const destination = await getOrCreateAssociatedTokenAccount(
+      provider.connection, admin, m.usdcMint, admin.publicKey
+    );
+
+    await expectError(
+      program.methods
+        .rescueTokens()
+        .accountsPartial({
+          market: m.market,
+          admin: admin.publicKey,
+          source: insuranceVault,
+          destination: destination.address,
+        })
+        .rpc(),
+      "CannotRescueVault"
+    );
+  });
+});
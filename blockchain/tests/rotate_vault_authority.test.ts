@@ -0,0 +1,74 @@
+import * as anchor from "@coral-xyz/anchor";
+import NodeWallet from "@coral-xyz/anchor/dist/cjs/nodewallet";
+import { Program } from "@coral-xyz/anchor";
+import { Sebi } from "../target/types/sebi";
+import { getAccount, TOKEN_PROGRAM_ID } from "@solana/spl-token";
+import { Keypair, PublicKey } from "@solana/web3.js";
+import { describe, it } from "node:test";
+import assert from "assert";
+import { setupMarket, expectError, TestMarket } from "./utils";
+
+describe("rotate vault authority", () => {
+  anchor.setProvider(anchor.AnchorProvider.env());
+  const provider = anchor.getProvider() as anchor.AnchorProvider;
+  const admin = (provider.wallet as NodeWallet).payer;
+  const program = anchor.workspace.Sebi as Program<Sebi>;
+
+  const rotate = (m: TestMarket, newAuthority: PublicKey, insuranceVault: PublicKey | null = null) =>
+    program.methods
+      .rotateVaultAuthority(newAuthority)
+      .accountsPartial({
+        market: m.market,
+        admin: admin.publicKey,
+        vaultBond: m.vaultBond,
+        vaultUsdc: m.vaultUsdc,
+        insuranceVault,
+        tokenProgram: TOKEN_PROGRAM_ID,
+      })
+      .rpc();
+
+  const pause = (m: TestMarket) =>
+    program.methods.pause().accountsPartial({ market: m.market, admin: admin.publicKey }).rpc();
+
+  it("hands both vaults to the new owner once the market is halted", async () => {
+    const m = await setupMarket(program, admin, new anchor.BN(1_000_000));
+    const multisig = Keypair.generate().publicKey;
+
+    await expectError(rotate(m, multisig), "MarketNotHalted");
+    await pause(m);
+    await rotate(m, multisig);
+
+    assert.ok((await getAccount(provider.connection, m.vaultBond)).owner.equals(multisig));
+    assert.ok((await getAccount(provider.connection, m.vaultUsdc)).owner.equals(multisig));
+    const market = await program.account.market.fetch(m.market);
+    assert.ok(market.vaultAuthority.equals(multisig));
+
+    // the PDA no longer owns the vaults, so there is nothing left to rotate
+    await expectError(rotate(m, Keypair.generate().publicKey), "InvalidVaultAuthority");
+  });
+
+  it("takes the insurance vault along when the market has one", async () => {
+    const m = await setupMarket(program, admin, new anchor.BN(1_000_000));
+    const [insuranceVault] = PublicKey.findProgramAddressSync(
+      [Buffer.from("insurance"), m.market.toBuffer()],
+      program.programId
+    );
+    await program.methods
+      .initInsuranceVault()
+      .accountsPartial({ market: m.market, usdcMint: m.usdcMint, insuranceVault, admin: admin.publicKey })
+      .rpc();
+    await pause(m);
+    const multisig = Keypair.generate().publicKey;
+
+    await expectError(rotate(m, multisig), "InsuranceVaultRequired");
+    await rotate(m, multisig, insuranceVault);
+    assert.ok((await getAccount(provider.connection, insuranceVault)).owner.equals(multisig));
+  });
+
+  it("rejects the market itself or the default key as the new owner", async () => {
+    const m = await setupMarket(program, admin, new anchor.BN(1_000_000));
+    await pause(m);
+    await expectError(rotate(m, m.market), "InvalidVaultAuthority");
+    await expectError(rotate(m, PublicKey.default), "InvalidVaultAuthority");
+  });
+});
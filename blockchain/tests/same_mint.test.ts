@@ -0,0 +1,22 @@
+import * as anchor from "@coral-xyz/anchor";
+import NodeWallet from "@coral-xyz/anchor/dist/cjs/nodewallet";
+import { Program } from "@coral-xyz/anchor";
+import { Sebi } from "../target/types/sebi";
+import { createMint } from "@solana/spl-token";
+import { describe, it } from "node:test";
+import { setupMarket, expectError } from "./utils";
+
+describe("same mint", () => {
+  anchor.setProvider(anchor.AnchorProvider.env());
+  const provider = anchor.getProvider() as anchor.AnchorProvider;
+  const admin = (provider.wallet as NodeWallet).payer;
+  const program = anchor.workspace.Sebi as Program<Sebi>;
+
+  it("rejects a market whose bond mint is also its quote mint", async () => {
+    const mint = await createMint(provider.connection, admin, admin.publicKey, null, 6);
+    await expectError(
+      setupMarket(program, admin, new anchor.BN(1_000_000), { bondMint: mint, usdcMint: mint }),
+      "SameMint"
+    );
+  });
+});
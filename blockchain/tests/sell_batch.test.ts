@@ -0,0 +1,87 @@
+import * as anchor from "@coral-xyz/anchor";
+import NodeWallet from "@coral-xyz/anchor/dist/cjs/nodewallet";
+import { Program } from "@coral-xyz/anchor";
+import { Sebi } from "../target/types/sebi";
+import { getOrCreateAssociatedTokenAccount, mintTo, TOKEN_PROGRAM_ID } from "@solana/spl-token";
+import { describe, it } from "node:test";
+import assert from "assert";
+import { setupMarket, createTrader, buy, tokenBalance, expectError, TestMarket, Trader } from "./utils";
+
+describe("sell batch", () => {
+  anchor.setProvider(anchor.AnchorProvider.env());
+  const provider = anchor.getProvider() as anchor.AnchorProvider;
+  const admin = (provider.wallet as NodeWallet).payer;
+  const program = anchor.workspace.Sebi as Program<Sebi>;
+
+  const group = (m: TestMarket, bond: anchor.web3.PublicKey, usdc: anchor.web3.PublicKey) => [
+    { pubkey: m.market, isSigner: false, isWritable: true },
+    { pubkey: bond, isSigner: false, isWritable: true },
+    { pubkey: usdc, isSigner: false, isWritable: true },
+    { pubkey: m.vaultBond, isSigner: false, isWritable: true },
+    { pubkey: m.vaultUsdc, isSigner: false, isWritable: true },
+  ];
+
+  // one seller holding bonds in two markets, each quoted in its own USDC mint
+  async function twoPositions() {
+    const a = await setupMarket(program, admin, new anchor.BN(1_000_000));
+    const b = await setupMarket(program, admin, new anchor.BN(2_000_000));
+    const seller = await createTrader(program, admin, a, 10_000_000);
+    await buy(program, a, seller, 3);
+
+    const bUsdc = await getOrCreateAssociatedTokenAccount(provider.connection, admin, b.usdcMint, seller.keypair.publicKey);
+    const bBond = await getOrCreateAssociatedTokenAccount(provider.connection, admin, b.bondMint, seller.keypair.publicKey);
+    await mintTo(provider.connection, admin, b.usdcMint, bUsdc.address, admin, 10_000_000);
+    const inB: Trader = { keypair: seller.keypair, usdc: bUsdc.address, bond: bBond.address };
+    await buy(program, b, inB, 2);
+
+    return { a, b, seller, inB };
+  }
+
+  const sellBatch = (seller: Trader, amounts: number[], minProceeds: number[], accounts: anchor.web3.AccountMeta[]) =>
+    program.methods
+      .sellBatch(amounts.map((x) => new anchor.BN(x)), minProceeds.map((x) => new anchor.BN(x)))
+      .accountsPartial({ seller: seller.keypair.publicKey, protocolStats: null, tokenProgram: TOKEN_PROGRAM_ID })
+      .remainingAccounts(accounts)
+      .signers([seller.keypair]);
+
+  it("sells across two markets and returns aggregate proceeds", async () => {
+    const { a, b, seller, inB } = await twoPositions();
+    const accounts = [...group(a, seller.bond, seller.usdc), ...group(b, inB.bond, inB.usdc)];
+
+    const sig = await sellBatch(seller, [3, 2], [3_000_000, 4_000_000], accounts).rpc({ commitment: "confirmed" });
+    const tx = await provider.connection.getTransaction(sig, { commitment: "confirmed", maxSupportedTransactionVersion: 0 });
+    const total = Buffer.from(tx!.meta!.returnData!.data[0], "base64").readBigUInt64LE();
+    assert.equal(total, 7_000_000n);
+
+    assert.equal(await tokenBalance(program, seller.bond), 0);
+    assert.equal(await tokenBalance(program, inB.bond), 0);
+    assert.equal(await tokenBalance(program, seller.usdc), 10_000_000);
+    assert.equal(await tokenBalance(program, inB.usdc), 10_000_000);
+  });
+
+  it("reverts every leg when one misses its minimum", async () => {
+    const { a, b, seller, inB } = await twoPositions();
+    const accounts = [...group(a, seller.bond, seller.usdc), ...group(b, inB.bond, inB.usdc)];
+
+    await expectError(sellBatch(seller, [3, 2], [3_000_000, 4_000_001], accounts).rpc(), "SlippageExceeded");
+    assert.equal(await tokenBalance(program, seller.bond), 3);
+    assert.equal(await tokenBalance(program, inB.bond), 2);
+  });
+
+  it("rejects account groups that don't match the arguments", async () => {
+    const { a, seller } = await twoPositions();
+    const accounts = group(a, seller.bond, seller.usdc);
+
+    await expectError(sellBatch(seller, [1, 1], [0, 0], accounts).rpc(), "InvalidBatch");
+    await expectError(sellBatch(seller, [1], [0, 0], accounts).rpc(), "InvalidBatch");
+    await expectError(sellBatch(seller, [1], [0], accounts.slice(0, 4)).rpc(), "InvalidBatch");
+  });
+
+  it("rejects an oversized batch before touching any market", async () => {
+    const { a, seller } = await twoPositions();
+    const amounts = new Array(9).fill(1);
+
+    await expectError(sellBatch(seller, amounts, amounts.map(() => 0), group(a, seller.bond, seller.usdc)).rpc(), "BatchTooLarge");
+    assert.equal(await tokenBalance(program, seller.bond), 3);
+  });
+});
@@ -0,0 +1,23 @@
+import * as anchor from "@coral-xyz/anchor";
+import NodeWallet from "@coral-xyz/anchor/dist/cjs/nodewallet";
+import { Program } from "@coral-xyz/anchor";
+import { Sebi } from "../target/types/sebi";
+import { describe, it } from "node:test";
+import assert from "assert";
+import { setupMarket, createTrader, buy, sell, tokenBalance, expectError } from "./utils";
+
+describe("sell_enabled", () => {
+  anchor.setProvider(anchor.AnchorProvider.env());
+  const provider = anchor.getProvider() as anchor.AnchorProvider;
+  const admin = (provider.wallet as NodeWallet).payer;
+  const program = anchor.workspace.Sebi as Program<Sebi>;
+
+  it("issue-only markets sell bonds out but never buy them back", async () => {
+    const m = await setupMarket(program, admin, new anchor.BN(1_000_000), { sellEnabled: false });
+    const trader = await createTrader(program, admin, m, 10_000_000);
+
+    await buy(program, m, trader, 2);
+    await expectError(sell(program, m, trader, 1), "SellDisabled");
+    assert.equal(await tokenBalance(program, trader.bond), 2);
+  });
+});
@@ -0,0 +1,37 @@
+import * as anchor from "@coral-xyz/anchor";
+import NodeWallet from "@coral-xyz/anchor/dist/cjs/nodewallet";
+import { Program } from "@coral-xyz/anchor";
+import { Sebi } from "../target/types/sebi";
+import { describe, it } from "node:test";
+import assert from "assert";
+import { setupMarket, createTrader, buy, sell, tokenBalance, expectError, priceHistoryPda } from "./utils";
+
+describe("sell preconditions", () => {
+  anchor.setProvider(anchor.AnchorProvider.env());
+  const provider = anchor.getProvider() as anchor.AnchorProvider;
+  const admin = (provider.wallet as NodeWallet).payer;
+  const program = anchor.workspace.Sebi as Program<Sebi>;
+
+  it("leaves every balance and counter untouched when the vault can't pay", async () => {
+    const m = await setupMarket(program, admin, new anchor.BN(1_000_000));
+    const trader = await createTrader(program, admin, m, 10_000_000);
+    await buy(program, m, trader, 2);
+
+    // doubling the price leaves the 2 USDC in the vault short of the 4 USDC owed
+    await program.methods
+      .updatePrice(new anchor.BN(2_000_000))
+      .accountsPartial({ market: m.market, priceHistory: priceHistoryPda(program, m.market), admin: admin.publicKey })
+      .rpc();
+    const before = await program.account.market.fetch(m.market);
+
+    await expectError(sell(program, m, trader, 2), "InsufficientVaultFunds");
+
+    assert.equal(await tokenBalance(program, trader.bond), 2);
+    assert.equal(await tokenBalance(program, trader.usdc), 8_000_000);
+    assert.equal(await tokenBalance(program, m.vaultBond), 998);
+    assert.equal(await tokenBalance(program, m.vaultUsdc), 2_000_000);
+    const after = await program.account.market.fetch(m.market);
+    assert.equal(after.netBondsOut.toString(), before.netBondsOut.toString());
+    assert.equal(after.netQuoteFlow.toString(), before.netQuoteFlow.toString());
+  });
+});
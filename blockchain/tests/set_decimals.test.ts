@@ -0,0 +1,31 @@
+import * as anchor from "@coral-xyz/anchor";
+import NodeWallet from "@coral-xyz/anchor/dist/cjs/nodewallet";
+import { Program } from "@coral-xyz/anchor";
+import { Sebi } from "../target/types/sebi";
+import { describe, it } from "node:test";
+import assert from "assert";
+import { setupMarket, expectError } from "./utils";
+
+describe("set decimals", () => {
+  anchor.setProvider(anchor.AnchorProvider.env());
+  const provider = anchor.getProvider() as anchor.AnchorProvider;
+  const admin = (provider.wallet as NodeWallet).payer;
+  const program = anchor.workspace.Sebi as Program<Sebi>;
+
+  // initialize_market records decimals itself, which spends the one call
+  it("cannot override decimals once recorded", async () => {
+    const m = await setupMarket(program, admin, new anchor.BN(1_000_000));
+    await expectError(
+      program.methods
+        .setDecimals(6, 6)
+        .accountsPartial({ market: m.market, admin: admin.publicKey })
+        .rpc(),
+      "DecimalsAlreadySet"
+    );
+
+    const market = await program.account.market.fetch(m.market);
+    assert.equal(market.decimalsSet, true);
+    assert.equal(market.bondDecimals, 0);
+    assert.equal(market.displayPrice.toNumber(), 1_000_000);
+  });
+});
@@ -0,0 +1,43 @@
+import * as anchor from "@coral-xyz/anchor";
+import NodeWallet from "@coral-xyz/anchor/dist/cjs/nodewallet";
+import { Program } from "@coral-xyz/anchor";
+import { Sebi } from "../target/types/sebi";
+import { getOrCreateAssociatedTokenAccount, mintTo } from "@solana/spl-token";
+import { describe, it } from "node:test";
+import assert from "assert";
+import { setupMarket, createTrader, buy, withdraw, tokenBalance, expectError } from "./utils";
+
+describe("solvent withdrawals", () => {
+  anchor.setProvider(anchor.AnchorProvider.env());
+  const provider = anchor.getProvider() as anchor.AnchorProvider;
+  const admin = (provider.wallet as NodeWallet).payer;
+  const program = anchor.workspace.Sebi as Program<Sebi>;
+
+  it("only releases USDC above the buyback obligation", async () => {
+    const m = await setupMarket(program, admin, new anchor.BN(1_000_000));
+    const trader = await createTrader(program, admin, m, 10_000_000);
+    const treasury = await getOrCreateAssociatedTokenAccount(
+      provider.connection, admin, m.usdcMint, admin.publicKey
+    );
+
+    // 3 bonds outstanding at 1 USDC need 3 USDC of backing
+    await buy(program, m, trader, 3);
+    await mintTo(provider.connection, admin, m.usdcMint, m.vaultUsdc, admin, 500_000);
+
+    await withdraw(program, admin, m, treasury.address, 500_000, true);
+    await expectError(withdraw(program, admin, m, treasury.address, 1, true), "WouldBeInsolvent");
+    assert.equal(await tokenBalance(program, m.vaultUsdc), 3_000_000);
+  });
+
+  it("does not restrict bond withdrawals", async () => {
+    const m = await setupMarket(program, admin, new anchor.BN(1_000_000));
+    const trader = await createTrader(program, admin, m, 10_000_000);
+    const treasury = await getOrCreateAssociatedTokenAccount(
+      provider.connection, admin, m.bondMint, admin.publicKey
+    );
+
+    await buy(program, m, trader, 3);
+    await withdraw(program, admin, m, treasury.address, 997, false);
+    assert.equal(await tokenBalance(program, m.vaultBond), 0);
+  });
+});
@@ -0,0 +1,92 @@
+import * as anchor from "@coral-xyz/anchor";
+import NodeWallet from "@coral-xyz/anchor/dist/cjs/nodewallet";
+import { Program } from "@coral-xyz/anchor";
+import { Sebi } from "../target/types/sebi";
+import { getOrCreateAssociatedTokenAccount, TOKEN_PROGRAM_ID } from "@solana/spl-token";
+import { PublicKey } from "@solana/web3.js";
+import { describe, it } from "node:test";
+import assert from "assert";
+import { setupMarket, createTrader, buy, sell, withdraw, tokenBalance, expectError, priceHistoryPda } from "./utils";
+
+describe("terminate", () => {
+  anchor.setProvider(anchor.AnchorProvider.env());
+  const provider = anchor.getProvider() as anchor.AnchorProvider;
+  const admin = (provider.wallet as NodeWallet).payer;
+  const program = anchor.workspace.Sebi as Program<Sebi>;
+
+  const terminate = (market: anchor.web3.PublicKey) =>
+    program.methods.terminate().accountsPartial({ market, admin: admin.publicKey }).rpc();
+
+  it("blocks trading and admin changes but still lets funds out", async () => {
+    const m = await setupMarket(program, admin, new anchor.BN(1_000_000));
+    const trader = await createTrader(program, admin, m, 10_000_000);
+    await buy(program, m, trader, 2);
+    await terminate(m.market);
+
+    await expectError(buy(program, m, trader, 1), "MarketTerminated");
+    await expectError(sell(program, m, trader, 1), "MarketTerminated");
+    await expectError(
+      program.methods
+        .updatePrice(new anchor.BN(2_000_000))
+        .accountsPartial({ market: m.market, priceHistory: priceHistoryPda(program, m.market), admin: admin.publicKey })
+        .rpc(),
+      "MarketTerminated"
+    );
+    await expectError(
+      program.methods.pause().accountsPartial({ market: m.market, admin: admin.publicKey }).rpc(),
+      "MarketTerminated"
+    );
+
+    // the 2 outstanding bonds keep their backing until they are redeemed
+    const treasury = await getOrCreateAssociatedTokenAccount(provider.connection, admin, m.usdcMint, admin.publicKey);
+    await expectError(withdraw(program, admin, m, treasury.address, 2_000_000, true), "WouldBeInsolvent");
+
+    const [redemptionWindow] = PublicKey.findProgramAddressSync(
+      [Buffer.from("redemption"), m.market.toBuffer()],
+      program.programId
+    );
+    const [claim] = PublicKey.findProgramAddressSync(
+      [Buffer.from("redemption_claim"), m.market.toBuffer(), trader.keypair.publicKey.toBuffer()],
+      program.programId
+    );
+    await program.methods
+      .openRedemption(new anchor.BN(1_000_000), new anchor.BN(2))
+      .accountsPartial({ market: m.market, redemptionWindow, admin: admin.publicKey })
+      .rpc();
+    await program.methods
+      .registerRedemption(new anchor.BN(2))
+      .accountsPartial({
+        market: m.market,
+        redemptionWindow,
+        claim,
+        holder: trader.keypair.publicKey,
+        holderBond: trader.bond,
+        vaultBond: m.vaultBond,
+        tokenProgram: TOKEN_PROGRAM_ID,
+      })
+      .signers([trader.keypair])
+      .rpc();
+    await new Promise((r) => setTimeout(r, 3_000));
+    await program.methods
+      .settleRedemptions()
+      .accountsPartial({ market: m.market, redemptionWindow, vaultUsdc: m.vaultUsdc, tokenProgram: TOKEN_PROGRAM_ID })
+      .remainingAccounts([
+        { pubkey: claim, isSigner: false, isWritable: true },
+        { pubkey: trader.usdc, isSigner: false, isWritable: true },
+      ])
+      .rpc();
+
+    assert.equal(await tokenBalance(program, trader.usdc), 10_000_000);
+    assert.equal(await tokenBalance(program, m.vaultUsdc), 0);
+    assert.equal((await program.account.market.fetch(m.market)).netBondsOut.toNumber(), 0);
+  });
+
+  it("cannot be terminated twice", async () => {
+    const m = await setupMarket(program, admin, new anchor.BN(1_000_000));
+    await terminate(m.market);
+    await expectError(terminate(m.market), "MarketTerminated");
+
+    const market = await program.account.market.fetch(m.market);
+    assert.equal(market.terminated, true);
+  });
+});
@@ -0,0 +1,163 @@
+import * as anchor from "@coral-xyz/anchor";
+import NodeWallet from "@coral-xyz/anchor/dist/cjs/nodewallet";
+import { Program } from "@coral-xyz/anchor";
+import { Sebi } from "../target/types/sebi";
+import { TOKEN_PROGRAM_ID } from "@solana/spl-token";
+import { PublicKey, SystemProgram } from "@solana/web3.js";
+import { describe, it } from "node:test";
+import assert from "assert";
+import { setupMarket, createTrader, buy, tokenBalance, expectError, TestMarket, Trader } from "./utils";
+
+describe("trade cooldown", () => {
+  anchor.setProvider(anchor.AnchorProvider.env());
+  const provider = anchor.getProvider() as anchor.AnchorProvider;
+  const admin = (provider.wallet as NodeWallet).payer;
+  const program = anchor.workspace.Sebi as Program<Sebi>;
+
+  const positionPda = (m: TestMarket, t: Trader) =>
+    PublicKey.findProgramAddressSync(
+      [Buffer.from("position"), m.market.toBuffer(), t.keypair.publicKey.toBuffer()],
+      program.programId
+    )[0];
+
+  const setCooldown = (m: TestMarket, secs: number) =>
+    program.methods
+      .setTradeCooldown(new anchor.BN(secs))
+      .accountsPartial({ market: m.market, admin: admin.publicKey })
+      .rpc();
+
+  const openPosition = (m: TestMarket, t: Trader) =>
+    program.methods
+      .openInvestorPosition()
+      .accountsPartial({ market: m.market, investorPosition: positionPda(m, t), owner: t.keypair.publicKey })
+      .signers([t.keypair])
+      .rpc();
+
+  const optional = {
+    instructions: null,
+    protocolStats: null,
+    complianceProgram: null,
+    tradingDelegate: null,
+    relayerAuthorization: null,
+    dailyStats: null,
+    notifyProgram: null,
+    tokenProgram: TOKEN_PROGRAM_ID,
+  };
+
+  const positionedBuy = (m: TestMarket, t: Trader, amount: number, create = false) =>
+    program.methods
+      .buy(new anchor.BN(amount))
+      .accountsPartial({
+        market: m.market,
+        buyer: t.keypair.publicKey,
+        buyerUsdc: t.usdc,
+        buyerBond: t.bond,
+        vaultUsdc: m.vaultUsdc,
+        vaultBond: m.vaultBond,
+        bondMint: null,
+        investorPosition: positionPda(m, t),
+        ...optional,
+        associatedTokenProgram: null,
+        systemProgram: create ? SystemProgram.programId : null,
+      })
+      .signers([t.keypair])
+      .rpc();
+
+  const positionedSell = (m: TestMarket, t: Trader, amount: number) =>
+    program.methods
+      .sell(new anchor.BN(amount))
+      .accountsPartial({
+        market: m.market,
+        seller: t.keypair.publicKey,
+        sellerBond: t.bond,
+        sellerUsdc: t.usdc,
+        vaultBond: m.vaultBond,
+        vaultUsdc: m.vaultUsdc,
+        insuranceVault: null,
+        investorPosition: positionPda(m, t),
+        ...optional,
+      })
+      .signers([t.keypair])
+      .rpc();
+
+  it("rejects rapid successive trades by one investor", async () => {
+    const m = await setupMarket(program, admin, new anchor.BN(1_000_000));
+    const trader = await createTrader(program, admin, m, 10_000_000);
+    await openPosition(m, trader);
+    await setCooldown(m, 3);
+
+    await positionedBuy(m, trader, 2);
+    await expectError(positionedBuy(m, trader, 1), "TradeCooldown");
+    await expectError(positionedSell(m, trader, 1), "TradeCooldown");
+
+    await new Promise((r) => setTimeout(r, 4_000));
+    await positionedSell(m, trader, 1);
+    await expectError(positionedBuy(m, trader, 1), "TradeCooldown");
+    assert.equal(await tokenBalance(program, trader.bond), 1);
+  });
+
+  it("keeps cooldowns per investor", async () => {
+    const m = await setupMarket(program, admin, new anchor.BN(1_000_000));
+    const [a, b] = [await createTrader(program, admin, m, 10_000_000), await createTrader(program, admin, m, 10_000_000)];
+    await openPosition(m, a);
+    await openPosition(m, b);
+    await setCooldown(m, 60);
+
+    await positionedBuy(m, a, 1);
+    await positionedBuy(m, b, 1);
+    await expectError(positionedBuy(m, a, 1), "TradeCooldown");
+    // reopening an existing position keeps its last trade
+    await openPosition(m, a);
+    await expectError(positionedBuy(m, a, 1), "TradeCooldown");
+  });
+
+  it("needs the position only while a cooldown is set", async () => {
+    const m = await setupMarket(program, admin, new anchor.BN(1_000_000));
+    const trader = await createTrader(program, admin, m, 10_000_000);
+
+    // 0 disables the cooldown, and trades without a position go through
+    await buy(program, m, trader, 1);
+    await buy(program, m, trader, 1);
+
+    await setCooldown(m, 60);
+    await expectError(buy(program, m, trader, 1), "PositionRequired");
+    assert.equal(await tokenBalance(program, trader.bond), 2);
+  });
+
+  it("creates a missing position on buy when given the system program", async () => {
+    const m = await setupMarket(program, admin, new anchor.BN(1_000_000));
+    const trader = await createTrader(program, admin, m, 10_000_000);
+    await setCooldown(m, 60);
+
+    await expectError(positionedBuy(m, trader, 1), "AccountNotInitialized");
+    await positionedBuy(m, trader, 1, true);
+
+    const position = await program.account.investorPosition.fetch(positionPda(m, trader));
+    assert.ok(position.owner.equals(trader.keypair.publicKey));
+    assert.ok(position.lastTradeTs.toNumber() > 0);
+    // the created position carries the cooldown like an opened one
+    await expectError(positionedBuy(m, trader, 1, true), "TradeCooldown");
+    assert.equal(await tokenBalance(program, trader.bond), 1);
+  });
+
+  it("rejects buy, sell, buy flips past the wash threshold", async () => {
+    const m = await setupMarket(program, admin, new anchor.BN(1_000_000));
+    const trader = await createTrader(program, admin, m, 10_000_000);
+    await openPosition(m, trader);
+    await program.methods
+      .setWashCheck(new anchor.BN(3_600), 1)
+      .accountsPartial({ market: m.market, admin: admin.publicKey })
+      .rpc();
+
+    await expectError(buy(program, m, trader, 1), "PositionRequired");
+    await positionedBuy(m, trader, 2);
+    await positionedSell(m, trader, 1);
+    await expectError(positionedBuy(m, trader, 1), "WashTradingSuspected");
+    // staying on the same side is not a flip
+    await positionedSell(m, trader, 1);
+
+    const position = await program.account.investorPosition.fetch(positionPda(m, trader));
+    assert.equal(position.flipCount, 1);
+    assert.equal(await tokenBalance(program, trader.bond), 0);
+  });
+});
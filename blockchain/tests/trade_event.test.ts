@@ -0,0 +1,105 @@
+import * as anchor from "@coral-xyz/anchor";
+import NodeWallet from "@coral-xyz/anchor/dist/cjs/nodewallet";
+import { Program } from "@coral-xyz/anchor";
+import { Sebi } from "../target/types/sebi";
+import { TradeRouter } from "../target/types/trade_router";
+import { TOKEN_PROGRAM_ID } from "@solana/spl-token";
+import { PublicKey, SYSVAR_INSTRUCTIONS_PUBKEY, TransactionInstruction } from "@solana/web3.js";
+import { describe, it } from "node:test";
+import assert from "assert";
+import { setupMarket, createTrader, buy, sell, tradeEvents, expectError, TestMarket, Trader } from "./utils";
+
+// attested prices are covered in price_attestation.test.ts
+describe("trade event prices", () => {
+  anchor.setProvider(anchor.AnchorProvider.env());
+  const provider = anchor.getProvider() as anchor.AnchorProvider;
+  const admin = (provider.wallet as NodeWallet).payer;
+  const program = anchor.workspace.Sebi as Program<Sebi>;
+  const router = anchor.workspace.TradeRouter as Program<TradeRouter>;
+
+  const buyIx = (m: TestMarket, t: Trader, amount: number, instructionsSysvar: PublicKey | null) =>
+    program.methods
+      .buy(new anchor.BN(amount))
+      .accountsPartial({
+        market: m.market,
+        buyer: t.keypair.publicKey,
+        buyerUsdc: t.usdc,
+        buyerBond: t.bond,
+        vaultUsdc: m.vaultUsdc,
+        vaultBond: m.vaultBond,
+        instructions: null,
+        protocolStats: null,
+        complianceProgram: null,
+        tradingDelegate: null,
+        relayerAuthorization: null,
+        investorPosition: null,
+        dailyStats: null,
+        notifyProgram: null,
+        bondMint: null,
+        tokenProgram: TOKEN_PROGRAM_ID,
+        instructionsSysvar,
+      })
+      .instruction();
+
+  // sends `ix` through trade_router, so sebi runs one level down the CPI stack
+  const viaRouter = (ix: TransactionInstruction, t: Trader) =>
+    router.methods
+      .forward(ix.data)
+      .accounts({ target: program.programId })
+      .remainingAccounts(ix.keys)
+      .signers([t.keypair])
+      .rpc({ commitment: "confirmed" });
+
+  it("reports the stored price for both sides", async () => {
+    const m = await setupMarket(program, admin, new anchor.BN(1_000_000));
+    const trader = await createTrader(program, admin, m, 10_000_000);
+
+    for (const sig of [await buy(program, m, trader, 2), await sell(program, m, trader, 1)]) {
+      const [event] = await tradeEvents(program, sig);
+      assert.equal(event.price.toNumber(), 1_000_000);
+      assert.equal(event.referencePrice.toNumber(), 1_000_000);
+    }
+  });
+
+  it("reports the auction price the trade was charged", async () => {
+    const now = Math.floor(Date.now() / 1000);
+    const auction = {
+      startPrice: new anchor.BN(1_000_000),
+      endPrice: new anchor.BN(400_000),
+      startTs: new anchor.BN(now - 7_200),
+      endTs: new anchor.BN(now - 3_600),
+    };
+    const m = await setupMarket(program, admin, new anchor.BN(1), { auction });
+    const trader = await createTrader(program, admin, m, 10_000_000);
+
+    const [event] = await tradeEvents(program, await buy(program, m, trader, 1));
+    assert.equal(event.price.toNumber(), 400_000);
+    assert.equal(event.referencePrice.toNumber(), 400_000);
+  });
+
+  it("attributes wallet-signed trades to no program", async () => {
+    const m = await setupMarket(program, admin, new anchor.BN(1_000_000));
+    const trader = await createTrader(program, admin, m, 10_000_000);
+
+    for (const sig of [await buy(program, m, trader, 2), await sell(program, m, trader, 1)]) {
+      const [event] = await tradeEvents(program, sig);
+      assert.ok(event.viaProgram.equals(PublicKey.default));
+    }
+  });
+
+  it("attributes CPI trades to the top-level program", async () => {
+    const m = await setupMarket(program, admin, new anchor.BN(1_000_000));
+    const trader = await createTrader(program, admin, m, 10_000_000);
+
+    const sig = await viaRouter(await buyIx(m, trader, 2, SYSVAR_INSTRUCTIONS_PUBKEY), trader);
+    const [event] = await tradeEvents(program, sig);
+    assert.ok(event.viaProgram.equals(router.programId));
+  });
+
+  it("rejects CPI trades that leave out the instructions sysvar", async () => {
+    const m = await setupMarket(program, admin, new anchor.BN(1_000_000));
+    const trader = await createTrader(program, admin, m, 10_000_000);
+
+    await expectError(viaRouter(await buyIx(m, trader, 1, null), trader), "InstructionsSysvarRequired");
+  });
+});
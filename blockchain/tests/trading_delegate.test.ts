@@ -0,0 +1,119 @@
+import * as anchor from "@coral-xyz/anchor";
+import NodeWallet from "@coral-xyz/anchor/dist/cjs/nodewallet";
+import { Program } from "@coral-xyz/anchor";
+import { Sebi } from "../target/types/sebi";
+import { approve, TOKEN_PROGRAM_ID } from "@solana/spl-token";
+import { Keypair, PublicKey } from "@solana/web3.js";
+import { describe, it } from "node:test";
+import assert from "assert";
+import { setupMarket, createTrader, tokenBalance, expectError, TestMarket, Trader } from "./utils";
+
+describe("trading delegate", () => {
+  anchor.setProvider(anchor.AnchorProvider.env());
+  const provider = anchor.getProvider() as anchor.AnchorProvider;
+  const admin = (provider.wallet as NodeWallet).payer;
+  const program = anchor.workspace.Sebi as Program<Sebi>;
+
+  const delegatePda = (m: TestMarket, owner: PublicKey) =>
+    PublicKey.findProgramAddressSync(
+      [Buffer.from("delegate"), m.market.toBuffer(), owner.toBuffer()],
+      program.programId
+    )[0];
+
+  const buyFor = (m: TestMarket, owner: Trader, signer: Keypair, tradingDelegate: PublicKey | null) =>
+    program.methods
+      .buy(new anchor.BN(2))
+      .accountsPartial({
+        market: m.market,
+        buyer: signer.publicKey,
+        buyerUsdc: owner.usdc,
+        buyerBond: owner.bond,
+        vaultUsdc: m.vaultUsdc,
+        vaultBond: m.vaultBond,
+        instructions: null,
+        protocolStats: null,
+        complianceProgram: null,
+        tradingDelegate,
+        relayerAuthorization: null,
+        investorPosition: null,
+        dailyStats: null,
+        notifyProgram: null,
+        bondMint: null,
+        tokenProgram: TOKEN_PROGRAM_ID,
+      })
+      .signers([signer])
+      .rpc();
+
+  const sellFor = (m: TestMarket, owner: Trader, signer: Keypair, tradingDelegate: PublicKey | null) =>
+    program.methods
+      .sell(new anchor.BN(1))
+      .accountsPartial({
+        market: m.market,
+        seller: signer.publicKey,
+        sellerBond: owner.bond,
+        sellerUsdc: owner.usdc,
+        vaultBond: m.vaultBond,
+        vaultUsdc: m.vaultUsdc,
+        insuranceVault: null,
+        instructions: null,
+        protocolStats: null,
+        complianceProgram: null,
+        tradingDelegate,
+        relayerAuthorization: null,
+        investorPosition: null,
+        dailyStats: null,
+        notifyProgram: null,
+        tokenProgram: TOKEN_PROGRAM_ID,
+      })
+      .signers([signer])
+      .rpc();
+
+  it("settles a delegate's trades against the owner's accounts", async () => {
+    const m = await setupMarket(program, admin, new anchor.BN(1_000_000));
+    const owner = await createTrader(program, admin, m, 10_000_000);
+    const delegate = Keypair.generate();
+    const record = delegatePda(m, owner.keypair.publicKey);
+
+    await program.methods
+      .setTradingDelegate(delegate.publicKey)
+      .accountsPartial({ market: m.market, tradingDelegate: record, owner: owner.keypair.publicKey })
+      .signers([owner.keypair])
+      .rpc();
+    await approve(provider.connection, admin, owner.usdc, delegate.publicKey, owner.keypair, 5_000_000);
+
+    await buyFor(m, owner, delegate, record);
+    assert.equal(await tokenBalance(program, owner.bond), 2);
+    assert.equal(await tokenBalance(program, owner.usdc), 8_000_000);
+
+    await approve(provider.connection, admin, owner.bond, delegate.publicKey, owner.keypair, 1);
+    await sellFor(m, owner, delegate, record);
+    assert.equal(await tokenBalance(program, owner.bond), 1);
+    assert.equal(await tokenBalance(program, owner.usdc), 9_000_000);
+
+    // a different signer can't borrow the record, and neither can trade without it
+    await expectError(buyFor(m, owner, Keypair.generate(), record), "NotDelegated");
+    await expectError(buyFor(m, owner, delegate, null), "ConstraintRaw");
+  });
+
+  it("rejects a revoked delegate", async () => {
+    const m = await setupMarket(program, admin, new anchor.BN(1_000_000));
+    const owner = await createTrader(program, admin, m, 10_000_000);
+    const delegate = Keypair.generate();
+    const record = delegatePda(m, owner.keypair.publicKey);
+
+    await program.methods
+      .setTradingDelegate(delegate.publicKey)
+      .accountsPartial({ market: m.market, tradingDelegate: record, owner: owner.keypair.publicKey })
+      .signers([owner.keypair])
+      .rpc();
+    await approve(provider.connection, admin, owner.usdc, delegate.publicKey, owner.keypair, 5_000_000);
+    await program.methods
+      .revokeTradingDelegate()
+      .accountsPartial({ market: m.market, tradingDelegate: record, owner: owner.keypair.publicKey })
+      .signers([owner.keypair])
+      .rpc();
+
+    await expectError(buyFor(m, owner, delegate, record), "AccountNotInitialized");
+    assert.equal(await tokenBalance(program, owner.usdc), 10_000_000);
+  });
+});
@@ -0,0 +1,42 @@
+import * as anchor from "@coral-xyz/anchor";
+import NodeWallet from "@coral-xyz/anchor/dist/cjs/nodewallet";
+import { Program } from "@coral-xyz/anchor";
+import { Sebi } from "../target/types/sebi";
+import { describe, it } from "node:test";
+import assert from "assert";
+import { setupMarket, priceHistoryPda } from "./utils";
+
+describe("twap", () => {
+  anchor.setProvider(anchor.AnchorProvider.env());
+  const provider = anchor.getProvider() as anchor.AnchorProvider;
+  const admin = (provider.wallet as NodeWallet).payer;
+  const program = anchor.workspace.Sebi as Program<Sebi>;
+
+  const twap = async (market: anchor.web3.PublicKey, windowSecs: number) =>
+    (await program.methods
+      .twap(new anchor.BN(windowSecs))
+      .accountsPartial({ market, priceHistory: priceHistoryPda(program, market) })
+      .view()).toNumber();
+
+  // exact weightings over known timestamps are covered by the PriceHistory unit
+  // tests; here the validator clock decides the segment lengths
+  it("returns the only price when there is a single point", async () => {
+    const m = await setupMarket(program, admin, new anchor.BN(1_000_000));
+    assert.equal(await twap(m.market, 3_600), 1_000_000);
+    assert.equal(await twap(m.market, 0), 1_000_000);
+  });
+
+  it("stays within the range of recorded prices", async () => {
+    const m = await setupMarket(program, admin, new anchor.BN(1_000_000));
+    await new Promise((r) => setTimeout(r, 2_000));
+    await program.methods
+      .updatePrice(new anchor.BN(2_000_000))
+      .accountsPartial({ market: m.market, priceHistory: priceHistoryPda(program, m.market), admin: admin.publicKey })
+      .rpc();
+    await new Promise((r) => setTimeout(r, 2_000));
+
+    const avg = await twap(m.market, 3_600);
+    assert.ok(avg > 1_000_000 && avg < 2_000_000, `twap ${avg}`);
+    assert.equal(await twap(m.market, 0), 2_000_000);
+  });
+});
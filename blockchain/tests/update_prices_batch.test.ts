@@ -0,0 +1,81 @@
+import * as anchor from "@coral-xyz/anchor";
+import NodeWallet from "@coral-xyz/anchor/dist/cjs/nodewallet";
+import { Program } from "@coral-xyz/anchor";
+import { Sebi } from "../target/types/sebi";
+import { describe, it } from "node:test";
+import assert from "assert";
+import { setupMarket, expectError, priceHistoryPda, TestMarket } from "./utils";
+
+describe("update prices batch", () => {
+  anchor.setProvider(anchor.AnchorProvider.env());
+  const provider = anchor.getProvider() as anchor.AnchorProvider;
+  const admin = (provider.wallet as NodeWallet).payer;
+  const program = anchor.workspace.Sebi as Program<Sebi>;
+
+  const group = (m: TestMarket) => [
+    { pubkey: m.market, isSigner: false, isWritable: true },
+    { pubkey: priceHistoryPda(program, m.market), isSigner: false, isWritable: true },
+  ];
+
+  const updateBatch = (prices: number[], accounts: anchor.web3.AccountMeta[]) =>
+    program.methods
+      .updatePricesBatch(prices.map((p) => new anchor.BN(p)))
+      .accountsPartial({ admin: admin.publicKey })
+      .remainingAccounts(accounts)
+      .rpc();
+
+  const price = async (m: TestMarket) => (await program.account.market.fetch(m.market)).pricePerToken.toNumber();
+
+  it("reprices two markets in one transaction", async () => {
+    const a = await setupMarket(program, admin, new anchor.BN(1_000_000));
+    const b = await setupMarket(program, admin, new anchor.BN(2_000_000));
+
+    await updateBatch([1_100_000, 1_900_000], [...group(a), ...group(b)]);
+    assert.equal(await price(a), 1_100_000);
+    assert.equal(await price(b), 1_900_000);
+
+    const points = await program.methods
+      .getPriceHistory()
+      .accountsPartial({ market: b.market, priceHistory: priceHistoryPda(program, b.market) })
+      .view();
+    assert.deepEqual(points.map((p: any) => p.price.toNumber()), [2_000_000, 1_900_000]);
+  });
+
+  it("leaves every market untouched when one update fails", async () => {
+    const a = await setupMarket(program, admin, new anchor.BN(1_000_000));
+    const b = await setupMarket(program, admin, new anchor.BN(2_000_000));
+    await program.methods.freezePrice().accountsPartial({ market: b.market, admin: admin.publicKey }).rpc();
+
+    await expectError(updateBatch([1_100_000, 1_900_000], [...group(a), ...group(b)]), "PriceFrozen");
+    assert.equal(await price(a), 1_000_000);
+  });
+
+  it("reverts the batch when one market's price guard rejects its update", async () => {
+    const a = await setupMarket(program, admin, new anchor.BN(1_000_000));
+    const b = await setupMarket(program, admin, new anchor.BN(2_000_000));
+    // b may move 5% a minute
+    await program.methods
+      .setPriceGuard(500, new anchor.BN(60))
+      .accountsPartial({ market: b.market, admin: admin.publicKey })
+      .rpc();
+
+    await expectError(updateBatch([1_100_000, 2_200_000], [...group(a), ...group(b)]), "PriceChangeTooLarge");
+    assert.equal(await price(a), 1_000_000);
+
+    await updateBatch([1_100_000, 2_100_000], [...group(a), ...group(b)]);
+    await expectError(updateBatch([1_200_000, 2_050_000], [...group(a), ...group(b)]), "PriceUpdateCooldown");
+    assert.equal(await price(a), 1_100_000);
+    assert.equal(await price(b), 2_100_000);
+  });
+
+  it("rejects misaligned arguments", async () => {
+    const a = await setupMarket(program, admin, new anchor.BN(1_000_000));
+    const b = await setupMarket(program, admin, new anchor.BN(2_000_000));
+
+    await expectError(updateBatch([1_100_000], [...group(a), ...group(b)]), "InvalidBatch");
+    await expectError(updateBatch([1_100_000, 1_900_000], group(a)), "InvalidBatch");
+    // a's history paired with b's market
+    await expectError(updateBatch([1_100_000], [group(b)[0], group(a)[1]]), "InvalidBatch");
+    await expectError(updateBatch([], []), "InvalidBatch");
+  });
+});
@@ -0,0 +1,278 @@
+import * as anchor from "@coral-xyz/anchor";
+import { Program } from "@coral-xyz/anchor";
+import { Sebi } from "../target/types/sebi";
+import { createMint, getOrCreateAssociatedTokenAccount, mintTo, TOKEN_PROGRAM_ID } from "@solana/spl-token";
+import { Keypair, PublicKey, LAMPORTS_PER_SOL } from "@solana/web3.js";
+
+export interface TestMarket {
+  bondMint: PublicKey;
+  usdcMint: PublicKey;
+  market: PublicKey;
+  vaultBond: PublicKey;
+  vaultUsdc: PublicKey;
+}
+
+export interface Trader {
+  keypair: Keypair;
+  usdc: PublicKey;
+  bond: PublicKey;
+}
+
+export interface MarketOptions {
+  bondSupply?: number;
+  sellEnabled?: boolean;
+  priceScale?: number;
+  bondMint?: PublicKey;
+  usdcMint?: PublicKey;
+  auction?: AuctionConfig;
+  protocolStats?: PublicKey;
+  // quote price_per_token per lot of this many bond base units
+  lotSize?: number;
+}
+
+export interface AuctionConfig {
+  startPrice: anchor.BN;
+  endPrice: anchor.BN;
+  startTs: anchor.BN;
+  endTs: anchor.BN;
+}
+
+export const REGISTRY_PAGE_SIZE = 32;
+
+const BPF_LOADER_UPGRADEABLE = new PublicKey("BPFLoaderUpgradeab1e11111111111111111111111");
+
+export function configPda(program: Program<Sebi>): PublicKey {
+  return PublicKey.findProgramAddressSync([Buffer.from("config")], program.programId)[0];
+}
+
+// The provider wallet deployed the program, so it is the upgrade authority.
+export async function ensureConfig(program: Program<Sebi>, authority: Keypair): Promise<PublicKey> {
+  const config = configPda(program);
+  if (!(await program.account.programConfig.fetchNullable(config))) {
+    const [programData] = PublicKey.findProgramAddressSync(
+      [program.programId.toBuffer()],
+      BPF_LOADER_UPGRADEABLE
+    );
+    await program.methods
+      .initConfig()
+      .accountsPartial({
+        config,
+        authority: authority.publicKey,
+        program: program.programId,
+        programData,
+      })
+      .signers([authority])
+      .rpc();
+  }
+  return config;
+}
+
+export function protocolStatsPda(program: Program<Sebi>): PublicKey {
+  return PublicKey.findProgramAddressSync([Buffer.from("protocol_stats")], program.programId)[0];
+}
+
+export function dailyStatsPda(program: Program<Sebi>, market: PublicKey): PublicKey {
+  return PublicKey.findProgramAddressSync(
+    [Buffer.from("daily_stats"), market.toBuffer()],
+    program.programId
+  )[0];
+}
+
+export function priceHistoryPda(program: Program<Sebi>, market: PublicKey): PublicKey {
+  return PublicKey.findProgramAddressSync(
+    [Buffer.from("price_history"), market.toBuffer()],
+    program.programId
+  )[0];
+}
+
+export async function registryAccounts(program: Program<Sebi>) {
+  const [registry] = PublicKey.findProgramAddressSync([Buffer.from("registry")], program.programId);
+  const existing = await program.account.marketRegistry.fetchNullable(registry);
+  const count = existing ? existing.marketCount.toNumber() : 0;
+  const page = new anchor.BN(Math.floor(count / REGISTRY_PAGE_SIZE));
+  const [registryPage] = PublicKey.findProgramAddressSync(
+    [Buffer.from("registry_page"), page.toArrayLike(Buffer, "le", 8)],
+    program.programId
+  );
+  return { registry, registryPage };
+}
+
+export async function setupMarket(
+  program: Program<Sebi>,
+  admin: Keypair,
+  price: anchor.BN,
+  opts: MarketOptions = {},
+): Promise<TestMarket> {
+  const connection = program.provider.connection;
+  const bondSupply = opts.bondSupply ?? 1000;
+
+  const bondMint = opts.bondMint ?? await createMint(connection, admin, admin.publicKey, null, 0);
+  const usdcMint = opts.usdcMint ?? await createMint(connection, admin, admin.publicKey, null, 6);
+
+  const [market] = PublicKey.findProgramAddressSync(
+    [Buffer.from("market"), bondMint.toBuffer()],
+    program.programId
+  );
+
+  const vaultBond = Keypair.generate();
+  const vaultUsdc = Keypair.generate();
+  const config = await ensureConfig(program, admin);
+  const { registry, registryPage } = await registryAccounts(program);
+
+  await program.methods
+    .initializeMarket(
+      price,
+      opts.priceScale ?? 0,
+      opts.sellEnabled ?? true,
+      opts.auction ?? null,
+      opts.lotSize ? { perLot: {} } : { perBaseUnit: {} },
+      new anchor.BN(opts.lotSize ?? 0),
+    )
+    .accountsPartial({
+      market,
+      bondMint,
+      usdcMint,
+      vaultBond: vaultBond.publicKey,
+      vaultUsdc: vaultUsdc.publicKey,
+      priceHistory: priceHistoryPda(program, market),
+      config,
+      registry,
+      registryPage,
+      protocolStats: opts.protocolStats ?? null,
+      admin: admin.publicKey,
+    })
+    .signers([vaultBond, vaultUsdc, admin])
+    .rpc();
+
+  if (bondSupply > 0) {
+    await mintTo(connection, admin, bondMint, vaultBond.publicKey, admin, bondSupply);
+  }
+
+  return { bondMint, usdcMint, market, vaultBond: vaultBond.publicKey, vaultUsdc: vaultUsdc.publicKey };
+}
+
+export async function createTrader(
+  program: Program<Sebi>,
+  admin: Keypair,
+  m: TestMarket,
+  usdcAmount: number,
+): Promise<Trader> {
+  const connection = program.provider.connection;
+  const keypair = Keypair.generate();
+
+  const sig = await connection.requestAirdrop(keypair.publicKey, LAMPORTS_PER_SOL);
+  await connection.confirmTransaction(sig);
+
+  const usdc = await getOrCreateAssociatedTokenAccount(connection, admin, m.usdcMint, keypair.publicKey);
+  const bond = await getOrCreateAssociatedTokenAccount(connection, admin, m.bondMint, keypair.publicKey);
+  if (usdcAmount > 0) {
+    await mintTo(connection, admin, m.usdcMint, usdc.address, admin, usdcAmount);
+  }
+
+  return { keypair, usdc: usdc.address, bond: bond.address };
+}
+
+export async function buy(program: Program<Sebi>, m: TestMarket, t: Trader, amount: number) {
+  return program.methods
+    .buy(new anchor.BN(amount))
+    .accountsPartial({
+      market: m.market,
+      buyer: t.keypair.publicKey,
+      buyerUsdc: t.usdc,
+      buyerBond: t.bond,
+      vaultUsdc: m.vaultUsdc,
+      vaultBond: m.vaultBond,
+      instructions: null,
+      protocolStats: null,
+      complianceProgram: null,
+      tradingDelegate: null,
+      relayerAuthorization: null,
+      investorPosition: null,
+      dailyStats: null,
+      notifyProgram: null,
+      bondMint: null,
+      tokenProgram: TOKEN_PROGRAM_ID,
+    })
+    .signers([t.keypair])
+    .rpc();
+}
+
+export async function sell(program: Program<Sebi>, m: TestMarket, t: Trader, amount: number) {
+  return program.methods
+    .sell(new anchor.BN(amount))
+    .accountsPartial({
+      market: m.market,
+      seller: t.keypair.publicKey,
+      sellerBond: t.bond,
+      sellerUsdc: t.usdc,
+      vaultBond: m.vaultBond,
+      vaultUsdc: m.vaultUsdc,
+      insuranceVault: null,
+      instructions: null,
+      protocolStats: null,
+      complianceProgram: null,
+      tradingDelegate: null,
+      relayerAuthorization: null,
+      investorPosition: null,
+      dailyStats: null,
+      notifyProgram: null,
+      tokenProgram: TOKEN_PROGRAM_ID,
+    })
+    .signers([t.keypair])
+    .rpc();
+}
+
+export async function withdraw(
+  program: Program<Sebi>,
+  admin: Keypair,
+  m: TestMarket,
+  destination: PublicKey,
+  amount: number,
+  isUsdc: boolean,
+) {
+  return program.methods
+    .withdraw(new anchor.BN(amount), isUsdc)
+    .accountsPartial({
+      market: m.market,
+      admin: admin.publicKey,
+      destination,
+      vaultBond: m.vaultBond,
+      vaultUsdc: m.vaultUsdc,
+      tokenProgram: TOKEN_PROGRAM_ID,
+    })
+    .rpc();
+}
+
+export async function programEvents(program: Program<Sebi>, sig: string) {
+  await program.provider.connection.confirmTransaction(sig, "confirmed");
+  const tx = await program.provider.connection.getTransaction(sig, {
+    commitment: "confirmed",
+    maxSupportedTransactionVersion: 0,
+  });
+  const parser = new anchor.EventParser(program.programId, program.coder);
+  return [...parser.parseLogs(tx!.meta!.logMessages!)];
+}
+
+export async function tradeEvents(program: Program<Sebi>, sig: string) {
+  return (await programEvents(program, sig))
+    .filter((e) => e.name === "tradeEvent")
+    .map((e) => e.data as { price: anchor.BN; referencePrice: anchor.BN; viaProgram: PublicKey });
+}
+
+export async function tokenBalance(program: Program<Sebi>, account: PublicKey): Promise<number> {
+  const bal = await program.provider.connection.getTokenAccountBalance(account);
+  return Number(bal.value.amount);
+}
+
+export async function expectError(promise: Promise<unknown>, code: string) {
+  try {
+    await promise;
+  } catch (err) {
+    const actual = (err as anchor.AnchorError).error?.errorCode?.code ?? String(err);
+    if (!actual.includes(code)) {
+      throw new Error(`expected ${code}, got ${actual}`);
+    }
+    return;
+  }
+  throw new Error(`expected ${code}, but the transaction succeeded`);
+}
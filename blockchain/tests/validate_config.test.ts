@@ -0,0 +1,42 @@
+import * as anchor from "@coral-xyz/anchor";
+import NodeWallet from "@coral-xyz/anchor/dist/cjs/nodewallet";
+import { Program } from "@coral-xyz/anchor";
+import { Sebi } from "../target/types/sebi";
+import { describe, it } from "node:test";
+import assert from "assert";
+import { setupMarket, TestMarket } from "./utils";
+
+// CONFIG_* bits from instructions/validate_config.rs
+const ZERO_PRICE = 1 << 0;
+const NO_INVENTORY = 1 << 2;
+const NOT_FUNDED = 1 << 3;
+const HALTED = 1 << 9;
+
+describe("validate config", () => {
+  anchor.setProvider(anchor.AnchorProvider.env());
+  const provider = anchor.getProvider() as anchor.AnchorProvider;
+  const admin = (provider.wallet as NodeWallet).payer;
+  const program = anchor.workspace.Sebi as Program<Sebi>;
+
+  const validate = (m: TestMarket) =>
+    program.methods
+      .validateConfig()
+      .accountsPartial({ market: m.market, vaultBond: m.vaultBond, vaultUsdc: m.vaultUsdc })
+      .view();
+
+  it("reports nothing for a well-configured market", async () => {
+    const m = await setupMarket(program, admin, new anchor.BN(1_000_000));
+    assert.equal(await validate(m), 0);
+  });
+
+  it("reports every failed check at once", async () => {
+    const m = await setupMarket(program, admin, new anchor.BN(0), { bondSupply: 0 });
+    await program.methods
+      .setMinInitialFunding(new anchor.BN(1_000_000))
+      .accountsPartial({ market: m.market, admin: admin.publicKey })
+      .rpc();
+    await program.methods.pause().accountsPartial({ market: m.market, admin: admin.publicKey }).rpc();
+
+    assert.equal(await validate(m), ZERO_PRICE | NO_INVENTORY | NOT_FUNDED | HALTED);
+  });
+});
@@ -0,0 +1,58 @@
+import * as anchor from "@coral-xyz/anchor";
+import NodeWallet from "@coral-xyz/anchor/dist/cjs/nodewallet";
+import { Program } from "@coral-xyz/anchor";
+import { Sebi } from "../target/types/sebi";
+import { getOrCreateAssociatedTokenAccount, mintTo } from "@solana/spl-token";
+import { describe, it } from "node:test";
+import assert from "assert";
+import { setupMarket, tokenBalance, expectError, withdraw, TestMarket } from "./utils";
+
+describe("cumulative withdraw cap", () => {
+  anchor.setProvider(anchor.AnchorProvider.env());
+  const provider = anchor.getProvider() as anchor.AnchorProvider;
+  const admin = (provider.wallet as NodeWallet).payer;
+  const program = anchor.workspace.Sebi as Program<Sebi>;
+
+  const setCap = (m: TestMarket, cap: number) =>
+    program.methods
+      .setMaxCumulativeWithdraw(new anchor.BN(cap))
+      .accountsPartial({ market: m.market, admin: admin.publicKey })
+      .rpc();
+
+  // sells off, so no USDC is held back as buyback backing
+  async function fundedMarket() {
+    const m = await setupMarket(program, admin, new anchor.BN(1_000_000), { sellEnabled: false });
+    await mintTo(provider.connection, admin, m.usdcMint, m.vaultUsdc, admin, 10_000_000);
+    const treasury = await getOrCreateAssociatedTokenAccount(provider.connection, admin, m.usdcMint, admin.publicKey);
+    return { m, treasury: treasury.address };
+  }
+
+  it("allows withdrawals up to the cap and none past it", async () => {
+    const { m, treasury } = await fundedMarket();
+    const before = await tokenBalance(program, treasury);
+    await setCap(m, 5_000_000);
+
+    await withdraw(program, admin, m, treasury, 3_000_000, true);
+    await expectError(withdraw(program, admin, m, treasury, 2_000_001, true), "WithdrawCapReached");
+    await withdraw(program, admin, m, treasury, 2_000_000, true);
+    await expectError(withdraw(program, admin, m, treasury, 1, true), "WithdrawCapReached");
+
+    assert.equal(await tokenBalance(program, treasury) - before, 5_000_000);
+    assert.equal((await program.account.market.fetch(m.market)).cumulativeWithdrawn.toNumber(), 5_000_000);
+
+    // bond withdrawals are not counted against it
+    const bonds = await getOrCreateAssociatedTokenAccount(provider.connection, admin, m.bondMint, admin.publicKey);
+    await withdraw(program, admin, m, bonds.address, 10, false);
+  });
+
+  it("counts earlier withdrawals and only lets the cap come down", async () => {
+    const { m, treasury } = await fundedMarket();
+    await withdraw(program, admin, m, treasury, 4_000_000, true);
+
+    await setCap(m, 6_000_000);
+    await expectError(setCap(m, 7_000_000), "InvalidWithdrawCap");
+    await expectError(setCap(m, 0), "InvalidWithdrawCap");
+    await setCap(m, 4_000_000);
+    await expectError(withdraw(program, admin, m, treasury, 1, true), "WithdrawCapReached");
+  });
+});
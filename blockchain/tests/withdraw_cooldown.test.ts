@@ -0,0 +1,38 @@
+import * as anchor from "@coral-xyz/anchor";
+import NodeWallet from "@coral-xyz/anchor/dist/cjs/nodewallet";
+import { Program } from "@coral-xyz/anchor";
+import { Sebi } from "../target/types/sebi";
+import { getOrCreateAssociatedTokenAccount } from "@solana/spl-token";
+import { describe, it } from "node:test";
+import assert from "assert";
+import { setupMarket, tokenBalance, expectError, withdraw, TestMarket } from "./utils";
+
+describe("withdraw cooldown", () => {
+  anchor.setProvider(anchor.AnchorProvider.env());
+  const provider = anchor.getProvider() as anchor.AnchorProvider;
+  const admin = (provider.wallet as NodeWallet).payer;
+  const program = anchor.workspace.Sebi as Program<Sebi>;
+
+  const setCooldown = (m: TestMarket, secs: number) =>
+    program.methods
+      .setWithdrawCooldown(new anchor.BN(secs))
+      .accountsPartial({ market: m.market, admin: admin.publicKey })
+      .rpc();
+
+  it("rejects a second withdrawal inside the cooldown", async () => {
+    const m = await setupMarket(program, admin, new anchor.BN(1_000_000));
+    const treasury = await getOrCreateAssociatedTokenAccount(provider.connection, admin, m.bondMint, admin.publicKey);
+
+    // 0 keeps back-to-back withdrawals
+    await withdraw(program, admin, m, treasury.address, 1, false);
+    await withdraw(program, admin, m, treasury.address, 2, false);
+
+    await setCooldown(m, 3);
+    await expectError(withdraw(program, admin, m, treasury.address, 3, false), "WithdrawCooldown");
+    await new Promise((r) => setTimeout(r, 4_000));
+    await withdraw(program, admin, m, treasury.address, 3, false);
+    await expectError(withdraw(program, admin, m, treasury.address, 4, false), "WithdrawCooldown");
+
+    assert.equal(await tokenBalance(program, treasury.address), 6);
+  });
+});
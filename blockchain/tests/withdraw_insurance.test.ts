@@ -0,0 +1,72 @@
+import * as anchor from "@coral-xyz/anchor";
+import NodeWallet from "@coral-xyz/anchor/dist/cjs/nodewallet";
+import { Program } from "@coral-xyz/anchor";
+import { Sebi } from "../target/types/sebi";
+import { PublicKey } from "@solana/web3.js";
+import { getOrCreateAssociatedTokenAccount, mintTo } from "@solana/spl-token";
+import { describe, it } from "node:test";
+import assert from "assert";
+import { setupMarket, tokenBalance, expectError, TestMarket } from "./utils";
+
+describe("withdraw insurance", () => {
+  anchor.setProvider(anchor.AnchorProvider.env());
+  const provider = anchor.getProvider() as anchor.AnchorProvider;
+  const admin = (provider.wallet as NodeWallet).payer;
+  const program = anchor.workspace.Sebi as Program<Sebi>;
+
+  async function fundedInsurance(m: TestMarket, amount: number) {
+    const [insuranceVault] = PublicKey.findProgramAddressSync(
+      [Buffer.from("insurance"), m.market.toBuffer()],
+      program.programId
+    );
+    await program.methods
+      .initInsuranceVault()
+      .accountsPartial({ market: m.market, usdcMint: m.usdcMint, insuranceVault, admin: admin.publicKey })
+      .rpc();
+    const treasury = await getOrCreateAssociatedTokenAccount(provider.connection, admin, m.usdcMint, admin.publicKey);
+    await mintTo(provider.connection, admin, m.usdcMint, treasury.address, admin, amount);
+    await program.methods
+      .fundInsurance(new anchor.BN(amount))
+      .accountsPartial({ market: m.market, funder: admin.publicKey, funderUsdc: treasury.address, insuranceVault })
+      .rpc();
+    return { insuranceVault, treasury: treasury.address };
+  }
+
+  function withdrawInsurance(m: TestMarket, insuranceVault: PublicKey, destination: PublicKey, amount: number) {
+    return program.methods
+      .withdrawInsurance(new anchor.BN(amount))
+      .accountsPartial({ market: m.market, admin: admin.publicKey, insuranceVault, destination })
+      .rpc();
+  }
+
+  it("is refused while sells can still draw on the fund", async () => {
+    const m = await setupMarket(program, admin, new anchor.BN(1_000_000));
+    const { insuranceVault, treasury } = await fundedInsurance(m, 500);
+
+    await expectError(withdrawInsurance(m, insuranceVault, treasury, 500), "InsuranceBacksSells");
+    assert.equal(await tokenBalance(program, insuranceVault), 500);
+  });
+
+  it("returns the fund once the market has matured", async () => {
+    const m = await setupMarket(program, admin, new anchor.BN(1_000_000));
+    const { insuranceVault, treasury } = await fundedInsurance(m, 500);
+    await program.methods.setPhase({ matured: {} }).accountsPartial({ market: m.market, admin: admin.publicKey }).rpc();
+
+    await expectError(withdrawInsurance(m, insuranceVault, treasury, 501), "InsufficientVaultFunds");
+    await withdrawInsurance(m, insuranceVault, treasury, 300);
+
+    assert.equal(await tokenBalance(program, insuranceVault), 200);
+    assert.equal(await tokenBalance(program, treasury), 300);
+    assert.equal((await program.account.market.fetch(m.market)).insuranceBalance.toNumber(), 200);
+  });
+
+  it("returns the fund of a terminated market", async () => {
+    const m = await setupMarket(program, admin, new anchor.BN(1_000_000));
+    const { insuranceVault, treasury } = await fundedInsurance(m, 500);
+    await program.methods.terminate().accountsPartial({ market: m.market, admin: admin.publicKey }).rpc();
+
+    await withdrawInsurance(m, insuranceVault, treasury, 500);
+    assert.equal(await tokenBalance(program, insuranceVault), 0);
+    assert.equal((await program.account.market.fetch(m.market)).insuranceBalance.toNumber(), 0);
+  });
+});